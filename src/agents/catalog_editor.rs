@@ -1,8 +1,13 @@
+use crate::agents::catalog_formatter::{is_sorted, sort_table};
+use crate::agents::usage_analyzer::resolve_reference;
 use crate::error::{GvcError, Result};
+use crate::gradle::BuildScriptScanner;
 use crate::maven::parse_maven_coordinate;
+use crate::utils::diff::FileEdit;
+use crate::utils::toml::TomlUtils;
 use std::fs;
 use std::path::{Path, PathBuf};
-use toml_edit::{DocumentMut, InlineTable, Item, Table, Value, value};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Key, Table, Value, value};
 
 #[derive(Debug)]
 pub struct AddResult {
@@ -17,17 +22,137 @@ pub enum AddTargetKind {
     Plugin,
 }
 
+/// Outcome of removing a catalog entry
+#[derive(Debug)]
+pub struct RemoveResult {
+    pub alias: String,
+    pub target: AddTargetKind,
+    /// The `[versions]` alias deleted because nothing else referenced it
+    pub removed_version_alias: Option<String>,
+    /// The `[versions]` alias no entry references any more, kept because build logic reads it
+    pub kept_version_alias: Option<String>,
+    /// Bundles the entry was dropped from
+    pub bundles: Vec<String>,
+}
+
+/// A catalog alias rename, computed without touching the file
+#[derive(Debug)]
+pub struct RenamePlan {
+    /// Catalog section holding the alias
+    pub section: &'static str,
+    pub from: String,
+    pub to: String,
+    /// Bundles whose member list mentions the alias
+    pub bundles: Vec<String>,
+    /// Libraries and plugins whose `version.ref` points at a renamed version alias
+    pub version_refs: Vec<String>,
+    pub edit: FileEdit,
+}
+
+/// A hard-coded dependency or plugin being moved into the catalog
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MigrationTarget {
+    Library {
+        group: String,
+        artifact: String,
+        version: String,
+    },
+    Plugin {
+        id: String,
+        version: String,
+    },
+}
+
+impl MigrationTarget {
+    pub fn section(&self) -> &'static str {
+        match self {
+            MigrationTarget::Library { .. } => "libraries",
+            MigrationTarget::Plugin { .. } => "plugins",
+        }
+    }
+
+    /// Library group or plugin id namespace; entries of one family tend to share a version
+    fn family(&self) -> &str {
+        match self {
+            MigrationTarget::Library { group, .. } => group,
+            MigrationTarget::Plugin { id, .. } => {
+                id.rsplit_once('.').map(|(family, _)| family).unwrap_or(id)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MigrationTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationTarget::Library {
+                group,
+                artifact,
+                version,
+            } => write!(f, "{}:{}:{}", group, artifact, version),
+            MigrationTarget::Plugin { id, version } => write!(f, "{} {}", id, version),
+        }
+    }
+}
+
+/// How a migrated literal maps onto the catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationOutcome {
+    /// A new entry was added, pointing at this `[versions]` alias
+    Created {
+        alias: String,
+        version_alias: String,
+    },
+    /// The catalog already declares the same coordinate and version
+    Existing { alias: String },
+    /// The catalog declares the coordinate with another version; the literal stays
+    Conflict {
+        alias: String,
+        catalog_version: String,
+    },
+}
+
+/// Libraries of one group that repeat the same literal version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateVersions {
+    pub group: String,
+    pub version: String,
+    /// Library aliases declaring the version inline, in catalog order
+    pub aliases: Vec<String>,
+    /// `[versions]` alias the libraries will reference
+    pub version_alias: String,
+    /// Whether `version_alias` already exists with this value
+    pub reused: bool,
+}
+
+/// A `[bundles]` entry with its member aliases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleInfo {
+    pub name: String,
+    pub members: Vec<String>,
+    /// Members that don't match any alias in `[libraries]`
+    pub missing: Vec<String>,
+}
+
 pub struct CatalogEditor {
     catalog_path: PathBuf,
+    scripts_root: Option<PathBuf>,
 }
 
 impl CatalogEditor {
     pub fn new<P: AsRef<Path>>(catalog_path: P) -> Self {
         Self {
             catalog_path: catalog_path.as_ref().to_path_buf(),
+            scripts_root: None,
         }
     }
 
+    /// Keep version aliases that build logic under `root` reads as `libs.versions.*`
+    pub fn with_build_scripts<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.scripts_root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
     pub fn add_library(
         &self,
         coordinate: &str,
@@ -87,7 +212,7 @@ impl CatalogEditor {
         );
         entry.fmt();
 
-        libraries.insert(&alias, Item::Value(Value::InlineTable(entry)));
+        insert_entry(libraries, &alias, Item::Value(Value::InlineTable(entry)));
 
         if updated_alias {
             println!(
@@ -164,7 +289,7 @@ impl CatalogEditor {
         );
         entry.fmt();
 
-        plugins.insert(&alias, Item::Value(Value::InlineTable(entry)));
+        insert_entry(plugins, &alias, Item::Value(Value::InlineTable(entry)));
 
         if updated_alias {
             println!(
@@ -182,6 +307,565 @@ impl CatalogEditor {
         })
     }
 
+    /// Remove a library or plugin entry, cleaning up bundles and orphaned version aliases
+    ///
+    /// Libraries are searched first unless `target` restricts the lookup.
+    pub fn remove_entry(&self, alias: &str, target: Option<AddTargetKind>) -> Result<RemoveResult> {
+        let mut doc = self.load_document()?;
+
+        let sections: &[(&str, AddTargetKind)] = match target {
+            Some(AddTargetKind::Library) => &[("libraries", AddTargetKind::Library)],
+            Some(AddTargetKind::Plugin) => &[("plugins", AddTargetKind::Plugin)],
+            None => &[
+                ("libraries", AddTargetKind::Library),
+                ("plugins", AddTargetKind::Plugin),
+            ],
+        };
+
+        let (section, kind, key) = sections
+            .iter()
+            .find_map(|(section, kind)| {
+                doc.get(section)
+                    .and_then(|v| v.as_table())
+                    .and_then(|table| find_library_alias(table, alias))
+                    .map(|key| (*section, *kind, key))
+            })
+            .ok_or_else(|| {
+                GvcError::ProjectValidation(format!(
+                    "No library or plugin with alias '{}' in the catalog",
+                    alias
+                ))
+            })?;
+
+        let removed = doc[section]
+            .as_table_mut()
+            .and_then(|table| table.remove(&key))
+            .ok_or_else(|| {
+                GvcError::TomlParsing(format!("Failed to access [{}] table", section))
+            })?;
+
+        let mut bundles = Vec::new();
+        if matches!(kind, AddTargetKind::Library) {
+            if let Some(table) = doc.get_mut("bundles").and_then(|v| v.as_table_mut()) {
+                let wanted = TomlUtils::accessor_key(&key);
+                for (name, item) in table.iter_mut() {
+                    let Some(members) = item.as_array_mut() else {
+                        continue;
+                    };
+                    let matches: Vec<usize> = members
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, m)| {
+                            m.as_str().map(TomlUtils::accessor_key) == Some(wanted.clone())
+                        })
+                        .map(|(index, _)| index)
+                        .collect();
+                    for index in matches.iter().rev() {
+                        TomlUtils::remove_array_value(members, *index);
+                    }
+                    if !matches.is_empty() {
+                        bundles.push(name.to_string());
+                    }
+                }
+            }
+        }
+
+        let orphaned = TomlUtils::extract_version_ref(&removed)
+            .filter(|version_ref| !version_alias_in_use(&doc, version_ref));
+        let (kept_version_alias, orphaned) = match orphaned {
+            Some(version_ref) if self.version_alias_read_by_scripts(&doc, &version_ref)? => {
+                (Some(version_ref), None)
+            }
+            orphaned => (None, orphaned),
+        };
+        let removed_version_alias = orphaned.filter(|version_ref| {
+            doc.get_mut("versions")
+                .and_then(|v| v.as_table_mut())
+                .and_then(|versions| versions.remove(version_ref))
+                .is_some()
+        });
+
+        self.write_document(&doc)?;
+
+        Ok(RemoveResult {
+            alias: key,
+            target: kind,
+            removed_version_alias,
+            kept_version_alias,
+            bundles,
+        })
+    }
+
+    /// Whether build logic reads `version_ref` directly (`libs.versions.x`, `findVersion("x")`)
+    fn version_alias_read_by_scripts(&self, doc: &DocumentMut, version_ref: &str) -> Result<bool> {
+        let Some(root) = &self.scripts_root else {
+            return Ok(false);
+        };
+        let aliases: Vec<String> = doc
+            .get("versions")
+            .and_then(|v| v.as_table())
+            .map(|versions| versions.iter().map(|(key, _)| key.to_string()).collect())
+            .unwrap_or_default();
+
+        Ok(BuildScriptScanner::new(root)
+            .catalog_references("libs")?
+            .iter()
+            .filter(|reference| reference.section == "versions")
+            .any(|reference| {
+                resolve_reference(&aliases, "versions", reference).as_deref() == Some(version_ref)
+            }))
+    }
+
+    /// Work out the catalog changes for renaming `old` to `new`
+    ///
+    /// The alias is looked up in every section unless `section` names one; an alias
+    /// found in several sections is rejected as ambiguous. Library renames update
+    /// bundle members and version alias renames update every `version.ref`.
+    pub fn plan_rename(&self, old: &str, new: &str, section: Option<&str>) -> Result<RenamePlan> {
+        if !is_valid_alias(new) {
+            return Err(GvcError::ProjectValidation(format!(
+                "'{}' is not a valid alias; use letters, digits, '-', '_' or '.', starting with a letter",
+                new
+            )));
+        }
+
+        let original = fs::read_to_string(&self.catalog_path)?;
+        let mut doc = self.load_document()?;
+
+        let matches: Vec<(&'static str, String)> = RENAMABLE_SECTIONS
+            .iter()
+            .filter(|candidate| section.is_none_or(|section| section == **candidate))
+            .filter_map(|candidate| {
+                doc.get(candidate)
+                    .and_then(|v| v.as_table())
+                    .and_then(|table| find_library_alias(table, old))
+                    .map(|key| (*candidate, key))
+            })
+            .collect();
+
+        let (section, key) = match matches.as_slice() {
+            [] => {
+                return Err(GvcError::ProjectValidation(format!(
+                    "No catalog entry named '{}'",
+                    old
+                )));
+            }
+            [single] => single.clone(),
+            several => {
+                let sections: Vec<String> = several
+                    .iter()
+                    .map(|(section, _)| format!("[{}]", section))
+                    .collect();
+                return Err(GvcError::ProjectValidation(format!(
+                    "'{}' exists in {}; pass --section to choose one",
+                    old,
+                    sections.join(" and ")
+                )));
+            }
+        };
+
+        let table = doc[section].as_table_mut().ok_or_else(|| {
+            GvcError::TomlParsing(format!("Failed to access [{}] table", section))
+        })?;
+        if let Some(existing) = find_library_alias(table, new).filter(|existing| existing != &key) {
+            return Err(GvcError::ProjectValidation(format!(
+                "[{}] already contains '{}'",
+                section, existing
+            )));
+        }
+
+        let sorted = is_sorted(table);
+        rename_key(table, &key, new);
+        if sorted {
+            sort_table(table);
+        }
+
+        let mut bundles = Vec::new();
+        let mut version_refs = Vec::new();
+        match section {
+            "libraries" => {
+                if let Some(table) = doc.get_mut("bundles").and_then(|v| v.as_table_mut()) {
+                    let wanted = TomlUtils::accessor_key(&key);
+                    for (name, item) in table.iter_mut() {
+                        let Some(members) = item.as_array_mut() else {
+                            continue;
+                        };
+                        let mut renamed = false;
+                        for member in members.iter_mut() {
+                            if member.as_str().map(TomlUtils::accessor_key) == Some(wanted.clone())
+                            {
+                                let decor = member.decor().clone();
+                                *member = Value::from(new);
+                                *member.decor_mut() = decor;
+                                renamed = true;
+                            }
+                        }
+                        if renamed {
+                            bundles.push(name.to_string());
+                        }
+                    }
+                }
+            }
+            "versions" => {
+                for entries in ["libraries", "plugins"] {
+                    let Some(table) = doc.get_mut(entries).and_then(|v| v.as_table_mut()) else {
+                        continue;
+                    };
+                    for (alias, item) in table.iter_mut() {
+                        if TomlUtils::uses_version_ref(item, &key)
+                            && TomlUtils::set_version_ref(item, new)
+                        {
+                            version_refs.push(alias.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(RenamePlan {
+            section,
+            from: key,
+            to: new.to_string(),
+            bundles,
+            version_refs,
+            edit: FileEdit {
+                path: self.catalog_path.clone(),
+                original,
+                updated: doc.to_string(),
+            },
+        })
+    }
+
+    /// Work out the catalog entries for hard-coded coordinates without writing them
+    ///
+    /// Coordinates already in the catalog reuse their alias, and new entries share an
+    /// existing `[versions]` alias when its value matches.
+    pub fn plan_migration(
+        &self,
+        targets: &[MigrationTarget],
+    ) -> Result<(Vec<MigrationOutcome>, FileEdit)> {
+        let original = fs::read_to_string(&self.catalog_path)?;
+        let mut doc = self.load_document()?;
+
+        let mut outcomes = Vec::new();
+        for target in targets {
+            let outcome = match target {
+                MigrationTarget::Library {
+                    group,
+                    artifact,
+                    version,
+                } => migrate_entry(
+                    &mut doc,
+                    target,
+                    version,
+                    |item| library_item_matches(item, group, artifact),
+                    || generate_library_alias(group, artifact),
+                    || generate_version_alias(group),
+                    || format!("{}:{}", group, artifact),
+                )?,
+                MigrationTarget::Plugin { id, version } => migrate_entry(
+                    &mut doc,
+                    target,
+                    version,
+                    |item| plugin_item_matches(item, id),
+                    || generate_plugin_alias(id),
+                    || generate_plugin_version_alias(id),
+                    || id.clone(),
+                )?,
+            };
+            outcomes.push(outcome);
+        }
+
+        Ok((
+            outcomes,
+            FileEdit {
+                path: self.catalog_path.clone(),
+                original,
+                updated: doc.to_string(),
+            },
+        ))
+    }
+
+    /// Find libraries of the same group declaring an identical literal version
+    ///
+    /// Each group gets the `[versions]` alias `gvc add` would generate for it, or an
+    /// existing alias with the same value already used by that group. A generated
+    /// alias holding another value gets a numeric suffix.
+    pub fn find_duplicate_versions(&self) -> Result<Vec<DuplicateVersions>> {
+        let doc = self.load_document()?;
+        let Some(libraries) = doc.get("libraries").and_then(|v| v.as_table()) else {
+            return Ok(Vec::new());
+        };
+        let versions = doc.get("versions").and_then(|v| v.as_table());
+
+        let mut duplicates: Vec<DuplicateVersions> = Vec::new();
+        for (alias, item) in libraries.iter() {
+            let (Some((group, _)), Some(version)) = (
+                TomlUtils::extract_group_artifact(item),
+                literal_version(item),
+            ) else {
+                continue;
+            };
+            match duplicates
+                .iter_mut()
+                .find(|d| d.group == group && d.version == version)
+            {
+                Some(duplicate) => duplicate.aliases.push(alias.to_string()),
+                None => duplicates.push(DuplicateVersions {
+                    group,
+                    version,
+                    aliases: vec![alias.to_string()],
+                    version_alias: String::new(),
+                    reused: false,
+                }),
+            }
+        }
+        duplicates.retain(|duplicate| duplicate.aliases.len() > 1);
+
+        // Aliases chosen for earlier groups count as taken with their version
+        let mut planned: Vec<(String, String)> = Vec::new();
+        for duplicate in &mut duplicates {
+            let value_of = |key: &str| -> Option<String> {
+                let key = TomlUtils::accessor_key(key);
+                versions
+                    .and_then(|versions| {
+                        versions
+                            .iter()
+                            .find(|(existing, _)| TomlUtils::accessor_key(existing) == key)
+                            .and_then(|(_, value)| value.as_str())
+                    })
+                    .map(str::to_string)
+                    .or_else(|| {
+                        planned
+                            .iter()
+                            .find(|(existing, _)| TomlUtils::accessor_key(existing) == key)
+                            .map(|(_, version)| version.clone())
+                    })
+            };
+
+            let shared = libraries
+                .iter()
+                .filter(|(_, item)| entry_family(item).as_deref() == Some(&duplicate.group))
+                .filter_map(|(_, item)| TomlUtils::extract_version_ref(item))
+                .find(|reference| value_of(reference).as_deref() == Some(&duplicate.version));
+            let version_alias = shared.unwrap_or_else(|| {
+                let generated = generate_version_alias(&duplicate.group);
+                std::iter::once(generated.clone())
+                    .chain((2..).map(|n| format!("{}-{}", generated, n)))
+                    .find(|candidate| {
+                        value_of(candidate).is_none_or(|value| value == duplicate.version)
+                    })
+                    .expect("an unused suffix exists")
+            });
+
+            // Reuse the catalog's spelling of a key that differs only in separators
+            let version_alias = versions
+                .and_then(|versions| {
+                    let key = TomlUtils::accessor_key(&version_alias);
+                    versions
+                        .iter()
+                        .map(|(existing, _)| existing)
+                        .find(|existing| TomlUtils::accessor_key(existing) == key)
+                        .map(str::to_string)
+                })
+                .unwrap_or(version_alias);
+            duplicate.reused = value_of(&version_alias).is_some();
+            planned.push((version_alias.clone(), duplicate.version.clone()));
+            duplicate.version_alias = version_alias;
+        }
+
+        Ok(duplicates)
+    }
+
+    /// Point the libraries of each group at its shared `[versions]` alias without writing
+    pub fn plan_dedupe_versions(&self, duplicates: &[DuplicateVersions]) -> Result<FileEdit> {
+        let original = fs::read_to_string(&self.catalog_path)?;
+        let mut doc = self.load_document()?;
+        ensure_section(&mut doc, "versions");
+
+        for duplicate in duplicates {
+            let versions = doc["versions"]
+                .as_table_mut()
+                .ok_or_else(|| GvcError::TomlParsing("Failed to access [versions] table".into()))?;
+            let key = TomlUtils::accessor_key(&duplicate.version_alias);
+            let existing = versions
+                .iter()
+                .map(|(existing, _)| existing.to_string())
+                .find(|existing| TomlUtils::accessor_key(existing) == key);
+            let version_alias = match existing {
+                Some(existing) => existing,
+                None => {
+                    insert_entry(
+                        versions,
+                        &duplicate.version_alias,
+                        value(duplicate.version.as_str()),
+                    );
+                    duplicate.version_alias.clone()
+                }
+            };
+
+            let libraries = doc["libraries"].as_table_mut().ok_or_else(|| {
+                GvcError::TomlParsing("Failed to access [libraries] table".into())
+            })?;
+            for alias in &duplicate.aliases {
+                let Some(item) = libraries.get_mut(alias) else {
+                    continue;
+                };
+                if !use_version_ref(item, &version_alias) {
+                    return Err(GvcError::ProjectValidation(format!(
+                        "Library '{}' has no literal version to replace",
+                        alias
+                    )));
+                }
+            }
+        }
+
+        Ok(FileEdit {
+            path: self.catalog_path.clone(),
+            original,
+            updated: doc.to_string(),
+        })
+    }
+
+    /// Remove a `[versions]` alias that no library or plugin references
+    pub fn remove_version(&self, alias: &str) -> Result<()> {
+        let mut doc = self.load_document()?;
+
+        if version_alias_in_use(&doc, alias) {
+            return Err(GvcError::ProjectValidation(format!(
+                "Version alias '{}' is still referenced from the catalog",
+                alias
+            )));
+        }
+
+        doc.get_mut("versions")
+            .and_then(|v| v.as_table_mut())
+            .and_then(|versions| versions.remove(alias))
+            .ok_or_else(|| {
+                GvcError::ProjectValidation(format!("No version alias '{}' in the catalog", alias))
+            })?;
+
+        self.write_document(&doc)
+    }
+
+    /// Add library aliases to a bundle, creating the bundle when needed
+    ///
+    /// Returns the aliases that were added; members already present are skipped.
+    pub fn add_to_bundle(&self, bundle: &str, aliases: &[String]) -> Result<Vec<String>> {
+        let bundle = sanitize_alias(bundle);
+        if bundle.is_empty() {
+            return Err(GvcError::ProjectValidation(
+                "Bundle name may not be empty".into(),
+            ));
+        }
+
+        let mut doc = self.load_document()?;
+
+        let resolved = {
+            let libraries = doc.get("libraries").and_then(|v| v.as_table());
+            aliases
+                .iter()
+                .map(|alias| {
+                    libraries
+                        .and_then(|libraries| find_library_alias(libraries, alias))
+                        .ok_or_else(|| {
+                            GvcError::ProjectValidation(format!(
+                                "Library alias '{}' does not exist in [libraries]",
+                                alias
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        ensure_section(&mut doc, "bundles");
+        let bundles = doc["bundles"]
+            .as_table_mut()
+            .ok_or_else(|| GvcError::TomlParsing("Failed to access [bundles] table".into()))?;
+
+        if !bundles.contains_key(&bundle) {
+            insert_entry(bundles, &bundle, value(Array::new()));
+        }
+        let members = bundles
+            .get_mut(&bundle)
+            .and_then(|item| item.as_array_mut())
+            .ok_or_else(|| {
+                GvcError::ProjectValidation(format!("Bundle '{}' is not an array", bundle))
+            })?;
+
+        let mut added = Vec::new();
+        for alias in resolved {
+            let present = members
+                .iter()
+                .filter_map(|m| m.as_str())
+                .any(|member| TomlUtils::accessor_key(member) == TomlUtils::accessor_key(&alias));
+            if !present && !added.contains(&alias) {
+                TomlUtils::push_array_value(members, alias.as_str());
+                added.push(alias);
+            }
+        }
+
+        if !added.is_empty() {
+            self.write_document(&doc)?;
+        }
+
+        Ok(added)
+    }
+
+    /// Remove aliases from a bundle, or the whole bundle when `aliases` is empty
+    ///
+    /// Returns the members that were removed.
+    pub fn remove_from_bundle(&self, bundle: &str, aliases: &[String]) -> Result<Vec<String>> {
+        let mut doc = self.load_document()?;
+        let bundles = doc
+            .get_mut("bundles")
+            .and_then(|v| v.as_table_mut())
+            .filter(|bundles| bundles.contains_key(bundle))
+            .ok_or_else(|| {
+                GvcError::ProjectValidation(format!("Bundle '{}' does not exist", bundle))
+            })?;
+
+        let removed = if aliases.is_empty() {
+            let item = bundles.remove(bundle).unwrap_or_default();
+            array_members(&item)
+        } else {
+            let members = bundles
+                .get_mut(bundle)
+                .and_then(|item| item.as_array_mut())
+                .ok_or_else(|| {
+                    GvcError::ProjectValidation(format!("Bundle '{}' is not an array", bundle))
+                })?;
+
+            let mut removed = Vec::new();
+            for alias in aliases {
+                let key = TomlUtils::accessor_key(alias);
+                let index = members
+                    .iter()
+                    .position(|m| m.as_str().map(TomlUtils::accessor_key) == Some(key.clone()))
+                    .ok_or_else(|| {
+                        GvcError::ProjectValidation(format!(
+                            "'{}' is not a member of bundle '{}'",
+                            alias, bundle
+                        ))
+                    })?;
+                if let Some(member) = TomlUtils::remove_array_value(members, index) {
+                    removed.extend(member.as_str().map(str::to_string));
+                }
+            }
+            removed
+        };
+
+        self.write_document(&doc)?;
+        Ok(removed)
+    }
+
+    /// Read all bundles from the catalog
+    pub fn list_bundles(&self) -> Result<Vec<BundleInfo>> {
+        let doc = self.load_document()?;
+        Ok(read_bundles(&doc))
+    }
+
     fn load_document(&self) -> Result<DocumentMut> {
         let content = fs::read_to_string(&self.catalog_path).map_err(|e| {
             GvcError::TomlParsing(format!(
@@ -211,14 +895,308 @@ impl CatalogEditor {
     }
 }
 
+/// Collect bundles and flag members that don't reference an existing library
+pub fn read_bundles(doc: &DocumentMut) -> Vec<BundleInfo> {
+    let Some(bundles) = doc.get("bundles").and_then(|v| v.as_table()) else {
+        return Vec::new();
+    };
+    let libraries = doc.get("libraries").and_then(|v| v.as_table());
+
+    bundles
+        .iter()
+        .map(|(name, item)| {
+            let members = array_members(item);
+            let missing = members
+                .iter()
+                .filter(|member| {
+                    libraries
+                        .and_then(|libraries| find_library_alias(libraries, member))
+                        .is_none()
+                })
+                .cloned()
+                .collect();
+
+            BundleInfo {
+                name: name.to_string(),
+                members,
+                missing,
+            }
+        })
+        .collect()
+}
+
+/// Find or create the catalog entry for one migration target
+fn migrate_entry(
+    doc: &mut DocumentMut,
+    target: &MigrationTarget,
+    version: &str,
+    matches: impl Fn(&Item) -> bool,
+    alias: impl FnOnce() -> String,
+    version_alias: impl FnOnce() -> String,
+    coordinate: impl FnOnce() -> String,
+) -> Result<MigrationOutcome> {
+    let section = target.section();
+    ensure_section(doc, "versions");
+    ensure_section(doc, section);
+
+    let existing = doc[section].as_table().and_then(|table| {
+        table
+            .iter()
+            .find(|(_, item)| matches(item))
+            .map(|(key, item)| (key.to_string(), entry_version(doc, item)))
+    });
+    if let Some((alias, catalog_version)) = existing {
+        return Ok(match catalog_version {
+            Some(catalog_version) if catalog_version != version => MigrationOutcome::Conflict {
+                alias,
+                catalog_version,
+            },
+            _ => MigrationOutcome::Existing { alias },
+        });
+    }
+
+    let alias = unique_key(doc[section].as_table(), &alias());
+
+    // Prefer a version alias already shared by entries of the same family at this version
+    let generated = version_alias();
+    let versions = doc["versions"]
+        .as_table()
+        .ok_or_else(|| GvcError::TomlParsing("Failed to access [versions] table".into()))?;
+    let family = target.family();
+    let shared = ["libraries", "plugins"]
+        .iter()
+        .filter_map(|section| doc.get(section).and_then(|v| v.as_table()))
+        .flat_map(|table| table.iter())
+        .filter(|(_, item)| entry_family(item).as_deref() == Some(family))
+        .filter_map(|(_, item)| TomlUtils::extract_version_ref(item))
+        .find(|reference| versions.get(reference).and_then(|v| v.as_str()) == Some(version));
+    let version_alias = match (shared, versions.get(&generated).and_then(|v| v.as_str())) {
+        (Some(shared), _) => shared,
+        (None, None) => generated,
+        (None, Some(existing)) if existing == version => generated,
+        (None, Some(_)) => unique_key(Some(versions), &format!("{}-version", alias)),
+    };
+
+    let versions = doc["versions"]
+        .as_table_mut()
+        .ok_or_else(|| GvcError::TomlParsing("Failed to access [versions] table".into()))?;
+    if !versions.contains_key(&version_alias) {
+        insert_entry(versions, &version_alias, value(version));
+    }
+
+    let mut entry = InlineTable::new();
+    let id_key = if section == "plugins" { "id" } else { "module" };
+    entry.insert(id_key, Value::from(coordinate()));
+    entry.insert(
+        "version",
+        Value::InlineTable(version_ref_inline(&version_alias)),
+    );
+    entry.fmt();
+
+    let table = doc[section]
+        .as_table_mut()
+        .ok_or_else(|| GvcError::TomlParsing(format!("Failed to access [{}] table", section)))?;
+    insert_entry(table, &alias, Item::Value(Value::InlineTable(entry)));
+
+    Ok(MigrationOutcome::Created {
+        alias,
+        version_alias,
+    })
+}
+
+/// The version written directly on a library, not through `version.ref`
+fn literal_version(item: &Item) -> Option<String> {
+    match item.as_str() {
+        Some(raw) => parse_maven_coordinate(raw).and_then(|(_, _, version)| version),
+        None => TomlUtils::extract_version(item),
+    }
+}
+
+/// Replace a library's literal version with `version = { ref = "..." }`
+fn use_version_ref(item: &mut Item, version_alias: &str) -> bool {
+    let reference = Value::InlineTable(version_ref_inline(version_alias));
+
+    if let Some(Value::String(raw)) = item.as_value() {
+        let Some((group, artifact, Some(_))) = parse_maven_coordinate(raw.value()) else {
+            return false;
+        };
+        let mut table = InlineTable::new();
+        table.insert("module", Value::from(format!("{}:{}", group, artifact)));
+        table.insert("version", reference);
+        table.fmt();
+
+        let mut expanded = Value::InlineTable(table);
+        *expanded.decor_mut() = raw.decor().clone();
+        *item = Item::Value(expanded);
+        return true;
+    }
+
+    if let Some(table) = item.as_inline_table_mut() {
+        let Some(version) = table.get_mut("version").filter(|version| version.is_str()) else {
+            return false;
+        };
+        let decor = version.decor().clone();
+        *version = reference;
+        *version.decor_mut() = decor;
+        return true;
+    }
+
+    if let Some(table) = item.as_table_mut() {
+        if !table.get("version").is_some_and(|version| version.is_str()) {
+            return false;
+        }
+        table.insert("version", Item::Value(reference));
+        return true;
+    }
+
+    false
+}
+
+/// Group of a library or id namespace of a plugin, e.g. `org.jetbrains.kotlin`
+fn entry_family(item: &Item) -> Option<String> {
+    if let Some((group, _)) = TomlUtils::extract_group_artifact(item) {
+        return Some(group);
+    }
+    let id = item
+        .as_inline_table()
+        .and_then(|table| table.get("id"))
+        .or_else(|| {
+            item.as_table()
+                .and_then(|table| table.get("id")?.as_value())
+        })
+        .and_then(|id| id.as_str())?;
+    id.rsplit_once('.').map(|(family, _)| family.to_string())
+}
+
+/// The concrete version of a library or plugin entry, following `version.ref`
+pub(crate) fn entry_version(doc: &DocumentMut, item: &Item) -> Option<String> {
+    if let Some(raw) = item.as_str() {
+        return raw.rsplit_once(':').map(|(_, version)| version.to_string());
+    }
+
+    TomlUtils::extract_version(item).or_else(|| {
+        TomlUtils::extract_version_ref(item).and_then(|reference| {
+            doc.get("versions")
+                .and_then(|versions| versions.get(&reference))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+    })
+}
+
+/// `base`, or `base-2`, `base-3`, ... when the accessor is already taken
+fn unique_key(table: Option<&Table>, base: &str) -> String {
+    let taken = |key: &str| table.is_some_and(|table| find_library_alias(table, key).is_some());
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken(candidate))
+        .expect("an unused suffix exists")
+}
+
+/// Sections whose keys `plan_rename` can rename
+const RENAMABLE_SECTIONS: &[&str] = &["libraries", "plugins", "bundles", "versions"];
+
+/// Gradle accepts aliases made of letters, digits and `-`, `_`, `.` separators
+fn is_valid_alias(alias: &str) -> bool {
+    alias.starts_with(|c: char| c.is_ascii_alphabetic())
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Rename a key in place, keeping its position and the comments above it
+fn rename_key(table: &mut Table, old: &str, new: &str) {
+    let entries: Vec<(Key, Item)> = table
+        .iter()
+        .filter_map(|(key, _)| table.get_key_value(key))
+        .map(|(key, item)| (key.clone(), item.clone()))
+        .collect();
+
+    table.clear();
+    for (key, item) in entries {
+        let key = if key.get() == old {
+            Key::new(new).with_leaf_decor(key.leaf_decor().clone())
+        } else {
+            key
+        };
+        table.insert_formatted(&key, item);
+    }
+}
+
+/// Whether any library or plugin still references the `[versions]` alias
+fn version_alias_in_use(doc: &DocumentMut, version_ref: &str) -> bool {
+    ["libraries", "plugins"].iter().any(|section| {
+        doc.get(section)
+            .and_then(|v| v.as_table())
+            .is_some_and(|table| {
+                table
+                    .iter()
+                    .any(|(_, item)| TomlUtils::uses_version_ref(item, version_ref))
+            })
+    })
+}
+
+fn array_members(item: &Item) -> Vec<String> {
+    item.as_array()
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find the `[libraries]` key matching `alias`, using Gradle's accessor normalization
+fn find_library_alias(libraries: &Table, alias: &str) -> Option<String> {
+    let wanted = TomlUtils::accessor_key(alias);
+    libraries
+        .iter()
+        .map(|(key, _)| key)
+        .find(|key| TomlUtils::accessor_key(key) == wanted)
+        .map(str::to_string)
+}
+
 fn ensure_section(doc: &mut DocumentMut, name: &str) {
     if !doc.contains_key(name) {
         let mut table = Table::new();
         table.set_implicit(false);
+        // Catalogs conventionally open with [versions]; keep a blank line before the next table
+        if name == "versions" {
+            table.set_position(-1);
+            let first = doc
+                .iter_mut()
+                .filter_map(|(_, item)| item.as_table_mut())
+                .min_by_key(|table| table.position());
+            if let Some(first) = first {
+                let prefix = first
+                    .decor()
+                    .prefix()
+                    .and_then(|p| p.as_str())
+                    .unwrap_or("");
+                if !prefix.starts_with('\n') {
+                    let prefix = format!("\n{}", prefix);
+                    first.decor_mut().set_prefix(prefix);
+                }
+            }
+        }
         doc[name] = Item::Table(table);
     }
 }
 
+/// Insert a new entry, keeping the section sorted when it already was
+pub(crate) fn insert_entry(table: &mut Table, key: &str, item: Item) {
+    let sorted = is_sorted(table);
+    table.insert(key, item);
+    if sorted {
+        sort_table(table);
+    }
+}
+
 fn upsert_version_alias(table: &mut Table, key: &str, version: String) -> Result<bool> {
     if let Some(existing) = table.get_mut(key) {
         if let Some(existing_version) = existing.as_str() {
@@ -236,7 +1214,7 @@ fn upsert_version_alias(table: &mut Table, key: &str, version: String) -> Result
         )));
     }
 
-    table.insert(key, value(version));
+    insert_entry(table, key, value(version));
     Ok(true)
 }
 
@@ -499,4 +1477,330 @@ mod tests {
         assert_eq!(id, "org.jetbrains.kotlin.jvm");
         assert_eq!(version, "1.9.0");
     }
+
+    fn editor_with(content: &str) -> (tempfile::TempDir, CatalogEditor) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        fs::write(&path, content).unwrap();
+        let editor = CatalogEditor::new(&path);
+        (dir, editor)
+    }
+
+    #[test]
+    fn rename_library_updates_bundles_and_keeps_comments() {
+        let (_dir, editor) = editor_with(
+            "[libraries]\n# HTTP client\nktor-core = { module = \"io.ktor:ktor-client-core\", version = \"2.3.12\" }\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\n\n[bundles]\nnetwork = [\"okhttp\", \"ktor_core\"]\n",
+        );
+
+        let plan = editor
+            .plan_rename("ktor-core", "ktor-client-core", None)
+            .unwrap();
+        assert_eq!(plan.section, "libraries");
+        assert_eq!(plan.bundles, vec!["network".to_string()]);
+        assert_eq!(
+            plan.edit.updated,
+            "[libraries]\n# HTTP client\nktor-client-core = { module = \"io.ktor:ktor-client-core\", version = \"2.3.12\" }\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\n\n[bundles]\nnetwork = [\"okhttp\", \"ktor-client-core\"]\n"
+        );
+    }
+
+    #[test]
+    fn rename_version_alias_rewrites_refs_and_rejects_ambiguity() {
+        let (_dir, editor) = editor_with(
+            "[versions]\nktor = \"2.3.12\"\n\n[libraries]\nktor = { module = \"io.ktor:ktor-bom\", version.ref = \"ktor\" }\nktor-core = { module = \"io.ktor:ktor-client-core\", version = { ref = \"ktor\" } }\n",
+        );
+
+        let err = editor.plan_rename("ktor", "ktorVersion", None).unwrap_err();
+        assert!(err.to_string().contains("--section"));
+
+        let plan = editor
+            .plan_rename("ktor", "ktor-client", Some("versions"))
+            .unwrap();
+        assert_eq!(plan.version_refs, vec!["ktor", "ktor-core"]);
+        assert!(plan.edit.updated.contains("ktor-client = \"2.3.12\""));
+        assert!(plan.edit.updated.contains("version.ref = \"ktor-client\""));
+        assert!(
+            plan.edit
+                .updated
+                .contains("version = { ref = \"ktor-client\" }")
+        );
+    }
+
+    const BUNDLE_CATALOG: &str = r#"[libraries]
+ktor-core = { module = "io.ktor:ktor-client-core", version = "2.3.12" }
+ktor-cio = { module = "io.ktor:ktor-client-cio", version = "2.3.12" }
+
+[bundles]
+ktor = ["ktor-core"]
+"#;
+
+    #[test]
+    fn adds_members_to_existing_and_new_bundles() {
+        let (_dir, editor) = editor_with(BUNDLE_CATALOG);
+
+        let added = editor
+            .add_to_bundle("ktor", &["ktor_cio".to_string(), "ktor-core".to_string()])
+            .unwrap();
+        assert_eq!(added, vec!["ktor-cio".to_string()]);
+
+        editor
+            .add_to_bundle("network", &["ktor-cio".to_string()])
+            .unwrap();
+
+        let content = fs::read_to_string(&editor.catalog_path).unwrap();
+        assert!(content.contains("ktor = [\"ktor-core\", \"ktor-cio\"]"));
+        assert!(content.contains("network = [\"ktor-cio\"]"));
+    }
+
+    #[test]
+    fn rejects_unknown_bundle_members() {
+        let (_dir, editor) = editor_with(BUNDLE_CATALOG);
+        let err = editor
+            .add_to_bundle("ktor", &["ktor-missing".to_string()])
+            .unwrap_err();
+        assert!(matches!(err, GvcError::ProjectValidation(_)));
+    }
+
+    #[test]
+    fn removes_members_or_whole_bundle() {
+        let (_dir, editor) = editor_with(BUNDLE_CATALOG);
+        editor
+            .add_to_bundle("ktor", &["ktor-cio".to_string()])
+            .unwrap();
+
+        let removed = editor
+            .remove_from_bundle("ktor", &["ktor-core".to_string()])
+            .unwrap();
+        assert_eq!(removed, vec!["ktor-core".to_string()]);
+        assert_eq!(
+            editor.list_bundles().unwrap()[0].members,
+            vec!["ktor-cio".to_string()]
+        );
+
+        editor.remove_from_bundle("ktor", &[]).unwrap();
+        assert!(editor.list_bundles().unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_missing_bundle_members() {
+        let doc: DocumentMut = r#"[libraries]
+okhttp = "com.squareup.okhttp3:okhttp:4.12.0"
+
+[bundles]
+network = ["okhttp", "retrofit"]
+"#
+        .parse()
+        .unwrap();
+
+        let bundles = read_bundles(&doc);
+        assert_eq!(bundles[0].missing, vec!["retrofit".to_string()]);
+    }
+
+    #[test]
+    fn removes_entry_with_bundle_membership_and_orphaned_version() {
+        let (_dir, editor) = editor_with(
+            r#"[versions]
+# Shared Ktor version
+ktor = "2.3.12"
+okhttp = "4.12.0"
+
+[libraries]
+ktor-core = { module = "io.ktor:ktor-client-core", version.ref = "ktor" }
+# OkHttp client
+okhttp = { module = "com.squareup.okhttp3:okhttp", version.ref = "okhttp" }
+
+[bundles]
+network = ["ktor-core", "okhttp"]
+"#,
+        );
+
+        let result = editor.remove_entry("okhttp", None).unwrap();
+        assert_eq!(result.removed_version_alias.as_deref(), Some("okhttp"));
+        assert_eq!(result.bundles, vec!["network".to_string()]);
+
+        let content = fs::read_to_string(&editor.catalog_path).unwrap();
+        assert_eq!(
+            content,
+            r#"[versions]
+# Shared Ktor version
+ktor = "2.3.12"
+
+[libraries]
+ktor-core = { module = "io.ktor:ktor-client-core", version.ref = "ktor" }
+
+[bundles]
+network = ["ktor-core"]
+"#
+        );
+    }
+
+    #[test]
+    fn keeps_orphaned_version_alias_read_by_build_scripts() {
+        let (dir, editor) = editor_with(
+            "[versions]\nkotlin = \"2.0.0\"\n\n[plugins]\nkotlin-jvm = { id = \"org.jetbrains.kotlin.jvm\", version.ref = \"kotlin\" }\n",
+        );
+        fs::write(
+            dir.path().join("build.gradle.kts"),
+            "val kotlinVersion = libs.versions.kotlin.get()\n",
+        )
+        .unwrap();
+
+        let result = editor
+            .with_build_scripts(dir.path())
+            .remove_entry("kotlin-jvm", None)
+            .unwrap();
+        assert_eq!(result.kept_version_alias.as_deref(), Some("kotlin"));
+        assert!(result.removed_version_alias.is_none());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("libs.versions.toml")).unwrap(),
+            "[versions]\nkotlin = \"2.0.0\"\n\n[plugins]\n"
+        );
+    }
+
+    #[test]
+    fn keeps_version_alias_still_in_use() {
+        let (_dir, editor) = editor_with(
+            r#"[versions]
+kotlin = "2.0.0"
+
+[libraries]
+kotlin-stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+
+[plugins]
+kotlin-jvm = { id = "org.jetbrains.kotlin.jvm", version.ref = "kotlin" }
+"#,
+        );
+
+        let result = editor
+            .remove_entry("kotlin-jvm", Some(AddTargetKind::Plugin))
+            .unwrap();
+        assert!(matches!(result.target, AddTargetKind::Plugin));
+        assert!(result.removed_version_alias.is_none());
+        assert!(editor.remove_entry("kotlin-jvm", None).is_err());
+    }
+
+    #[test]
+    fn inserts_in_sorted_position_only_when_section_is_sorted() {
+        let (_dir, editor) = editor_with(
+            r#"[versions]
+kotlin = "2.0.0"
+
+[libraries]
+kotlin-stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+zxing = { module = "com.google.zxing:core", version = "3.5.3" }
+
+[plugins]
+ksp = { id = "com.google.devtools.ksp", version = "2.0.0-1.0.24" }
+android = { id = "com.android.application", version = "8.5.0" }
+"#,
+        );
+
+        editor
+            .add_library("com.squareup.okhttp3:okhttp:4.12.0", None, None)
+            .unwrap();
+        editor
+            .add_plugin(
+                "org.jetbrains.kotlin.jvm:2.0.0",
+                Some("kotlin-jvm"),
+                Some("kotlin"),
+            )
+            .unwrap();
+
+        let doc: DocumentMut = fs::read_to_string(&editor.catalog_path)
+            .unwrap()
+            .parse()
+            .unwrap();
+        let keys = |section: &str| -> Vec<String> {
+            doc[section]
+                .as_table()
+                .unwrap()
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .collect()
+        };
+
+        assert_eq!(keys("versions"), vec!["kotlin", "squareup-okhttp3"]);
+        assert_eq!(
+            keys("libraries"),
+            vec!["kotlin-stdlib", "squareup-okhttp3-okhttp", "zxing"]
+        );
+        // [plugins] was unsorted, so the new entry is appended
+        assert_eq!(keys("plugins"), vec!["ksp", "android", "kotlin-jvm"]);
+    }
+
+    #[test]
+    fn dedupes_repeated_literal_versions_per_group() {
+        let (_dir, editor) = editor_with(
+            r#"[versions]
+ktor = "2.3.0"
+okhttp = "4.12.0"
+
+[libraries]
+ktor-core = { module = "io.ktor:ktor-client-core", version = "2.3.12" } # client
+ktor-cio = "io.ktor:ktor-client-cio:2.3.12"
+ktor-json = { module = "io.ktor:ktor-serialization-json", version = "2.3.12" }
+okhttp = { module = "com.squareup.okhttp3:okhttp", version.ref = "okhttp" }
+logging = { module = "com.squareup.okhttp3:logging-interceptor", version = "4.12.0" }
+mockwebserver = "com.squareup.okhttp3:mockwebserver:4.12.0"
+single = "com.example:single:1.0"
+"#,
+        );
+
+        let duplicates = editor.find_duplicate_versions().unwrap();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].group, "io.ktor");
+        assert_eq!(
+            duplicates[0].aliases,
+            vec!["ktor-core", "ktor-cio", "ktor-json"]
+        );
+        // `ktor` holds another version, so a suffixed alias is created
+        assert_eq!(duplicates[0].version_alias, "ktor-2");
+        assert!(!duplicates[0].reused);
+        // okhttp entries join the alias their group already uses
+        assert_eq!(duplicates[1].version_alias, "okhttp");
+        assert!(duplicates[1].reused);
+
+        let updated = editor.plan_dedupe_versions(&duplicates).unwrap().updated;
+        assert!(updated.contains("ktor-2 = \"2.3.12\""));
+        assert!(updated.contains(
+            "ktor-core = { module = \"io.ktor:ktor-client-core\", version = { ref = \"ktor-2\" } } # client"
+        ));
+        assert!(updated.contains(
+            "ktor-cio = { module = \"io.ktor:ktor-client-cio\", version = { ref = \"ktor-2\" } }"
+        ));
+        assert!(updated.contains(
+            "mockwebserver = { module = \"com.squareup.okhttp3:mockwebserver\", version = { ref = \"okhttp\" } }"
+        ));
+        assert!(updated.contains("single = \"com.example:single:1.0\""));
+    }
+
+    #[test]
+    fn dedupe_reuses_the_spelling_of_an_existing_version_key() {
+        let (_dir, editor) = editor_with(
+            r#"[versions]
+squareup_okhttp3 = "4.12.0"
+
+[libraries]
+logging = "com.squareup.okhttp3:logging-interceptor:4.12.0"
+mockwebserver = "com.squareup.okhttp3:mockwebserver:4.12.0"
+"#,
+        );
+
+        let duplicates = editor.find_duplicate_versions().unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].version_alias, "squareup_okhttp3");
+        assert!(duplicates[0].reused);
+
+        let updated = editor.plan_dedupe_versions(&duplicates).unwrap().updated;
+        assert!(!updated.contains("squareup-okhttp3"));
+        assert!(updated.contains(
+            "logging = { module = \"com.squareup.okhttp3:logging-interceptor\", version = { ref = \"squareup_okhttp3\" } }"
+        ));
+
+        // A plan computed with the generated spelling still lands on the existing key
+        let mut generated = duplicates;
+        generated[0].version_alias = "squareup-okhttp3".into();
+        let updated = editor.plan_dedupe_versions(&generated).unwrap().updated;
+        assert!(!updated.contains("squareup-okhttp3"));
+        assert!(updated.contains("version = { ref = \"squareup_okhttp3\" }"));
+    }
 }
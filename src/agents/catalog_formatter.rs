@@ -0,0 +1,251 @@
+use crate::error::{GvcError, Result};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Key, RawString, Table, Value};
+
+/// Catalog sections whose entries are kept in sorted order
+pub const SORTED_SECTIONS: &[&str] = &["versions", "libraries", "bundles", "plugins"];
+
+/// CatalogFormatter rewrites a version catalog into its canonical layout
+pub struct CatalogFormatter {
+    catalog_path: PathBuf,
+}
+
+impl CatalogFormatter {
+    pub fn new<P: AsRef<Path>>(catalog_path: P) -> Self {
+        Self {
+            catalog_path: catalog_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Format the catalog in place, returning true when the file changed
+    ///
+    /// With `check` set the file is left untouched.
+    pub fn format(&self, check: bool) -> Result<bool> {
+        let original = fs::read_to_string(&self.catalog_path).map_err(|e| {
+            GvcError::TomlParsing(format!(
+                "Failed to read catalog '{}': {}",
+                self.catalog_path.display(),
+                e
+            ))
+        })?;
+
+        let formatted = format_catalog(&original)?;
+        let changed = formatted != original;
+        if changed && !check {
+            fs::write(&self.catalog_path, formatted)?;
+        }
+
+        Ok(changed)
+    }
+}
+
+/// Return the canonical form of catalog source text
+pub fn format_catalog(content: &str) -> Result<String> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .map_err(|e| GvcError::TomlParsing(format!("Failed to parse catalog: {}", e)))?;
+    format_document(&mut doc);
+    Ok(doc.to_string())
+}
+
+/// Sort the catalog sections and normalize spacing, keeping comments with their entries
+pub fn format_document(doc: &mut DocumentMut) {
+    let mut first = true;
+    let mut tables: Vec<(isize, String)> = doc
+        .iter()
+        .filter_map(|(name, item)| {
+            item.as_table()
+                .map(|table| (table.position().unwrap_or(isize::MAX), name.to_string()))
+        })
+        .collect();
+    tables.sort();
+
+    for (_, name) in tables {
+        let Some(table) = doc.get_mut(&name).and_then(|item| item.as_table_mut()) else {
+            continue;
+        };
+
+        let prefix = comment_block(table.decor().prefix());
+        let prefix = if first {
+            prefix
+        } else {
+            format!("\n{}", prefix)
+        };
+        table.decor_mut().set_prefix(prefix);
+        first = false;
+
+        if SORTED_SECTIONS.contains(&name.as_str()) {
+            sort_table(table);
+        }
+        for (mut key, item) in table.iter_mut() {
+            let comments = comment_block(key.leaf_decor().prefix());
+            key.leaf_decor_mut().set_prefix(comments);
+            if let Item::Value(value) = item {
+                key.leaf_decor_mut().set_suffix(" ");
+                normalize_entry_value(value);
+            }
+        }
+    }
+
+    let trailing = comment_block(Some(doc.trailing()));
+    doc.set_trailing(trailing);
+}
+
+/// Sort entries by accessor name, moving their comments along
+pub fn sort_table(table: &mut Table) {
+    table.sort_values_by(|a, _, b, _| compare_keys(a, b));
+}
+
+/// Whether the entries of `table` are already in sorted order
+pub fn is_sorted(table: &Table) -> bool {
+    let keys: Vec<&str> = table.iter().map(|(key, _)| key).collect();
+    keys.windows(2)
+        .all(|pair| compare_names(pair[0], pair[1]) != Ordering::Greater)
+}
+
+fn compare_keys(a: &Key, b: &Key) -> Ordering {
+    compare_names(a.get(), b.get())
+}
+
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.to_ascii_lowercase()
+        .cmp(&b.to_ascii_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+fn normalize_entry_value(value: &mut Value) {
+    let comment = trailing_comment(value.decor().suffix());
+    match value {
+        Value::InlineTable(table) => normalize_inline_table(table),
+        Value::Array(array) => normalize_array(array),
+        _ => {}
+    }
+    value.decor_mut().set_prefix(" ");
+    value.decor_mut().set_suffix(comment);
+}
+
+fn normalize_inline_table(table: &mut InlineTable) {
+    table.fmt();
+    table.set_preamble("");
+    for (_, value) in table.iter_mut() {
+        if let Value::InlineTable(nested) = value {
+            normalize_inline_table(nested);
+        }
+    }
+}
+
+/// Single-line arrays use `["a", "b"]`; multi-line arrays put each element on its own line
+fn normalize_array(array: &mut Array) {
+    let multiline = array.iter().any(|value| {
+        value
+            .decor()
+            .prefix()
+            .and_then(|raw| raw.as_str())
+            .is_some_and(|raw| raw.contains('\n'))
+    });
+
+    if !multiline {
+        array.fmt();
+        return;
+    }
+
+    for value in array.iter_mut() {
+        let comments = comment_block(value.decor().prefix());
+        let indented: String = comments
+            .lines()
+            .map(|line| format!("\n    {}", line))
+            .collect();
+        value.decor_mut().set_prefix(format!("{}\n    ", indented));
+        let suffix = trailing_comment(value.decor().suffix());
+        value.decor_mut().set_suffix(suffix);
+    }
+    array.set_trailing_comma(true);
+    array.set_trailing("\n");
+}
+
+/// Keep only the comment lines of a decor prefix, one per line without blank lines
+fn comment_block(raw: Option<&RawString>) -> String {
+    raw.and_then(|raw| raw.as_str())
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// A trailing `# comment` from a value suffix, with a single space before it
+fn trailing_comment(raw: Option<&RawString>) -> String {
+    raw.and_then(|raw| raw.as_str())
+        .map(str::trim)
+        .filter(|comment| comment.starts_with('#'))
+        .map(|comment| format!(" {}", comment))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_sections_and_keeps_comments() {
+        let input = r#"# Project catalog
+[versions]
+ktor="2.3.12"
+# Kotlin toolchain
+kotlin = "2.0.0"   # keep in sync with IDE
+
+
+[libraries]
+okhttp = {module="com.squareup.okhttp3:okhttp",version.ref="okhttp"}
+ktor-core = { module = "io.ktor:ktor-client-core", version = { ref = "ktor" } }
+
+[bundles]
+network = [
+  "okhttp",
+      "ktor-core"
+]
+"#;
+
+        let expected = r#"# Project catalog
+[versions]
+# Kotlin toolchain
+kotlin = "2.0.0" # keep in sync with IDE
+ktor = "2.3.12"
+
+[libraries]
+ktor-core = { module = "io.ktor:ktor-client-core", version = { ref = "ktor" } }
+okhttp = { module = "com.squareup.okhttp3:okhttp", version.ref = "okhttp" }
+
+[bundles]
+network = [
+    "okhttp",
+    "ktor-core",
+]
+"#;
+
+        assert_eq!(format_catalog(input).unwrap(), expected);
+        assert_eq!(format_catalog(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn leaves_sub_table_headers_alone() {
+        let input = "[versions]\nkotlin = \"2.0.0\"\n\n[libraries]\nokhttp=\"com.squareup.okhttp3:okhttp:4.12.0\"\n\n[libraries.frozen]\nmodule = \"org.example:frozen\"\nversion = \"1.0\"\n";
+        let expected = "[versions]\nkotlin = \"2.0.0\"\n\n[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\n\n[libraries.frozen]\nmodule = \"org.example:frozen\"\nversion = \"1.0\"\n";
+
+        assert_eq!(format_catalog(input).unwrap(), expected);
+        assert_eq!(format_catalog(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn detects_sorted_tables() {
+        let doc: DocumentMut =
+            "[libraries]\nA = \"a:a:1\"\nb = \"b:b:1\"\n[plugins]\nz = \"z:1\"\na = \"a:1\"\n"
+                .parse()
+                .unwrap();
+        assert!(is_sorted(doc["libraries"].as_table().unwrap()));
+        assert!(!is_sorted(doc["plugins"].as_table().unwrap()));
+    }
+}
@@ -0,0 +1,625 @@
+use crate::agents::usage_analyzer::resolve_reference;
+use crate::error::{GvcError, Result};
+use crate::gradle::BuildScriptScanner;
+use crate::gradle::build_scripts::CatalogReference;
+use crate::maven::parse_maven_coordinate;
+use crate::utils::toml::TomlUtils;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Document, DocumentMut, InlineTable, Item, Table, Value};
+
+/// Aliases Gradle reserves as the first segment of a library accessor
+const RESERVED_PREFIXES: &[&str] = &["bundles", "plugins", "versions"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintSeverity::Warning => f.write_str("warning"),
+            LintSeverity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// The individual checks performed by `gvc lint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    MissingVersionRef,
+    UnusedVersion,
+    DuplicateCoordinate,
+    AccessorCollision,
+    ReservedPrefix,
+    InvalidPluginId,
+    MixedNotation,
+}
+
+impl LintRule {
+    pub fn code(self) -> &'static str {
+        match self {
+            LintRule::MissingVersionRef => "missing-version-ref",
+            LintRule::UnusedVersion => "unused-version",
+            LintRule::DuplicateCoordinate => "duplicate-coordinate",
+            LintRule::AccessorCollision => "accessor-collision",
+            LintRule::ReservedPrefix => "reserved-prefix",
+            LintRule::InvalidPluginId => "invalid-plugin-id",
+            LintRule::MixedNotation => "mixed-notation",
+        }
+    }
+
+    pub fn severity(self) -> LintSeverity {
+        match self {
+            LintRule::UnusedVersion | LintRule::DuplicateCoordinate | LintRule::MixedNotation => {
+                LintSeverity::Warning
+            }
+            _ => LintSeverity::Error,
+        }
+    }
+
+    /// Whether `--fix` can resolve the issue without changing what Gradle resolves
+    pub fn fixable(self) -> bool {
+        matches!(self, LintRule::UnusedVersion | LintRule::MixedNotation)
+    }
+}
+
+/// A problem found in the catalog, positioned at the offending key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub section: String,
+    pub alias: String,
+}
+
+/// CatalogLinter validates a version catalog and applies safe fixes
+pub struct CatalogLinter {
+    catalog_path: PathBuf,
+    scripts_root: Option<PathBuf>,
+}
+
+impl CatalogLinter {
+    pub fn new<P: AsRef<Path>>(catalog_path: P) -> Self {
+        Self {
+            catalog_path: catalog_path.as_ref().to_path_buf(),
+            scripts_root: None,
+        }
+    }
+
+    /// Treat `[versions]` aliases referenced from build scripts below `root` as used
+    pub fn with_build_scripts<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.scripts_root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Run every check, returning issues in document order
+    pub fn lint(&self) -> Result<Vec<LintIssue>> {
+        let content = self.read_catalog()?;
+        let doc = Document::parse(content.as_str()).map_err(|e| {
+            GvcError::TomlParsing(format!(
+                "Failed to parse catalog '{}': {}",
+                self.catalog_path.display(),
+                e
+            ))
+        })?;
+
+        let mut lint = Lint {
+            raw: doc.raw(),
+            issues: Vec::new(),
+        };
+        let root = doc.as_table();
+
+        lint.check_version_refs(root, &self.script_version_references()?);
+        lint.check_duplicate_coordinates(root);
+        lint.check_accessor_collisions(root);
+        lint.check_reserved_prefixes(root);
+        lint.check_plugin_ids(root);
+        lint.check_mixed_notation(root);
+
+        let mut issues = lint.issues;
+        issues.sort_by_key(|issue| (issue.line, issue.column));
+        Ok(issues)
+    }
+
+    /// Apply the fixable subset of `issues`, returning the ones that were fixed
+    pub fn fix(&self, issues: &[LintIssue]) -> Result<Vec<LintIssue>> {
+        let content = self.read_catalog()?;
+        let mut doc = content.parse::<DocumentMut>().map_err(|e| {
+            GvcError::TomlParsing(format!(
+                "Failed to parse catalog '{}': {}",
+                self.catalog_path.display(),
+                e
+            ))
+        })?;
+
+        let mut fixed = Vec::new();
+        for issue in issues.iter().filter(|issue| issue.rule.fixable()) {
+            let Some(table) = doc.get_mut(&issue.section).and_then(|v| v.as_table_mut()) else {
+                continue;
+            };
+
+            let applied = match issue.rule {
+                LintRule::UnusedVersion => table.remove(&issue.alias).is_some(),
+                LintRule::MixedNotation => table
+                    .get_mut(&issue.alias)
+                    .is_some_and(|item| expand_string_notation(&issue.section, item)),
+                _ => false,
+            };
+
+            if applied {
+                fixed.push(issue.clone());
+            }
+        }
+
+        if !fixed.is_empty() {
+            fs::write(&self.catalog_path, doc.to_string())?;
+        }
+
+        Ok(fixed)
+    }
+
+    fn read_catalog(&self) -> Result<String> {
+        fs::read_to_string(&self.catalog_path).map_err(|e| {
+            GvcError::TomlParsing(format!(
+                "Failed to read catalog '{}': {}",
+                self.catalog_path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Version aliases read directly by build logic, as `libs.versions.x` or `findVersion("x")`
+    fn script_version_references(&self) -> Result<Vec<CatalogReference>> {
+        let Some(root) = &self.scripts_root else {
+            return Ok(Vec::new());
+        };
+
+        Ok(BuildScriptScanner::new(root)
+            .catalog_references("libs")?
+            .into_iter()
+            .filter(|reference| reference.section == "versions")
+            .collect())
+    }
+}
+
+struct Lint<'a> {
+    raw: &'a str,
+    issues: Vec<LintIssue>,
+}
+
+impl Lint<'_> {
+    fn report(
+        &mut self,
+        rule: LintRule,
+        table: &Table,
+        section: &str,
+        alias: &str,
+        message: String,
+    ) {
+        let offset = table
+            .key(alias)
+            .and_then(|key| key.span())
+            .map(|span| span.start)
+            .unwrap_or(0);
+        let (line, column) = line_column(self.raw, offset);
+
+        self.issues.push(LintIssue {
+            rule,
+            message,
+            line,
+            column,
+            section: section.to_string(),
+            alias: alias.to_string(),
+        });
+    }
+
+    fn check_version_refs(&mut self, root: &Table, script_references: &[CatalogReference]) {
+        let versions = section(root, "versions");
+        let mut referenced = HashSet::new();
+
+        for name in ["libraries", "plugins"] {
+            let Some(table) = section(root, name) else {
+                continue;
+            };
+            for (alias, item) in table.iter() {
+                let Some(version_ref) = TomlUtils::extract_version_ref(item) else {
+                    continue;
+                };
+                if !versions.is_some_and(|versions| versions.contains_key(&version_ref)) {
+                    self.report(
+                        LintRule::MissingVersionRef,
+                        table,
+                        name,
+                        alias,
+                        format!(
+                            "'{}' references version '{}', which is not defined in [versions]",
+                            alias, version_ref
+                        ),
+                    );
+                }
+                referenced.insert(version_ref);
+            }
+        }
+
+        let Some(versions) = versions else {
+            return;
+        };
+        let aliases: Vec<String> = versions
+            .iter()
+            .map(|(alias, _)| alias.to_string())
+            .collect();
+        referenced.extend(
+            script_references
+                .iter()
+                .filter_map(|reference| resolve_reference(&aliases, "versions", reference)),
+        );
+        for (alias, _) in versions.iter() {
+            if referenced.contains(alias) {
+                continue;
+            }
+            self.report(
+                LintRule::UnusedVersion,
+                versions,
+                "versions",
+                alias,
+                format!("Version alias '{}' is not used by any entry", alias),
+            );
+        }
+    }
+
+    fn check_duplicate_coordinates(&mut self, root: &Table) {
+        let Some(libraries) = section(root, "libraries") else {
+            return;
+        };
+
+        let mut seen: HashMap<(String, String), String> = HashMap::new();
+        for (alias, item) in libraries.iter() {
+            let Some(coordinate) = TomlUtils::extract_group_artifact(item) else {
+                continue;
+            };
+            match seen.get(&coordinate) {
+                Some(first) => {
+                    let message = format!(
+                        "'{}' declares {}:{} again (already declared as '{}')",
+                        alias, coordinate.0, coordinate.1, first
+                    );
+                    self.report(
+                        LintRule::DuplicateCoordinate,
+                        libraries,
+                        "libraries",
+                        alias,
+                        message,
+                    );
+                }
+                None => {
+                    seen.insert(coordinate, alias.to_string());
+                }
+            }
+        }
+    }
+
+    fn check_accessor_collisions(&mut self, root: &Table) {
+        for name in ["versions", "libraries", "bundles", "plugins"] {
+            let Some(table) = section(root, name) else {
+                continue;
+            };
+
+            let mut seen: HashMap<String, String> = HashMap::new();
+            for (alias, _) in table.iter() {
+                let key = TomlUtils::accessor_key(alias);
+                match seen.get(&key) {
+                    Some(first) => {
+                        let message = format!(
+                            "'{}' and '{}' generate the same accessor in [{}]",
+                            first, alias, name
+                        );
+                        self.report(LintRule::AccessorCollision, table, name, alias, message);
+                    }
+                    None => {
+                        seen.insert(key, alias.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_reserved_prefixes(&mut self, root: &Table) {
+        let Some(libraries) = section(root, "libraries") else {
+            return;
+        };
+
+        for (alias, _) in libraries.iter() {
+            let key = TomlUtils::accessor_key(alias);
+            let first = key.split('-').next().unwrap_or_default();
+            if RESERVED_PREFIXES.contains(&first) {
+                let message = format!(
+                    "Library alias '{}' starts with the reserved prefix '{}'",
+                    alias, first
+                );
+                self.report(
+                    LintRule::ReservedPrefix,
+                    libraries,
+                    "libraries",
+                    alias,
+                    message,
+                );
+            }
+        }
+    }
+
+    fn check_plugin_ids(&mut self, root: &Table) {
+        let Some(plugins) = section(root, "plugins") else {
+            return;
+        };
+
+        for (alias, item) in plugins.iter() {
+            let id = match item.as_str() {
+                Some(raw) => raw.split(':').next().map(str::to_string),
+                None => item
+                    .as_table_like()
+                    .and_then(|table| table.get("id"))
+                    .and_then(|id| id.as_str())
+                    .map(str::to_string),
+            };
+
+            let message = match id {
+                None => format!("Plugin '{}' has no id", alias),
+                Some(id) if !is_valid_plugin_id(&id) => {
+                    format!("Plugin '{}' has an invalid id '{}'", alias, id)
+                }
+                Some(_) => continue,
+            };
+            self.report(
+                LintRule::InvalidPluginId,
+                plugins,
+                "plugins",
+                alias,
+                message,
+            );
+        }
+    }
+
+    fn check_mixed_notation(&mut self, root: &Table) {
+        for name in ["libraries", "plugins"] {
+            let Some(table) = section(root, name) else {
+                continue;
+            };
+
+            let strings: Vec<&str> = table
+                .iter()
+                .filter(|(_, item)| item.is_str())
+                .map(|(alias, _)| alias)
+                .collect();
+            if strings.is_empty() || strings.len() == table.len() {
+                continue;
+            }
+
+            for alias in strings {
+                let message = format!(
+                    "'{}' uses string notation while other entries in [{}] use tables",
+                    alias, name
+                );
+                self.report(LintRule::MixedNotation, table, name, alias, message);
+            }
+        }
+    }
+}
+
+fn section<'a>(root: &'a Table, name: &str) -> Option<&'a Table> {
+    root.get(name).and_then(|item| item.as_table())
+}
+
+/// Gradle plugin ids: ASCII letters, digits, `-`, `_` and non-leading/trailing/doubled dots
+fn is_valid_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        && id.split('.').all(|segment| !segment.is_empty())
+}
+
+/// Replace `"group:artifact:version"` / `"plugin.id:version"` with the table form
+fn expand_string_notation(section: &str, item: &mut Item) -> bool {
+    let Some(Value::String(raw)) = item.as_value() else {
+        return false;
+    };
+
+    let mut table = InlineTable::new();
+    match section {
+        "libraries" => {
+            let Some((group, artifact, version)) = parse_maven_coordinate(raw.value()) else {
+                return false;
+            };
+            table.insert("module", Value::from(format!("{}:{}", group, artifact)));
+            if let Some(version) = version {
+                table.insert("version", Value::from(version));
+            }
+        }
+        "plugins" => {
+            let Some((id, version)) = raw.value().split_once(':') else {
+                return false;
+            };
+            table.insert("id", Value::from(id));
+            table.insert("version", Value::from(version));
+        }
+        _ => return false,
+    }
+    table.fmt();
+
+    let mut expanded = Value::InlineTable(table);
+    *expanded.decor_mut() = raw.decor().clone();
+    *item = Item::Value(expanded);
+    true
+}
+
+pub(crate) fn line_column(raw: &str, offset: usize) -> (usize, usize) {
+    let before = &raw[..offset.min(raw.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|tail| tail.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn lint(content: &str) -> Vec<LintIssue> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        fs::write(&path, content).unwrap();
+        CatalogLinter::new(&path).lint().unwrap()
+    }
+
+    fn rules(issues: &[LintIssue]) -> Vec<&'static str> {
+        issues.iter().map(|issue| issue.rule.code()).collect()
+    }
+
+    #[test]
+    fn reports_version_ref_problems_with_positions() {
+        let issues = lint(
+            r#"[versions]
+kotlin = "2.0.0"
+unused = "1.0"
+
+[libraries]
+ktor = { module = "io.ktor:ktor-client-core", version.ref = "ktor" }
+stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+"#,
+        );
+
+        assert_eq!(
+            rules(&issues),
+            vec!["unused-version", "missing-version-ref"]
+        );
+        assert_eq!((issues[0].line, issues[0].column), (3, 1));
+        assert_eq!((issues[1].line, issues[1].column), (6, 1));
+    }
+
+    #[test]
+    fn reports_duplicates_collisions_and_reserved_prefixes() {
+        let issues = lint(
+            r#"[libraries]
+okhttp = "com.squareup.okhttp3:okhttp:4.12.0"
+okhttp-client = "com.squareup.okhttp3:okhttp:4.12.0"
+core_ktx = "androidx.core:core-ktx:1.13.1"
+core-ktx = "androidx.core:core:1.13.1"
+versions-catalog = "com.example:catalog:1.0"
+"#,
+        );
+
+        assert_eq!(
+            rules(&issues),
+            vec![
+                "duplicate-coordinate",
+                "accessor-collision",
+                "reserved-prefix"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_plugin_ids_and_mixed_notation() {
+        let issues = lint(
+            r#"[plugins]
+kotlin-jvm = { id = "org.jetbrains.kotlin.jvm", version = "2.0.0" }
+broken = { id = "org..broken plugin", version = "1.0" }
+android = "com.android.application:8.5.0"
+"#,
+        );
+
+        assert_eq!(rules(&issues), vec!["invalid-plugin-id", "mixed-notation"]);
+    }
+
+    #[test]
+    fn fix_removes_unused_versions_and_expands_strings() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        fs::write(
+            &path,
+            r#"[versions]
+kotlin = "2.0.0"
+unused = "1.0"
+
+[libraries]
+stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+okhttp = "com.squareup.okhttp3:okhttp:4.12.0" # HTTP client
+"#,
+        )
+        .unwrap();
+
+        let linter = CatalogLinter::new(&path);
+        let issues = linter.lint().unwrap();
+        assert_eq!(linter.fix(&issues).unwrap().len(), 2);
+        assert!(linter.lint().unwrap().is_empty());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("unused"));
+        assert!(content.contains(
+            r#"okhttp = { module = "com.squareup.okhttp3:okhttp", version = "4.12.0" } # HTTP client"#
+        ));
+    }
+
+    #[test]
+    fn versions_used_in_build_scripts_are_not_unused() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        fs::write(&path, "[versions]\njvm-target = \"17\"\n").unwrap();
+        fs::write(
+            dir.path().join("build.gradle.kts"),
+            "jvmToolchain(libs.versions.jvm.target.get().toInt())\n",
+        )
+        .unwrap();
+
+        let issues = CatalogLinter::new(&path)
+            .with_build_scripts(dir.path())
+            .lint()
+            .unwrap();
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn versions_used_from_build_logic_are_not_unused() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        fs::write(
+            &path,
+            "[versions]\njvm = \"17\"\ncompose = \"1.6.0\"\nstale = \"1\"\n",
+        )
+        .unwrap();
+        let plugins = dir.path().join("buildSrc/src/main/kotlin");
+        fs::create_dir_all(&plugins).unwrap();
+        fs::write(
+            plugins.join("android-conventions.gradle.kts"),
+            "jvmToolchain(libs.versions.jvm.get().toInt())\n",
+        )
+        .unwrap();
+        fs::write(
+            plugins.join("Compose.kt"),
+            "val compose = catalog.findVersion(\"compose\").get()\n",
+        )
+        .unwrap();
+
+        let linter = CatalogLinter::new(&path).with_build_scripts(dir.path());
+        let issues = linter.lint().unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].alias, "stale");
+
+        linter.fix(&issues).unwrap();
+        let fixed = fs::read_to_string(&path).unwrap();
+        assert!(fixed.contains("jvm = ") && fixed.contains("compose = "));
+        assert!(!fixed.contains("stale"));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::agents::version_control::{Bump, CommitStep};
+use crate::config::GitConfig;
+use crate::error::{GvcError, Result};
+use crate::report::entry::{EntryStatus, ReportEntry};
+use crate::utils::diff::FileEdit;
+use crate::utils::glob::compile_glob;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+
+/// How `gvc update` splits its changes into commits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitStrategy {
+    /// One commit with every update
+    #[default]
+    Single,
+    /// One commit per updated entry
+    PerDependency,
+    /// One commit per configured group; entries outside every group get their own
+    PerGroup,
+}
+
+/// Dependencies committed together by the `per-group` strategy
+///
+/// Patterns match an entry's alias, its `group:artifact` coordinate or its plugin id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommitGroup {
+    pub name: String,
+    pub patterns: Vec<String>,
+}
+
+/// Splits the catalog edits of an update into a sequence of commits
+pub struct CommitPlanner {
+    strategy: CommitStrategy,
+    groups: Vec<(String, Vec<Regex>)>,
+}
+
+impl CommitPlanner {
+    pub fn from_config(config: &GitConfig) -> Result<Self> {
+        let groups = config
+            .groups
+            .iter()
+            .map(|group| {
+                if group.name.trim().is_empty() {
+                    return Err(GvcError::Config("Commit groups need a name".to_string()));
+                }
+                let patterns = group
+                    .patterns
+                    .iter()
+                    .map(|pattern| compile_glob(pattern))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((group.name.clone(), patterns))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            strategy: config.commit_strategy,
+            groups,
+        })
+    }
+
+    /// Commits applying `edits` step by step, or none for the `single` strategy
+    ///
+    /// Each step copies its entries from the updated catalogs into the previous
+    /// step's content. The last step carries the complete edits, so changes that
+    /// belong to no updated entry (such as alignment) land in the final commit.
+    pub fn plan(&self, entries: &[ReportEntry], edits: &[FileEdit]) -> Result<Vec<CommitStep>> {
+        if self.strategy == CommitStrategy::Single {
+            return Ok(Vec::new());
+        }
+
+        let mut batches: Vec<(Option<String>, Vec<&ReportEntry>)> = Vec::new();
+        for entry in entries {
+            if entry.status != EntryStatus::Updated
+                || entry.latest.is_none()
+                || !edits.iter().any(|edit| edit.path == entry.catalog_path)
+            {
+                continue;
+            }
+            let group = self.group_of(entry);
+            match batches
+                .iter_mut()
+                .find(|(name, _)| group.is_some() && *name == group)
+            {
+                Some((_, members)) => members.push(entry),
+                None => batches.push((group, vec![entry])),
+            }
+        }
+
+        let mut current = Vec::new();
+        for edit in edits {
+            current.push((parse(edit, &edit.original)?, parse(edit, &edit.updated)?));
+        }
+
+        let last = batches.len().saturating_sub(1);
+        let mut steps = Vec::new();
+        for (index, (group, members)) in batches.into_iter().enumerate() {
+            let mut files = Vec::new();
+            for (edit, (doc, updated)) in edits.iter().zip(current.iter_mut()) {
+                let before = doc.to_string();
+                for entry in members
+                    .iter()
+                    .filter(|entry| entry.catalog_path == edit.path)
+                {
+                    let section = entry.section.as_str();
+                    let Some(item) = updated
+                        .get(section)
+                        .and_then(|table| table.get(&entry.alias))
+                    else {
+                        continue;
+                    };
+                    if let Some(table) = doc.get_mut(section).and_then(|t| t.as_table_like_mut()) {
+                        table.insert(&entry.alias, item.clone());
+                    }
+                }
+                let after = if index == last {
+                    edit.updated.clone()
+                } else {
+                    doc.to_string()
+                };
+                if before != after {
+                    files.push(FileEdit {
+                        path: edit.path.clone(),
+                        original: before,
+                        updated: after,
+                    });
+                }
+            }
+            if files.is_empty() {
+                continue;
+            }
+            let bumps = members
+                .iter()
+                .map(|entry| Bump {
+                    name: entry.alias.clone(),
+                    old: entry.current.clone().unwrap_or_default(),
+                    new: entry.latest.clone().unwrap_or_default(),
+                })
+                .collect();
+            steps.push(CommitStep {
+                group,
+                bumps,
+                files,
+            });
+        }
+        Ok(steps)
+    }
+
+    fn group_of(&self, entry: &ReportEntry) -> Option<String> {
+        if self.strategy != CommitStrategy::PerGroup {
+            return None;
+        }
+        let mut identifiers = vec![entry.alias.as_str()];
+        identifiers.extend(entry.coordinate.as_deref());
+        self.groups
+            .iter()
+            .find(|(_, patterns)| {
+                patterns
+                    .iter()
+                    .any(|regex| identifiers.iter().any(|id| regex.is_match(id)))
+            })
+            .map(|(name, _)| name.clone())
+    }
+}
+
+fn parse(edit: &FileEdit, content: &str) -> Result<DocumentMut> {
+    content.parse().map_err(|e| {
+        GvcError::TomlParsing(format!("Failed to parse {}: {}", edit.path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::update::context::Section;
+    use std::path::PathBuf;
+
+    fn updated(
+        alias: &str,
+        section: Section,
+        coordinate: &str,
+        old: &str,
+        new: &str,
+    ) -> ReportEntry {
+        ReportEntry {
+            build: None,
+            catalog: "libs".into(),
+            catalog_path: PathBuf::from("/p/gradle/libs.versions.toml"),
+            line: 1,
+            section,
+            alias: alias.into(),
+            coordinate: Some(coordinate.into()),
+            current: Some(old.into()),
+            status: EntryStatus::Updated,
+            latest: Some(new.into()),
+            candidates: vec![new.into()],
+            category: None,
+            repository: None,
+            diagnostics: Vec::new(),
+            packages: Vec::new(),
+            advisories: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn splits_edits_into_group_and_dependency_commits() {
+        let edit = FileEdit {
+            path: PathBuf::from("/p/gradle/libs.versions.toml"),
+            original: "[versions]\ncore = \"1.10.0\" # androidx\nokhttp = \"4.11.0\"\n\n[libraries]\nappcompat = \"androidx.appcompat:appcompat:1.6.0\"\n".into(),
+            updated: "[versions]\ncore = \"1.12.0\" # androidx\nokhttp = \"4.12.0\"\n\n[libraries]\nappcompat = \"androidx.appcompat:appcompat:1.7.0\"\n".into(),
+        };
+        let entries = vec![
+            updated(
+                "core",
+                Section::Versions,
+                "androidx.core:core",
+                "1.10.0",
+                "1.12.0",
+            ),
+            updated(
+                "okhttp",
+                Section::Versions,
+                "com.squareup.okhttp3:okhttp",
+                "4.11.0",
+                "4.12.0",
+            ),
+            updated(
+                "appcompat",
+                Section::Libraries,
+                "androidx.appcompat:appcompat",
+                "1.6.0",
+                "1.7.0",
+            ),
+        ];
+
+        let config = GitConfig {
+            commit_strategy: CommitStrategy::PerGroup,
+            groups: vec![CommitGroup {
+                name: "androidx".into(),
+                patterns: vec!["androidx.*".into()],
+            }],
+            ..GitConfig::default()
+        };
+        let steps = CommitPlanner::from_config(&config)
+            .unwrap()
+            .plan(&entries, std::slice::from_ref(&edit))
+            .unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].group.as_deref(), Some("androidx"));
+        assert_eq!(steps[0].bumps.len(), 2);
+        assert_eq!(
+            steps[0].files[0].updated,
+            "[versions]\ncore = \"1.12.0\" # androidx\nokhttp = \"4.11.0\"\n\n[libraries]\nappcompat = \"androidx.appcompat:appcompat:1.7.0\"\n"
+        );
+        assert_eq!(steps[1].bumps[0].name, "okhttp");
+        assert_eq!(steps[1].files[0].original, steps[0].files[0].updated);
+        assert_eq!(steps[1].files[0].updated, edit.updated);
+
+        let per_dependency = CommitPlanner::from_config(&GitConfig {
+            commit_strategy: CommitStrategy::PerDependency,
+            ..config
+        })
+        .unwrap();
+        assert_eq!(per_dependency.plan(&entries, &[edit]).unwrap().len(), 3);
+    }
+}
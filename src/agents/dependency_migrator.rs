@@ -0,0 +1,313 @@
+use crate::agents::catalog_editor::{CatalogEditor, MigrationOutcome, MigrationTarget};
+use crate::error::Result;
+use crate::gradle::{BuildScriptScanner, catalog_accessor};
+use crate::utils::diff::FileEdit;
+use regex::Regex;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Dependency configurations (by suffix) whose string arguments are coordinates
+///
+/// `classpath` is left out: catalog accessors are not reliably available inside
+/// `buildscript {}` blocks.
+const CONFIGURATION_SUFFIXES: &[&str] = &[
+    "implementation",
+    "api",
+    "compileonly",
+    "runtimeonly",
+    "annotationprocessor",
+    "kapt",
+    "ksp",
+    "desugaring",
+    "platform",
+];
+
+/// Settings scripts can't use catalog accessors
+const SETTINGS_SCRIPTS: &[&str] = &["settings.gradle", "settings.gradle.kts"];
+
+/// A hard-coded coordinate or plugin version in a build script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralDeclaration {
+    pub path: PathBuf,
+    pub line: usize,
+    pub target: MigrationTarget,
+    /// Byte range replaced by the accessor
+    range: Range<usize>,
+}
+
+/// Everything `gvc migrate` would change
+#[derive(Debug)]
+pub struct MigrationPlan {
+    pub declarations: Vec<LiteralDeclaration>,
+    /// Catalog outcome for every distinct target, in first-seen order
+    pub outcomes: Vec<(MigrationTarget, MigrationOutcome)>,
+    /// The catalog edit followed by one edit per rewritten script
+    pub edits: Vec<FileEdit>,
+}
+
+/// DependencyMigrator moves literal coordinates from build scripts into the catalog
+pub struct DependencyMigrator {
+    project_path: PathBuf,
+    catalog_path: PathBuf,
+}
+
+impl DependencyMigrator {
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(project_path: P, catalog_path: Q) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            catalog_path: catalog_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Find literal declarations and compute the catalog and script edits, writing nothing
+    pub fn plan(&self) -> Result<MigrationPlan> {
+        let scanner = BuildScriptScanner::new(&self.project_path);
+        let root = self
+            .project_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.project_path.clone());
+        let excluded: Vec<PathBuf> = scanner
+            .included_builds()
+            .into_iter()
+            .chain(std::iter::once(root.join("buildSrc")))
+            .collect();
+
+        let mut scripts = Vec::new();
+        for path in scanner.scripts()? {
+            let is_settings = path
+                .file_name()
+                .is_some_and(|name| SETTINGS_SCRIPTS.iter().any(|s| name == *s));
+            if is_settings
+                || !path.starts_with(&root)
+                || excluded.iter().any(|dir| path.starts_with(dir))
+            {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            let declarations = find_literals(&path, &content);
+            if !declarations.is_empty() {
+                scripts.push((path, content, declarations));
+            }
+        }
+
+        let mut targets: Vec<MigrationTarget> = Vec::new();
+        for declaration in scripts.iter().flat_map(|(_, _, found)| found) {
+            if !targets.contains(&declaration.target) {
+                targets.push(declaration.target.clone());
+            }
+        }
+
+        let (outcomes, catalog_edit) =
+            CatalogEditor::new(&self.catalog_path).plan_migration(&targets)?;
+        let outcomes: Vec<(MigrationTarget, MigrationOutcome)> =
+            targets.into_iter().zip(outcomes).collect();
+
+        let mut edits = vec![catalog_edit];
+        let mut declarations = Vec::new();
+        for (path, original, found) in scripts {
+            let mut updated = original.clone();
+            for declaration in found.iter().rev() {
+                let accessor = outcomes
+                    .iter()
+                    .find(|(target, _)| *target == declaration.target)
+                    .and_then(|(target, outcome)| replacement(target, outcome));
+                if let Some(accessor) = accessor {
+                    updated.replace_range(declaration.range.clone(), &accessor);
+                }
+            }
+            edits.push(FileEdit {
+                path,
+                original,
+                updated,
+            });
+            declarations.extend(found);
+        }
+        edits.retain(FileEdit::is_change);
+
+        Ok(MigrationPlan {
+            declarations,
+            outcomes,
+            edits,
+        })
+    }
+}
+
+/// Text replacing a declaration; `None` leaves a conflicting literal in place
+fn replacement(target: &MigrationTarget, outcome: &MigrationOutcome) -> Option<String> {
+    let alias = match outcome {
+        MigrationOutcome::Created { alias, .. } | MigrationOutcome::Existing { alias } => alias,
+        MigrationOutcome::Conflict { .. } => return None,
+    };
+    let accessor = catalog_accessor("libs", target.section(), alias);
+    Some(match target {
+        MigrationTarget::Library { .. } => accessor,
+        MigrationTarget::Plugin { .. } => format!("alias({})", accessor),
+    })
+}
+
+/// Literal `group:artifact:version` arguments and `id("x") version "y"` plugin declarations
+fn find_literals(path: &Path, content: &str) -> Vec<LiteralDeclaration> {
+    let library = Regex::new(
+        r#"\b([A-Za-z][A-Za-z0-9]*)(?:\s*\(\s*|\s+)(["'][A-Za-z0-9_.\-]+:[A-Za-z0-9_.\-]+:[A-Za-z0-9_.\-+]+["'])"#,
+    )
+    .expect("valid library literal regex");
+    let plugin = Regex::new(
+        r#"\b(?:id\s*\(?\s*["']([A-Za-z0-9_.\-]+)["']\s*\)?|kotlin\s*\(\s*["']([A-Za-z0-9_.\-]+)["']\s*\))\s*version\s*["']([A-Za-z0-9_.\-+]+)["']"#,
+    )
+    .expect("valid plugin declaration regex");
+
+    let mut found = Vec::new();
+    for caps in library.captures_iter(content) {
+        let configuration = caps[1].to_ascii_lowercase();
+        if !CONFIGURATION_SUFFIXES
+            .iter()
+            .any(|suffix| configuration.ends_with(suffix))
+        {
+            continue;
+        }
+        let literal = caps.get(2).expect("literal group always matches");
+        let parts: Vec<&str> = literal.as_str()[1..literal.len() - 1].split(':').collect();
+        found.push((
+            literal.range(),
+            MigrationTarget::Library {
+                group: parts[0].to_string(),
+                artifact: parts[1].to_string(),
+                version: parts[2].to_string(),
+            },
+        ));
+    }
+
+    for caps in plugin.captures_iter(content) {
+        let id = match (caps.get(1), caps.get(2)) {
+            (Some(id), _) => id.as_str().to_string(),
+            (None, Some(kotlin)) => format!("org.jetbrains.kotlin.{}", kotlin.as_str()),
+            (None, None) => continue,
+        };
+        found.push((
+            caps.get(0).expect("whole match").range(),
+            MigrationTarget::Plugin {
+                id,
+                version: caps[3].to_string(),
+            },
+        ));
+    }
+
+    found.sort_by_key(|(range, _)| range.start);
+    found
+        .into_iter()
+        .filter(|(range, _)| !is_commented(content, range.start))
+        .map(|(range, target)| LiteralDeclaration {
+            path: path.to_path_buf(),
+            line: content[..range.start].matches('\n').count() + 1,
+            target,
+            range,
+        })
+        .collect()
+}
+
+/// Whether `offset` sits behind a `//` line comment
+fn is_commented(content: &str, offset: usize) -> bool {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    content[line_start..offset].contains("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn finds_kotlin_and_groovy_literals() {
+        let content = r#"plugins {
+    id("com.google.devtools.ksp") version "2.0.0-1.0.24"
+    kotlin("jvm") version "2.0.0"
+    id 'org.jlleitschuh.gradle.ktlint' version '12.1.1' apply false
+}
+dependencies {
+    implementation("com.squareup.okhttp3:okhttp:4.12.0")
+    testImplementation 'junit:junit:4.13.2'
+    implementation(platform("io.ktor:ktor-bom:2.3.12"))
+    // implementation("com.example:commented:1.0")
+    implementation("com.example:templated:$version")
+    implementation("com.example:classified:1.0:sources")
+}
+buildscript {
+    dependencies {
+        classpath("com.android.tools.build:gradle:8.5.0")
+    }
+}
+"#;
+
+        let found: Vec<String> = find_literals(Path::new("build.gradle.kts"), content)
+            .iter()
+            .map(|d| format!("{}@{}", d.target, d.line))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                "com.google.devtools.ksp 2.0.0-1.0.24@2",
+                "org.jetbrains.kotlin.jvm 2.0.0@3",
+                "org.jlleitschuh.gradle.ktlint 12.1.1@4",
+                "com.squareup.okhttp3:okhttp:4.12.0@7",
+                "junit:junit:4.13.2@8",
+                "io.ktor:ktor-bom:2.3.12@9",
+            ]
+        );
+    }
+
+    #[test]
+    fn plans_catalog_entries_and_accessor_rewrites() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("gradle")).unwrap();
+        fs::create_dir_all(dir.path().join("app")).unwrap();
+        let catalog = dir.path().join("gradle/libs.versions.toml");
+        fs::write(
+            &catalog,
+            "[versions]\nktor = \"2.3.12\"\nokhttp = \"4.11.0\"\n\n[libraries]\nktor-core = { module = \"io.ktor:ktor-client-core\", version.ref = \"ktor\" }\nokhttp = { module = \"com.squareup.okhttp3:okhttp\", version.ref = \"okhttp\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("settings.gradle.kts"),
+            "plugins { id(\"org.gradle.toolchains.foojay-resolver-convention\") version \"0.8.0\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("app/build.gradle.kts"),
+            "plugins {\n    id(\"org.jetbrains.kotlin.jvm\") version \"2.0.0\"\n}\ndependencies {\n    implementation(\"io.ktor:ktor-client-core:2.3.12\")\n    implementation(\"io.ktor:ktor-client-cio:2.3.12\")\n    implementation(\"com.squareup.okhttp3:okhttp:4.12.0\")\n}\n",
+        )
+        .unwrap();
+
+        let plan = DependencyMigrator::new(dir.path(), &catalog)
+            .plan()
+            .unwrap();
+
+        assert_eq!(plan.declarations.len(), 4);
+        let script = plan
+            .edits
+            .iter()
+            .find(|edit| edit.path.ends_with("build.gradle.kts"))
+            .unwrap();
+        assert_eq!(
+            script.updated,
+            "plugins {\n    alias(libs.plugins.jetbrains.kotlin.jvm)\n}\ndependencies {\n    implementation(libs.ktor.core)\n    implementation(libs.ktor.client.cio)\n    implementation(\"com.squareup.okhttp3:okhttp:4.12.0\")\n}\n"
+        );
+        assert!(plan.outcomes.iter().any(|(_, outcome)| matches!(
+            outcome,
+            MigrationOutcome::Conflict { alias, catalog_version } if alias == "okhttp" && catalog_version == "4.11.0"
+        )));
+
+        let catalog = &plan.edits[0].updated;
+        assert!(catalog.contains(
+            "ktor-client-cio = { module = \"io.ktor:ktor-client-cio\", version = { ref = \"ktor\" } }"
+        ));
+        assert!(catalog.contains("[plugins]"));
+        assert!(
+            !plan
+                .edits
+                .iter()
+                .any(|edit| edit.path.ends_with("settings.gradle.kts"))
+        );
+    }
+}
@@ -2,14 +2,26 @@ use crate::agents::update::{
     context::{UpdateContext, UpdateReport},
     handlers::{LibraryHandler, PluginHandler, TargetedHandler, VersionHandler},
     interaction::UpdateInteraction,
+    markers::MarkerRules,
+    policy::StabilityPolicy,
 };
 use crate::error::Result;
 use crate::repository::{
     DefaultVersionStrategy, RepositoryClient, RepositoryFactory, VersionStrategy,
 };
+use crate::utils::diff::FileEdit;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Outcome of updating one catalog in memory
+///
+/// Nothing is written: callers apply `edit` or show it as a diff.
+#[derive(Debug)]
+pub struct CatalogUpdate {
+    pub report: UpdateReport,
+    pub edit: FileEdit,
+}
+
 /// DependencyUpdater handles the actual dependency version updates
 ///
 /// This struct has been refactored to delegate to focused handler components
@@ -19,6 +31,7 @@ pub struct DependencyUpdater {
     library_client: Arc<dyn RepositoryClient>,
     plugin_client: Arc<dyn RepositoryClient>,
     version_strategy: Arc<dyn VersionStrategy>,
+    rules: MarkerRules,
 }
 
 impl DependencyUpdater {
@@ -40,22 +53,29 @@ impl DependencyUpdater {
             library_client,
             plugin_client,
             version_strategy,
+            rules: MarkerRules::default(),
         })
     }
 
+    /// Apply ignore/pin rules from the configuration on top of comment markers
+    pub fn with_rules(mut self, rules: MarkerRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// Check for updates without modifying the file
     ///
     /// This is a read-only operation that reports available updates.
     pub fn check_for_updates<P: AsRef<Path>>(
         &self,
         catalog_path: P,
-        stable_only: bool,
+        policy: StabilityPolicy,
     ) -> Result<UpdateReport> {
         let catalog_path = catalog_path.as_ref();
         let context = UpdateContext::new(
             catalog_path,
             crate::agents::update::context::UpdateType::Check,
-            stable_only,
+            policy,
             false, // No interaction in check mode
         );
 
@@ -65,18 +85,15 @@ impl DependencyUpdater {
 
         // Check [versions] section first
         if let Some(_versions) = doc.get("versions").and_then(|v| v.as_table()) {
-            if let Some(_libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
-                let mut handler = VersionHandler::new(
-                    self.library_client.as_ref(),
-                    Arc::clone(&self.version_strategy),
-                    &mut interaction,
-                );
-                let version_report = handler.check(&doc, stable_only)?;
-                // Merge reports
-                for (k, v) in version_report.version_updates {
-                    report.add_version_update(k, v.0, v.1);
-                }
-            }
+            let mut handler = VersionHandler::new(
+                self.library_client.as_ref(),
+                self.plugin_client.as_ref(),
+                Arc::clone(&self.version_strategy),
+                &mut interaction,
+                &self.rules,
+            );
+            let version_report = handler.check(&doc, policy)?;
+            report.merge(version_report);
         }
 
         // Check [libraries] section
@@ -85,18 +102,28 @@ impl DependencyUpdater {
                 self.library_client.as_ref(),
                 Arc::clone(&self.version_strategy),
                 &mut interaction,
+                &self.rules,
+            );
+            let library_report = handler.check(libraries, policy)?;
+            report.merge(library_report);
+        }
+
+        // Check [plugins] section
+        if let Some(plugins) = doc.get("plugins").and_then(|v| v.as_table()) {
+            let mut handler = PluginHandler::new(
+                self.plugin_client.as_ref(),
+                Arc::clone(&self.version_strategy),
+                &mut interaction,
+                &self.rules,
             );
-            let library_report = handler.check(libraries, stable_only)?;
-            // Merge reports
-            for (k, v) in library_report.library_updates {
-                report.add_library_update(k, v.0, v.1);
-            }
+            let plugin_report = handler.check(plugins, policy)?;
+            report.merge(plugin_report);
         }
 
         Ok(report)
     }
 
-    /// Update the version catalog file
+    /// Update all sections of the version catalog in memory
     ///
     /// This method updates all sections of the catalog based on the
     /// provided configuration. It will prompt the user for confirmation
@@ -104,35 +131,33 @@ impl DependencyUpdater {
     pub fn update_version_catalog<P: AsRef<Path>>(
         &self,
         catalog_path: P,
-        stable_only: bool,
+        policy: StabilityPolicy,
         interactive: bool,
-    ) -> Result<UpdateReport> {
+    ) -> Result<CatalogUpdate> {
         let catalog_path = catalog_path.as_ref();
         let context = UpdateContext::new(
             catalog_path,
             crate::agents::update::context::UpdateType::Libraries,
-            stable_only,
+            policy,
             interactive,
         );
 
-        let mut doc = context.load_document()?;
+        let original = context.read_catalog()?;
+        let mut doc = UpdateContext::parse_document(&original)?;
         let mut report = UpdateReport::new();
         let mut interaction = UpdateInteraction::new(interactive);
 
         // Update [versions] section
         if let Some(_versions) = doc.get("versions").and_then(|v| v.as_table()) {
-            if let Some(_libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
-                let mut handler = VersionHandler::new(
-                    self.library_client.as_ref(),
-                    Arc::clone(&self.version_strategy),
-                    &mut interaction,
-                );
-                let version_report = handler.update(&mut doc, stable_only)?;
-                // Merge reports
-                for (k, v) in version_report.version_updates {
-                    report.add_version_update(k, v.0, v.1);
-                }
-            }
+            let mut handler = VersionHandler::new(
+                self.library_client.as_ref(),
+                self.plugin_client.as_ref(),
+                Arc::clone(&self.version_strategy),
+                &mut interaction,
+                &self.rules,
+            );
+            let version_report = handler.update(&mut doc, policy)?;
+            report.merge(version_report);
         }
 
         // Update [libraries] section
@@ -141,12 +166,10 @@ impl DependencyUpdater {
                 self.library_client.as_ref(),
                 Arc::clone(&self.version_strategy),
                 &mut interaction,
+                &self.rules,
             );
-            let library_report = handler.update(libraries, stable_only)?;
-            // Merge reports
-            for (k, v) in library_report.library_updates {
-                report.add_library_update(k, v.0, v.1);
-            }
+            let library_report = handler.update(libraries, policy)?;
+            report.merge(library_report);
         }
 
         // Update [plugins] section
@@ -155,42 +178,43 @@ impl DependencyUpdater {
                 self.plugin_client.as_ref(),
                 Arc::clone(&self.version_strategy),
                 &mut interaction,
+                &self.rules,
             );
-            let plugin_report = handler.update(plugins, stable_only)?;
-            // Merge reports
-            for (k, v) in plugin_report.plugin_updates {
-                report.add_plugin_update(k, v.0, v.1);
-            }
-        }
-
-        // Write back the updated document
-        if !report.is_empty() {
-            context.save_document(&doc)?;
+            let plugin_report = handler.update(plugins, policy)?;
+            report.merge(plugin_report);
         }
 
-        Ok(report)
+        Ok(CatalogUpdate {
+            report,
+            edit: FileEdit {
+                path: catalog_path.to_path_buf(),
+                updated: doc.to_string(),
+                original,
+            },
+        })
     }
 
-    /// Update a specific dependency by pattern
+    /// Update a specific dependency by pattern, in memory
     ///
     /// This method finds dependencies matching the given pattern and
     /// allows the user to select which one to update.
     pub fn update_targeted_dependency<P: AsRef<Path>>(
         &self,
         catalog_path: P,
-        stable_only: bool,
+        policy: StabilityPolicy,
         interactive: bool,
         pattern: &str,
-    ) -> Result<UpdateReport> {
+    ) -> Result<CatalogUpdate> {
         let catalog_path = catalog_path.as_ref();
         let context = UpdateContext::new(
             catalog_path,
             crate::agents::update::context::UpdateType::Targeted,
-            stable_only,
+            policy,
             interactive,
         );
 
-        let mut doc = context.load_document()?;
+        let original = context.read_catalog()?;
+        let mut doc = UpdateContext::parse_document(&original)?;
         let mut interaction = UpdateInteraction::new(interactive);
 
         let mut handler = TargetedHandler::new(
@@ -198,15 +222,18 @@ impl DependencyUpdater {
             self.plugin_client.as_ref(),
             Arc::clone(&self.version_strategy),
             &mut interaction,
+            &self.rules,
         );
 
-        let report = handler.update(&mut doc, stable_only, pattern)?;
+        let report = handler.update(&mut doc, policy, pattern)?;
 
-        // Write back the updated document
-        if !report.is_empty() {
-            context.save_document(&doc)?;
-        }
-
-        Ok(report)
+        Ok(CatalogUpdate {
+            report,
+            edit: FileEdit {
+                path: catalog_path.to_path_buf(),
+                updated: doc.to_string(),
+                original,
+            },
+        })
     }
 }
@@ -0,0 +1,360 @@
+use crate::agents::version_control::VersionControlAgent;
+use crate::error::{GvcError, Result};
+use crate::gradle::{BuildScriptScanner, discover_builds, discover_catalogs};
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+const OBJECTS_DIR: &str = "objects";
+
+/// Log of the catalog and build script changes gvc made, for `gvc undo`
+///
+/// Lives in `.git/gvc/` in Git repositories and `.gradle/gvc/` elsewhere. Each line
+/// of `journal.jsonl` is one command; the previous content of every file it
+/// changed is kept under `objects/`, named by its SHA-256.
+pub struct Journal {
+    project_path: PathBuf,
+    dir: PathBuf,
+}
+
+/// One journaled command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: String,
+    pub command: String,
+    /// Branch checked out when the command started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Commit checked out when the command started, for a detached HEAD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    pub files: Vec<FileChange>,
+    pub branches: Vec<BranchChange>,
+}
+
+/// A catalog or build logic file the command rewrote, relative to the project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// SHA-256 of the content before, `None` when the file did not exist
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A branch the command created or moved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchChange {
+    pub name: String,
+    pub commit: String,
+    /// Commit the branch pointed at before, `None` when gvc created it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+}
+
+/// File contents and branch tips before a command runs
+pub struct Snapshot {
+    journal: Journal,
+    git: Option<VersionControlAgent>,
+    branch: Option<String>,
+    head: Option<String>,
+    branches: Vec<(String, String)>,
+    files: Vec<(PathBuf, Option<String>)>,
+}
+
+impl Journal {
+    pub fn new(project_path: &Path) -> Self {
+        let git_dir = project_path.join(".git");
+        let dir = if git_dir.is_dir() {
+            git_dir.join("gvc")
+        } else {
+            project_path.join(".gradle").join("gvc")
+        };
+        Self {
+            project_path: project_path.to_path_buf(),
+            dir,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The most recent entry not undone yet
+    pub fn last(&self) -> Result<Option<JournalEntry>> {
+        let Some(line) = self.lines()?.pop() else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+
+    /// Drop the most recent entry once it has been undone
+    ///
+    /// The journal is rewritten through a temporary file, so an interrupted undo
+    /// never leaves it truncated.
+    pub fn pop(&self) -> Result<()> {
+        let mut lines = self.lines()?;
+        lines.pop();
+        let content: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+        let temporary = self.dir.join(format!("{}.tmp", JOURNAL_FILE));
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, self.dir.join(JOURNAL_FILE))?;
+        Ok(())
+    }
+
+    /// Content of a file as it was before a journaled command
+    pub fn read_object(&self, hash: &str) -> Result<String> {
+        fs::read_to_string(self.dir.join(OBJECTS_DIR).join(hash)).map_err(|e| {
+            GvcError::ProjectValidation(format!(
+                "The journal lost the content {} ({}); it cannot be restored",
+                hash, e
+            ))
+        })
+    }
+
+    /// Absolute path of a journaled file
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.project_path.join(path)
+    }
+
+    fn append(&self, entry: &JournalEntry) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(JOURNAL_FILE))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    fn store_object(&self, content: &str) -> Result<String> {
+        let hash = content_hash(content);
+        let objects = self.dir.join(OBJECTS_DIR);
+        fs::create_dir_all(&objects)?;
+        let path = objects.join(&hash);
+        if !path.exists() {
+            fs::write(path, content)?;
+        }
+        Ok(hash)
+    }
+
+    fn lines(&self) -> Result<Vec<String>> {
+        let path = self.dir.join(JOURNAL_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+impl Snapshot {
+    /// Record the state of every catalog (of every build) and every local branch
+    ///
+    /// With `build_logic`, build scripts and buildSrc or included build sources are
+    /// recorded too, for commands that rewrite accessors in them.
+    pub fn take(project_path: &Path, build_logic: bool) -> Result<Self> {
+        let journal = Journal::new(project_path);
+        let git = if project_path.join(".git").exists() {
+            Some(VersionControlAgent::new(project_path)?)
+        } else {
+            None
+        };
+        let (branch, head, branches) = match &git {
+            Some(git) => (
+                git.current_branch()?,
+                git.head_commit()?,
+                git.branch_tips()?,
+            ),
+            None => (None, None, Vec::new()),
+        };
+
+        let mut paths: Vec<PathBuf> = discover_catalogs(project_path)
+            .into_iter()
+            .chain(
+                discover_builds(project_path)
+                    .into_iter()
+                    .flat_map(|build| build.catalogs),
+            )
+            .map(|catalog| catalog.path.canonicalize().unwrap_or(catalog.path))
+            .collect();
+        if build_logic {
+            paths.extend(BuildScriptScanner::new(project_path).build_logic_files()?);
+        }
+        paths.sort();
+        paths.dedup();
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path).ok();
+                (path, content)
+            })
+            .collect();
+
+        Ok(Self {
+            journal,
+            git,
+            branch,
+            head,
+            branches,
+            files,
+        })
+    }
+
+    /// Journal what `command` changed since the snapshot; nothing when it changed nothing
+    pub fn record(self, command: &str) -> Result<Option<JournalEntry>> {
+        let mut files = Vec::new();
+        for (path, before) in &self.files {
+            let after = fs::read_to_string(path).ok();
+            if &after == before {
+                continue;
+            }
+            let relative = path
+                .strip_prefix(&self.journal.project_path)
+                .unwrap_or(path)
+                .to_path_buf();
+            files.push(FileChange {
+                path: relative,
+                before: before
+                    .as_deref()
+                    .map(|content| self.journal.store_object(content))
+                    .transpose()?,
+                after: after.as_deref().map(content_hash),
+            });
+        }
+
+        let mut branches = Vec::new();
+        if let Some(git) = &self.git {
+            for (name, commit) in git.branch_tips()? {
+                let previous = self
+                    .branches
+                    .iter()
+                    .find(|(branch, _)| *branch == name)
+                    .map(|(_, commit)| commit.clone());
+                if previous.as_ref() != Some(&commit) {
+                    branches.push(BranchChange {
+                        name,
+                        commit,
+                        previous,
+                    });
+                }
+            }
+        }
+
+        if files.is_empty() && branches.is_empty() {
+            return Ok(None);
+        }
+        let entry = JournalEntry {
+            timestamp: Timestamp::now().to_string(),
+            command: command.to_string(),
+            branch: self.branch.clone(),
+            head: self.head.clone(),
+            files,
+            branches,
+        };
+        self.journal.append(&entry)?;
+        Ok(Some(entry))
+    }
+}
+
+/// Hex SHA-256 of a file's content
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn journals_changed_catalogs_outside_git() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("gradle")).unwrap();
+        let catalog = root.join("gradle/libs.versions.toml");
+        fs::write(&catalog, "[versions]\nokhttp = \"4.11.0\"\n").unwrap();
+        fs::write(root.join("settings.gradle.kts"), "").unwrap();
+
+        let unchanged = Snapshot::take(&root, false).unwrap();
+        assert_eq!(unchanged.record("fmt").unwrap(), None);
+
+        let snapshot = Snapshot::take(&root, false).unwrap();
+        fs::write(&catalog, "[versions]\nokhttp = \"4.12.0\"\n").unwrap();
+        let entry = snapshot.record("update").unwrap().unwrap();
+
+        let journal = Journal::new(&root);
+        assert_eq!(journal.dir(), root.join(".gradle/gvc"));
+        assert_eq!(journal.last().unwrap(), Some(entry.clone()));
+        assert_eq!(
+            entry.files[0].path,
+            PathBuf::from("gradle/libs.versions.toml")
+        );
+        assert_eq!(
+            entry.files[0].after.as_deref(),
+            Some(content_hash("[versions]\nokhttp = \"4.12.0\"\n").as_str())
+        );
+        assert_eq!(
+            journal
+                .read_object(entry.files[0].before.as_deref().unwrap())
+                .unwrap(),
+            "[versions]\nokhttp = \"4.11.0\"\n"
+        );
+
+        journal.pop().unwrap();
+        assert_eq!(journal.last().unwrap(), None);
+        assert!(journal.dir().join(JOURNAL_FILE).exists());
+        assert!(!journal.dir().join("journal.jsonl.tmp").exists());
+    }
+
+    #[test]
+    fn journals_branches_created_by_a_command() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(&root)
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        fs::create_dir_all(root.join("gradle")).unwrap();
+        fs::write(root.join("gradle/libs.versions.toml"), "[versions]\n").unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        let snapshot = Snapshot::take(&root, false).unwrap();
+        git(&["checkout", "-q", "-b", "deps/update"]);
+        fs::write(
+            root.join("gradle/libs.versions.toml"),
+            "[versions]\na = \"1\"\n",
+        )
+        .unwrap();
+        git(&["commit", "-q", "-am", "update"]);
+        let entry = snapshot.record("update").unwrap().unwrap();
+
+        assert_eq!(Journal::new(&root).dir(), root.join(".git/gvc"));
+        assert_eq!(entry.branch.as_deref(), Some("main"));
+        assert_eq!(entry.files.len(), 1);
+        assert_eq!(entry.branches.len(), 1);
+        assert_eq!(entry.branches[0].name, "deps/update");
+        assert_eq!(entry.branches[0].previous, None);
+    }
+}
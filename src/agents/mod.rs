@@ -1,13 +1,26 @@
 pub mod catalog_editor;
+pub mod catalog_formatter;
+pub mod catalog_linter;
+pub mod commit_planner;
+pub mod dependency_migrator;
 pub mod dependency_updater;
+pub mod journal;
 pub mod project_scanner;
+pub mod usage_analyzer;
+pub mod version_aligner;
 pub mod version_control;
 
 // New refactored update module
 pub mod update;
-pub use update::UpdateReport;
+pub use update::{StabilityPolicy, UpdateReport};
 
-pub use catalog_editor::{AddResult, AddTargetKind, CatalogEditor};
+pub use catalog_editor::{AddResult, AddTargetKind, BundleInfo, CatalogEditor};
+pub use catalog_formatter::CatalogFormatter;
+pub use catalog_linter::{CatalogLinter, LintIssue};
+pub use commit_planner::CommitPlanner;
+pub use dependency_migrator::DependencyMigrator;
 pub use dependency_updater::DependencyUpdater;
 pub use project_scanner::ProjectScannerAgent;
+pub use usage_analyzer::UsageAnalyzer;
+pub use version_aligner::VersionAligner;
 pub use version_control::VersionControlAgent;
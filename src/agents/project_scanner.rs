@@ -1,4 +1,6 @@
 use crate::error::{GvcError, Result};
+use crate::gradle::catalogs::DEFAULT_CATALOG;
+use crate::gradle::{CatalogFile, discover_catalogs};
 use std::path::{Path, PathBuf};
 
 /// ProjectScannerAgent validates the project structure
@@ -25,21 +27,25 @@ impl ProjectScannerAgent {
             ));
         }
 
-        // Check for libs.versions.toml
-        let toml_path = self.project_path.join("gradle/libs.versions.toml");
-        if !toml_path.exists() {
-            return Err(GvcError::ProjectValidation(
-                "gradle/libs.versions.toml not found".to_string(),
-            ));
-        }
+        // Find version catalogs; the default one backs single-catalog commands
+        let catalogs = discover_catalogs(&self.project_path);
+        let toml_path = catalogs
+            .first()
+            .map(|catalog| catalog.path.clone())
+            .ok_or_else(|| {
+                GvcError::ProjectValidation(
+                    "No version catalog found (gradle/libs.versions.toml, gradle/*.versions.toml or versionCatalogs in settings)"
+                        .to_string(),
+                )
+            })?;
 
-        // Check for Git repository
-        let git_dir = self.project_path.join(".git");
-        let is_git_repo = git_dir.exists() && git_dir.is_dir();
+        // Check for Git repository; linked worktrees have a `.git` file
+        let is_git_repo = self.project_path.join(".git").exists();
 
         Ok(ProjectInfo {
             project_path: self.project_path.clone(),
             toml_path,
+            catalogs,
             has_git: is_git_repo,
             gradlew_path: if cfg!(target_os = "windows") {
                 self.project_path.join("gradlew.bat")
@@ -52,10 +58,59 @@ impl ProjectScannerAgent {
 
 #[derive(Debug, Clone)]
 pub struct ProjectInfo {
-    #[allow(dead_code)]
     pub project_path: PathBuf,
     pub toml_path: PathBuf,
+    /// Every catalog of the build, the default `libs` catalog first
+    pub catalogs: Vec<CatalogFile>,
     pub has_git: bool,
     #[allow(dead_code)]
     pub gradlew_path: PathBuf,
 }
+
+impl ProjectInfo {
+    /// The catalogs a command works on: the named one, or all of them
+    pub fn select_catalogs(&self, name: Option<&str>) -> Result<Vec<CatalogFile>> {
+        match name {
+            Some(name) => self.catalog(Some(name)).map(|catalog| vec![catalog]),
+            None => Ok(self.catalogs.clone()),
+        }
+    }
+
+    /// A single catalog: the named one, or the default when the build has no choice to make
+    pub fn catalog(&self, name: Option<&str>) -> Result<CatalogFile> {
+        let names = || {
+            self.catalogs
+                .iter()
+                .map(|catalog| catalog.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match name {
+            Some(name) => self
+                .catalogs
+                .iter()
+                .find(|catalog| catalog.name == name)
+                .cloned()
+                .ok_or_else(|| {
+                    GvcError::ProjectValidation(format!(
+                        "No version catalog named '{}' (available: {})",
+                        name,
+                        names()
+                    ))
+                }),
+            None => self
+                .catalogs
+                .iter()
+                .find(|catalog| catalog.name == DEFAULT_CATALOG)
+                .or_else(|| self.catalogs.first().filter(|_| self.catalogs.len() == 1))
+                .cloned()
+                .ok_or_else(|| {
+                    GvcError::ProjectValidation(format!(
+                        "Several version catalogs found ({}); pick one with --catalog",
+                        names()
+                    ))
+                }),
+        }
+    }
+}
@@ -1,5 +1,5 @@
+use crate::agents::update::policy::StabilityPolicy;
 use crate::error::Result;
-use std::collections::HashMap;
 use std::path::Path;
 
 /// Represents different types of catalog update operations
@@ -25,8 +25,8 @@ pub struct UpdateContext<'a> {
     pub catalog_path: &'a Path,
     /// Type of update operation
     pub _update_type: UpdateType,
-    /// Whether to consider only stable versions
-    pub _stable_only: bool,
+    /// Which release channels are eligible
+    pub _stability: StabilityPolicy,
     /// Whether to run in interactive mode
     pub _interactive: bool,
 }
@@ -36,82 +36,274 @@ impl<'a> UpdateContext<'a> {
     pub fn new(
         catalog_path: &'a Path,
         update_type: UpdateType,
-        stable_only: bool,
+        stability: StabilityPolicy,
         interactive: bool,
     ) -> Self {
         Self {
             catalog_path,
             _update_type: update_type,
-            _stable_only: stable_only,
+            _stability: stability,
             _interactive: interactive,
         }
     }
 
     /// Load the TOML document from the catalog path
     pub fn load_document(&self) -> Result<toml_edit::DocumentMut> {
-        let content = std::fs::read_to_string(self.catalog_path).map_err(|e| {
+        Self::parse_document(&self.read_catalog()?)
+    }
+
+    /// Read the catalog text as it is on disk
+    pub fn read_catalog(&self) -> Result<String> {
+        std::fs::read_to_string(self.catalog_path).map_err(|e| {
             crate::error::GvcError::TomlParsing(format!("Failed to read catalog: {}", e))
-        })?;
+        })
+    }
 
+    /// Parse catalog text into an editable document
+    pub fn parse_document(content: &str) -> Result<toml_edit::DocumentMut> {
         content.parse::<toml_edit::DocumentMut>().map_err(|e| {
             crate::error::GvcError::TomlParsing(format!("Failed to parse TOML: {}", e))
         })
     }
+}
 
-    /// Save the updated document back to the catalog path
-    pub fn save_document(&self, doc: &toml_edit::DocumentMut) -> Result<()> {
-        std::fs::write(self.catalog_path, doc.to_string()).map_err(|e| {
-            crate::error::GvcError::TomlParsing(format!("Failed to write catalog: {}", e))
-        })
+/// Catalog table an entry belongs to, in the order reports list them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Versions,
+    Libraries,
+    Plugins,
+}
+
+impl Section {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Section::Versions => "versions",
+            Section::Libraries => "libraries",
+            Section::Plugins => "plugins",
+        }
     }
 }
 
-/// Tracks the changes made during an update operation
+/// What a repository returned for one catalog entry, whether or not it was updated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryLookup {
+    pub section: Section,
+    pub name: String,
+    /// `group:artifact` or plugin id; a version alias reports its first library
+    pub coordinate: String,
+    pub current: String,
+    /// Newer versions allowed by the policy and markers, oldest first
+    pub candidates: Vec<String>,
+    /// Repository the versions were read from
+    pub repository: Option<String>,
+}
+
+/// Everything a check or update learned about one catalog entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryRecord {
+    pub section: Section,
+    pub name: String,
+    /// `group:artifact` or plugin id; a version alias reports its first library
+    pub coordinate: Option<String>,
+    /// Version in the catalog before the update
+    pub current: Option<String>,
+    /// Version the entry was (or would be) moved to
+    pub update: Option<String>,
+    /// Newer versions allowed by the policy and markers, oldest first
+    pub candidates: Vec<String>,
+    /// Repository the versions were read from
+    pub repository: Option<String>,
+    /// Why the entry was left untouched on purpose
+    pub skip_reason: Option<String>,
+}
+
+impl EntryRecord {
+    fn new(section: Section, name: String) -> Self {
+        Self {
+            section,
+            name,
+            coordinate: None,
+            current: None,
+            update: None,
+            candidates: Vec::new(),
+            repository: None,
+            skip_reason: None,
+        }
+    }
+
+    /// Fill in what `other` knows about the same entry
+    fn absorb(&mut self, other: EntryRecord) {
+        self.coordinate = self.coordinate.take().or(other.coordinate);
+        self.current = self.current.take().or(other.current);
+        self.update = other.update.or(self.update.take());
+        if !other.candidates.is_empty() {
+            self.candidates = other.candidates;
+        }
+        self.repository = self.repository.take().or(other.repository);
+        self.skip_reason = other.skip_reason.or(self.skip_reason.take());
+    }
+}
+
+/// Tracks the entries looked up, updated and skipped during an update operation
+///
+/// Entries are kept by section (versions, libraries, plugins) and in catalog
+/// order within a section, so reports come out the same on every run.
 #[derive(Debug, Clone, Default)]
 pub struct UpdateReport {
-    /// Version updates from [versions] section
-    pub version_updates: HashMap<String, (String, String)>,
-    /// Library updates from [libraries] section
-    pub library_updates: HashMap<String, (String, String)>,
-    /// Plugin updates from [plugins] section
-    pub plugin_updates: HashMap<String, (String, String)>,
+    entries: Vec<EntryRecord>,
 }
 
 impl UpdateReport {
     /// Create a new empty update report
     pub fn new() -> Self {
         Self {
-            version_updates: HashMap::new(),
-            library_updates: HashMap::new(),
-            plugin_updates: HashMap::new(),
+            entries: Vec::new(),
         }
     }
 
     /// Add a version update to the report
     pub fn add_version_update(&mut self, name: String, old_version: String, new_version: String) {
-        self.version_updates
-            .insert(name, (old_version, new_version));
+        self.add_update(Section::Versions, name, old_version, new_version);
     }
 
     /// Add a library update to the report
     pub fn add_library_update(&mut self, name: String, old: String, new: String) {
-        self.library_updates.insert(name, (old, new));
+        self.add_update(Section::Libraries, name, old, new);
     }
 
     /// Add a plugin update to the report
     pub fn add_plugin_update(&mut self, name: String, old: String, new: String) {
-        self.plugin_updates.insert(name, (old, new));
+        self.add_update(Section::Plugins, name, old, new);
+    }
+
+    fn add_update(&mut self, section: Section, name: String, old: String, new: String) {
+        let mut record = EntryRecord::new(section, name);
+        record.current = Some(old);
+        record.update = Some(new);
+        self.record(record);
+    }
+
+    /// Record an entry that was skipped on purpose
+    pub fn add_skipped(&mut self, section: Section, name: String, reason: String) {
+        let mut record = EntryRecord::new(section, name);
+        record.skip_reason = Some(reason);
+        self.record(record);
+    }
+
+    /// Record what was found for an entry
+    pub fn add_lookup(&mut self, lookup: EntryLookup) {
+        let mut record = EntryRecord::new(lookup.section, lookup.name);
+        record.coordinate = Some(lookup.coordinate).filter(|c| !c.is_empty());
+        record.current = Some(lookup.current).filter(|c| !c.is_empty());
+        record.candidates = lookup.candidates;
+        record.repository = lookup.repository;
+        self.record(record);
+    }
+
+    /// Merge `record` into the entry it describes, or add it after its section
+    fn record(&mut self, record: EntryRecord) {
+        if let Some(existing) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.section == record.section && e.name == record.name)
+        {
+            existing.absorb(record);
+            return;
+        }
+        let position = self
+            .entries
+            .iter()
+            .position(|e| e.section > record.section)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, record);
+    }
+
+    /// Merge another report into this one
+    pub fn merge(&mut self, other: UpdateReport) {
+        for record in other.entries {
+            self.record(record);
+        }
+    }
+
+    /// Prefix every entry name with its catalog, for reports spanning several catalogs
+    pub fn prefixed(self, catalog: &str) -> Self {
+        Self {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|record| EntryRecord {
+                    name: format!("{}:{}", catalog, record.name),
+                    ..record
+                })
+                .collect(),
+        }
+    }
+
+    /// Every reported entry, by section and in catalog order
+    pub fn entries(&self) -> &[EntryRecord] {
+        &self.entries
+    }
+
+    /// `(name, old, new)` of each update in `section`
+    pub fn updates(&self, section: Section) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.entries
+            .iter()
+            .filter(move |e| e.section == section)
+            .filter_map(|e| {
+                let new = e.update.as_deref()?;
+                Some((e.name.as_str(), e.current.as_deref().unwrap_or(""), new))
+            })
+    }
+
+    /// `(name, reason)` of each entry skipped on purpose
+    pub fn skipped(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .filter(|e| e.update.is_none())
+            .filter_map(|e| Some((e.name.as_str(), e.skip_reason.as_deref()?)))
     }
 
     /// Check if the report is empty (no updates)
     pub fn is_empty(&self) -> bool {
-        self.version_updates.is_empty()
-            && self.library_updates.is_empty()
-            && self.plugin_updates.is_empty()
+        self.total_updates() == 0
     }
 
     /// Get the total number of updates
     pub fn total_updates(&self) -> usize {
-        self.version_updates.len() + self.library_updates.len() + self.plugin_updates.len()
+        self.entries.iter().filter(|e| e.update.is_some()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_entries_by_section_in_recorded_order() {
+        let mut report = UpdateReport::new();
+        report.add_library_update("okhttp".into(), "4.11.0".into(), "4.12.0".into());
+        report.add_plugin_update("detekt".into(), "1.23.0".into(), "1.23.6".into());
+        report.add_skipped(Section::Versions, "ktor".into(), "pinned".into());
+        report.add_library_update("junit".into(), "4.12".into(), "4.13.2".into());
+        report.add_lookup(EntryLookup {
+            section: Section::Libraries,
+            name: "okhttp".into(),
+            coordinate: "com.squareup.okhttp3:okhttp".into(),
+            current: "4.11.0".into(),
+            candidates: vec!["4.12.0".into()],
+            repository: Some("Maven Central".into()),
+        });
+
+        let names: Vec<&str> = report.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["ktor", "okhttp", "junit", "detekt"]);
+        let okhttp = &report.entries()[1];
+        assert_eq!(okhttp.update.as_deref(), Some("4.12.0"));
+        assert_eq!(okhttp.repository.as_deref(), Some("Maven Central"));
+        assert_eq!(report.total_updates(), 3);
+        assert_eq!(
+            report.skipped().collect::<Vec<_>>(),
+            vec![("ktor", "pinned")]
+        );
     }
 }
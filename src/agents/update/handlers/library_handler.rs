@@ -1,7 +1,10 @@
-use crate::agents::update::context::UpdateReport;
+use crate::agents::update::context::{EntryLookup, Section, UpdateReport};
 use crate::agents::update::interaction::UpdateInteraction;
+use crate::agents::update::markers::{EntryMarkers, MarkerRules, Resolution, resolve_latest};
+use crate::agents::update::policy::StabilityPolicy;
 use crate::error::Result;
 use crate::repository::{Coordinate, RepositoryClient, VersionStrategy};
+use crate::utils::output::outln;
 use crate::utils::toml::{LibraryDetails, TomlUtils};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
@@ -16,6 +19,7 @@ pub struct LibraryHandler<'a> {
     library_client: &'a (dyn RepositoryClient + Send + Sync),
     version_strategy: Arc<dyn VersionStrategy>,
     interaction: &'a mut UpdateInteraction,
+    rules: &'a MarkerRules,
 }
 
 impl<'a> LibraryHandler<'a> {
@@ -24,11 +28,13 @@ impl<'a> LibraryHandler<'a> {
         library_client: &'a (dyn RepositoryClient + Send + Sync),
         version_strategy: Arc<dyn VersionStrategy>,
         interaction: &'a mut UpdateInteraction,
+        rules: &'a MarkerRules,
     ) -> Self {
         Self {
             library_client,
             version_strategy,
             interaction,
+            rules,
         }
     }
 
@@ -36,11 +42,15 @@ impl<'a> LibraryHandler<'a> {
     ///
     /// Checks each library for newer versions and updates them
     /// if the user confirms (in interactive mode).
-    pub fn update(&mut self, libraries: &mut Table, stable_only: bool) -> Result<UpdateReport> {
+    pub fn update(
+        &mut self,
+        libraries: &mut Table,
+        policy: StabilityPolicy,
+    ) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
         let keys: Vec<String> = libraries.iter().map(|(k, _)| k.to_string()).collect();
 
-        println!("\n{}", "Checking library updates...".cyan());
+        outln!("\n{}", "Checking library updates...".cyan());
 
         let pb = ProgressBar::new(keys.len() as u64);
         if self.interaction.is_enabled() {
@@ -56,8 +66,19 @@ impl<'a> LibraryHandler<'a> {
         for key in keys {
             pb.set_message(format!("Checking {}", key));
 
+            let markers = EntryMarkers::for_entry(libraries, &key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                if libraries.get(&key).is_some_and(has_concrete_version) {
+                    report.add_skipped(Section::Libraries, key.clone(), reason);
+                }
+                pb.inc(1);
+                continue;
+            }
+
             if let Some(lib_value) = libraries.get_mut(&key) {
-                if let Some(updated) = self.check_library_update(&key, lib_value, stable_only)? {
+                if let Some(updated) =
+                    self.check_library_update(&key, lib_value, policy, &markers, &mut report)?
+                {
                     report.add_library_update(
                         key.clone(),
                         updated.old_version,
@@ -76,11 +97,11 @@ impl<'a> LibraryHandler<'a> {
     /// Check libraries section (read-only)
     ///
     /// Checks for updates without modifying the catalog.
-    pub fn check(&mut self, libraries: &Table, stable_only: bool) -> Result<UpdateReport> {
+    pub fn check(&mut self, libraries: &Table, policy: StabilityPolicy) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
         let keys: Vec<String> = libraries.iter().map(|(k, _)| k.to_string()).collect();
 
-        println!("\n{}", "Checking library updates...".cyan());
+        outln!("\n{}", "Checking library updates...".cyan());
 
         let pb = ProgressBar::new(keys.len() as u64);
         pb.set_style(
@@ -93,8 +114,19 @@ impl<'a> LibraryHandler<'a> {
         for key in keys {
             pb.set_message(format!("Checking {}", key));
 
+            let markers = EntryMarkers::for_entry(libraries, &key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                if libraries.get(&key).is_some_and(has_concrete_version) {
+                    report.add_skipped(Section::Libraries, key.clone(), reason);
+                }
+                pb.inc(1);
+                continue;
+            }
+
             if let Some(lib_value) = libraries.get(&key) {
-                if let Some(updated) = self.check_library_for_update(lib_value, stable_only)? {
+                if let Some(updated) =
+                    self.check_library_for_update(&key, lib_value, policy, &markers, &mut report)?
+                {
                     report.add_library_update(
                         key.clone(),
                         updated.old_version,
@@ -113,8 +145,11 @@ impl<'a> LibraryHandler<'a> {
     /// Check a single library for updates (read-only)
     fn check_library_for_update(
         &self,
+        name: &str,
         lib_value: &Item,
-        stable_only: bool,
+        policy: StabilityPolicy,
+        markers: &EntryMarkers,
+        report: &mut UpdateReport,
     ) -> Result<Option<DependencyUpdate>> {
         let details = match TomlUtils::extract_library_details(lib_value) {
             Some(details) => details,
@@ -127,10 +162,17 @@ impl<'a> LibraryHandler<'a> {
         };
 
         let coordinate = Coordinate::new(details.group.as_str(), details.artifact.as_str());
-        if let Some(latest) = self
-            .library_client
-            .fetch_latest_version(&coordinate, stable_only)?
-        {
+        let resolution = resolve_latest(
+            self.library_client,
+            self.version_strategy.as_ref(),
+            &coordinate,
+            current,
+            policy,
+            markers,
+        )?;
+        report.add_lookup(lookup(name, &coordinate, current, &resolution));
+
+        if let Some(latest) = resolution.latest {
             if latest != *current && self.version_strategy.is_upgrade(current, &latest) {
                 return Ok(Some(DependencyUpdate {
                     old_version: current.to_string(),
@@ -147,7 +189,9 @@ impl<'a> LibraryHandler<'a> {
         &mut self,
         name: &str,
         lib_value: &mut Item,
-        stable_only: bool,
+        policy: StabilityPolicy,
+        markers: &EntryMarkers,
+        report: &mut UpdateReport,
     ) -> Result<Option<DependencyUpdate>> {
         let details = match TomlUtils::extract_library_details(lib_value) {
             Some(details) => details,
@@ -167,10 +211,16 @@ impl<'a> LibraryHandler<'a> {
         };
 
         let coordinate = Coordinate::new(group.as_str(), artifact.as_str());
-        let latest = match self
-            .library_client
-            .fetch_latest_version(&coordinate, stable_only)?
-        {
+        let resolution = resolve_latest(
+            self.library_client,
+            self.version_strategy.as_ref(),
+            &coordinate,
+            &current,
+            policy,
+            markers,
+        )?;
+        report.add_lookup(lookup(name, &coordinate, &current, &resolution));
+        let latest = match resolution.latest {
             Some(latest) => latest,
             None => return Ok(None),
         };
@@ -196,6 +246,28 @@ impl<'a> LibraryHandler<'a> {
     }
 }
 
+/// Only entries with a literal version are handled here; `version.ref` entries
+/// are reported through their `[versions]` alias instead.
+fn has_concrete_version(item: &Item) -> bool {
+    TomlUtils::extract_library_details(item).is_some_and(|details| details.version.is_some())
+}
+
+fn lookup(
+    name: &str,
+    coordinate: &Coordinate,
+    current: &str,
+    resolution: &Resolution,
+) -> EntryLookup {
+    EntryLookup {
+        section: Section::Libraries,
+        name: name.to_string(),
+        coordinate: format!("{}:{}", coordinate.group, coordinate.artifact),
+        current: current.to_string(),
+        candidates: resolution.candidates.clone(),
+        repository: resolution.repository.clone(),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct DependencyUpdate {
     old_version: String,
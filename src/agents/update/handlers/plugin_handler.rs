@@ -1,11 +1,15 @@
-use crate::agents::update::context::UpdateReport;
+use crate::agents::update::context::{EntryLookup, Section, UpdateReport};
 use crate::agents::update::interaction::UpdateInteraction;
+use crate::agents::update::markers::{EntryMarkers, MarkerRules, resolve_latest};
+use crate::agents::update::policy::StabilityPolicy;
 use crate::error::Result;
 use crate::repository::{Coordinate, RepositoryClient, VersionStrategy};
+use crate::utils::output::outln;
+use crate::utils::toml::TomlUtils;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::sync::Arc;
-use toml_edit::{Item, Table, Value};
+use toml_edit::{Item, Table};
 
 /// Handles updates for the [plugins] section of the version catalog
 ///
@@ -14,6 +18,7 @@ pub struct PluginHandler<'a> {
     plugin_client: &'a (dyn RepositoryClient + Send + Sync),
     version_strategy: Arc<dyn VersionStrategy>,
     interaction: &'a mut UpdateInteraction,
+    rules: &'a MarkerRules,
 }
 
 impl<'a> PluginHandler<'a> {
@@ -22,11 +27,13 @@ impl<'a> PluginHandler<'a> {
         plugin_client: &'a (dyn RepositoryClient + Send + Sync),
         version_strategy: Arc<dyn VersionStrategy>,
         interaction: &'a mut UpdateInteraction,
+        rules: &'a MarkerRules,
     ) -> Self {
         Self {
             plugin_client,
             version_strategy,
             interaction,
+            rules,
         }
     }
 
@@ -34,11 +41,11 @@ impl<'a> PluginHandler<'a> {
     ///
     /// Checks each plugin for newer versions on the Gradle Plugin Portal
     /// and updates them if the user confirms (in interactive mode).
-    pub fn update(&mut self, plugins: &mut Table, stable_only: bool) -> Result<UpdateReport> {
+    pub fn update(&mut self, plugins: &mut Table, policy: StabilityPolicy) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
         let keys: Vec<String> = plugins.iter().map(|(k, _)| k.to_string()).collect();
 
-        println!("\n{}", "Checking plugin updates...".cyan());
+        outln!("\n{}", "Checking plugin updates...".cyan());
 
         let pb = ProgressBar::new(keys.len() as u64);
         if self.interaction.is_enabled() {
@@ -54,8 +61,19 @@ impl<'a> PluginHandler<'a> {
         for key in keys {
             pb.set_message(format!("Checking {}", key));
 
+            let markers = EntryMarkers::for_entry(plugins, &key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                if plugins.get(&key).is_some_and(has_literal_version) {
+                    report.add_skipped(Section::Plugins, key.clone(), reason);
+                }
+                pb.inc(1);
+                continue;
+            }
+
             if let Some(plugin_value) = plugins.get_mut(&key) {
-                if let Some(updated) = self.check_plugin_update(&key, plugin_value, stable_only)? {
+                if let Some(updated) =
+                    self.check_plugin_update(&key, plugin_value, policy, &markers, &mut report)?
+                {
                     report.add_plugin_update(key.clone(), updated.old_version, updated.new_version);
                 }
             }
@@ -70,12 +88,11 @@ impl<'a> PluginHandler<'a> {
     /// Check plugins section (read-only)
     ///
     /// Checks for updates without modifying the catalog.
-    #[allow(dead_code)]
-    pub fn check(&mut self, plugins: &Table, stable_only: bool) -> Result<UpdateReport> {
+    pub fn check(&mut self, plugins: &Table, policy: StabilityPolicy) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
         let keys: Vec<String> = plugins.iter().map(|(k, _)| k.to_string()).collect();
 
-        println!("\n{}", "Checking plugin updates...".cyan());
+        outln!("\n{}", "Checking plugin updates...".cyan());
 
         let pb = ProgressBar::new(keys.len() as u64);
         pb.set_style(
@@ -88,8 +105,19 @@ impl<'a> PluginHandler<'a> {
         for key in keys {
             pb.set_message(format!("Checking {}", key));
 
+            let markers = EntryMarkers::for_entry(plugins, &key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                if plugins.get(&key).is_some_and(has_literal_version) {
+                    report.add_skipped(Section::Plugins, key.clone(), reason);
+                }
+                pb.inc(1);
+                continue;
+            }
+
             if let Some(plugin_value) = plugins.get(&key) {
-                if let Some(updated) = self.check_plugin_for_update(plugin_value, stable_only)? {
+                if let Some(updated) =
+                    self.check_plugin_for_update(&key, plugin_value, policy, &markers, &mut report)?
+                {
                     report.add_plugin_update(key.clone(), updated.old_version, updated.new_version);
                 }
             }
@@ -102,61 +130,53 @@ impl<'a> PluginHandler<'a> {
     }
 
     /// Check a single plugin for updates (read-only)
-    #[allow(dead_code)]
+    ///
+    /// Plugins are queried from the Gradle Plugin Portal. Entries may use
+    /// `"id:version"`, `{ id = "...", version = "..." }` or a `[plugins.x]` table;
+    /// `version.ref` entries are handled through the `[versions]` section.
     fn check_plugin_for_update(
         &self,
+        name: &str,
         plugin_value: &Item,
-        stable_only: bool,
+        policy: StabilityPolicy,
+        markers: &EntryMarkers,
+        report: &mut UpdateReport,
     ) -> Result<Option<DependencyUpdate>> {
-        // Plugins are queried from Gradle Plugin Portal
-        // Format: { id = "org.jetbrains.kotlin.jvm", version = "1.9.0" }
-        if let Some(table) = plugin_value.as_table() {
-            let plugin_id = if let Some(id) = table.get("id").and_then(|v| v.as_str()) {
-                id.to_string()
-            } else {
-                return Ok(None);
-            };
-
-            // Get current version
-            let current_version = if let Some(version_item) = table.get("version") {
-                if let Some(v) = version_item.as_str() {
-                    v.to_string()
-                } else if let Some(version_table) = version_item.as_table() {
-                    // Handle version.ref case - skip for now as it's handled in versions section
-                    if version_table.get("ref").is_some() {
-                        return Ok(None);
-                    }
-                    return Ok(None);
-                } else if let Some(version_inline) = version_item.as_inline_table() {
-                    // Handle version.ref case - skip for now as it's handled in versions section
-                    if version_inline.get("ref").is_some() {
-                        return Ok(None);
-                    }
-                    return Ok(None);
-                } else {
-                    return Ok(None);
-                }
-            } else {
-                return Ok(None);
-            };
-
-            // Fetch latest version from Plugin Portal
-            let coordinate = Coordinate::plugin(plugin_id.as_str());
-            if let Some(latest) = self
-                .plugin_client
-                .fetch_latest_version(&coordinate, stable_only)?
-            {
-                if latest != current_version
-                    && self.version_strategy.is_upgrade(&current_version, &latest)
-                {
-                    return Ok(Some(DependencyUpdate {
-                        old_version: current_version,
-                        new_version: latest,
-                    }));
-                }
-            }
-        }
-        Ok(None)
+        let (Some(plugin_id), Some(current_version)) = (
+            TomlUtils::extract_plugin_id(plugin_value),
+            TomlUtils::extract_plugin_version(plugin_value),
+        ) else {
+            return Ok(None);
+        };
+
+        let coordinate = Coordinate::plugin(plugin_id.as_str());
+        let resolution = resolve_latest(
+            self.plugin_client,
+            self.version_strategy.as_ref(),
+            &coordinate,
+            &current_version,
+            policy,
+            markers,
+        )?;
+        report.add_lookup(EntryLookup {
+            section: Section::Plugins,
+            name: name.to_string(),
+            coordinate: plugin_id,
+            current: current_version.clone(),
+            candidates: resolution.candidates,
+            repository: resolution.repository,
+        });
+
+        Ok(resolution
+            .latest
+            .filter(|latest| {
+                *latest != current_version
+                    && self.version_strategy.is_upgrade(&current_version, latest)
+            })
+            .map(|latest| DependencyUpdate {
+                old_version: current_version,
+                new_version: latest,
+            }))
     }
 
     /// Check and update a single plugin
@@ -164,64 +184,32 @@ impl<'a> PluginHandler<'a> {
         &mut self,
         name: &str,
         plugin_value: &mut Item,
-        stable_only: bool,
+        policy: StabilityPolicy,
+        markers: &EntryMarkers,
+        report: &mut UpdateReport,
     ) -> Result<Option<DependencyUpdate>> {
-        // Plugins are queried from Gradle Plugin Portal
-        // Format: { id = "org.jetbrains.kotlin.jvm", version = "1.9.0" }
-        if let Some(table) = plugin_value.as_table_mut() {
-            let plugin_id = if let Some(id) = table.get("id").and_then(|v| v.as_str()) {
-                id.to_string()
-            } else {
-                return Ok(None);
-            };
-
-            // Get current version
-            let current_version = if let Some(version_item) = table.get("version") {
-                if let Some(v) = version_item.as_str() {
-                    v.to_string()
-                } else if let Some(version_table) = version_item.as_table() {
-                    // Handle version.ref case - skip for now as it's handled in versions section
-                    if version_table.get("ref").is_some() {
-                        return Ok(None);
-                    }
-                    return Ok(None);
-                } else if let Some(version_inline) = version_item.as_inline_table() {
-                    // Handle version.ref case - skip for now as it's handled in versions section
-                    if version_inline.get("ref").is_some() {
-                        return Ok(None);
-                    }
-                    return Ok(None);
-                } else {
-                    return Ok(None);
-                }
-            } else {
-                return Ok(None);
-            };
-
-            // Fetch latest version from Plugin Portal
-            let coordinate = Coordinate::plugin(plugin_id.as_str());
-            if let Some(latest) = self
-                .plugin_client
-                .fetch_latest_version(&coordinate, stable_only)?
-            {
-                if latest != current_version
-                    && self.version_strategy.is_upgrade(&current_version, &latest)
-                    && self
-                        .interaction
-                        .confirm_plugin(name, &current_version, &latest)?
-                {
-                    *table.get_mut("version").unwrap() = Item::Value(Value::from(latest.as_str()));
-                    return Ok(Some(DependencyUpdate {
-                        old_version: current_version,
-                        new_version: latest,
-                    }));
-                }
-            }
+        let Some(update) =
+            self.check_plugin_for_update(name, plugin_value, policy, markers, report)?
+        else {
+            return Ok(None);
+        };
+
+        if self
+            .interaction
+            .confirm_plugin(name, &update.old_version, &update.new_version)?
+            && TomlUtils::set_plugin_version(plugin_value, &update.new_version)
+        {
+            return Ok(Some(update));
         }
         Ok(None)
     }
 }
 
+/// `version.ref` plugins are reported through their `[versions]` alias instead.
+fn has_literal_version(item: &Item) -> bool {
+    TomlUtils::extract_version_ref(item).is_none()
+}
+
 #[derive(Debug, Clone)]
 struct DependencyUpdate {
     old_version: String,
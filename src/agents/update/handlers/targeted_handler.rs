@@ -1,8 +1,12 @@
-use crate::agents::update::context::UpdateReport;
+use crate::agents::update::context::{Section, UpdateReport};
 use crate::agents::update::interaction::UpdateInteraction;
+use crate::agents::update::markers::{EntryMarkers, MarkerRules};
+use crate::agents::update::policy::StabilityPolicy;
 use crate::error::Result;
 use crate::maven::version::Version;
 use crate::repository::{Coordinate, RepositoryClient, VersionStrategy};
+use crate::utils::glob::compile_glob;
+use crate::utils::output::{out, outln};
 use crate::utils::toml::TomlUtils;
 use colored::Colorize;
 use regex::Regex;
@@ -23,6 +27,7 @@ pub struct TargetedHandler<'a> {
     plugin_client: &'a (dyn RepositoryClient + Send + Sync),
     version_strategy: Arc<dyn VersionStrategy>,
     interaction: &'a mut UpdateInteraction,
+    rules: &'a MarkerRules,
 }
 
 impl<'a> TargetedHandler<'a> {
@@ -32,12 +37,14 @@ impl<'a> TargetedHandler<'a> {
         plugin_client: &'a (dyn RepositoryClient + Send + Sync),
         version_strategy: Arc<dyn VersionStrategy>,
         interaction: &'a mut UpdateInteraction,
+        rules: &'a MarkerRules,
     ) -> Self {
         Self {
             library_client,
             plugin_client,
             version_strategy,
             interaction,
+            rules,
         }
     }
 
@@ -45,30 +52,31 @@ impl<'a> TargetedHandler<'a> {
     pub fn update(
         &mut self,
         doc: &mut DocumentMut,
-        stable_only: bool,
+        policy: StabilityPolicy,
         pattern: &str,
     ) -> Result<UpdateReport> {
         let matcher = PatternMatcher::new(pattern)?;
-        let mut candidates = self.collect_candidates(doc, &matcher)?;
+        let mut report = UpdateReport::new();
+        let mut candidates = self.collect_candidates(doc, &matcher, &mut report)?;
 
         if candidates.is_empty() {
-            println!(
+            outln!(
                 "{}",
                 format!("No dependencies matched pattern '{}'.", pattern).yellow()
             );
-            return Ok(UpdateReport::new());
+            return Ok(report);
         }
 
         let selected_index = self.prompt_candidate_selection(&candidates)?;
         let candidate = candidates.remove(selected_index);
 
-        let version_entries = self.fetch_versions_for_candidate(&candidate, stable_only)?;
+        let version_entries = self.fetch_versions_for_candidate(&candidate, policy)?;
         if version_entries.is_empty() {
-            println!(
+            outln!(
                 "{}",
                 format!("No versions found for {}.", candidate.display_name()).yellow()
             );
-            return Ok(UpdateReport::new());
+            return Ok(report);
         }
 
         let context = VersionSelectionContext {
@@ -79,14 +87,13 @@ impl<'a> TargetedHandler<'a> {
         let chosen_version = Self::select_version(&candidate, context)?;
 
         if chosen_version == candidate.current_version {
-            println!(
+            outln!(
                 "{}",
                 "Selected version matches the current version; nothing to update.".yellow()
             );
-            return Ok(UpdateReport::new());
+            return Ok(report);
         }
 
-        let mut report = UpdateReport::new();
         self.apply_update(doc, &candidate, &chosen_version, &mut report)?;
 
         Ok(report)
@@ -96,6 +103,7 @@ impl<'a> TargetedHandler<'a> {
         &self,
         doc: &DocumentMut,
         matcher: &PatternMatcher,
+        report: &mut UpdateReport,
     ) -> Result<Vec<TargetCandidate>> {
         let mut candidates = Vec::new();
 
@@ -103,7 +111,8 @@ impl<'a> TargetedHandler<'a> {
         if let Some(libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
             for (name, item) in libraries.iter() {
                 if matcher.matches(name) {
-                    if let Some(candidate) = self.build_library_candidate(name, item) {
+                    let markers = EntryMarkers::for_entry(libraries, name, self.rules);
+                    if let Some(candidate) = self.build_library_candidate(name, item, markers) {
                         candidates.push(candidate);
                     }
                 }
@@ -118,14 +127,19 @@ impl<'a> TargetedHandler<'a> {
                 }
 
                 if let Some(current_version) = item.as_str() {
-                    if let Some((group, artifact)) = self.find_representative_coordinate(doc, name)
-                    {
-                        candidates.push(TargetCandidate {
-                            name: name.to_string(),
-                            current_version: current_version.to_string(),
-                            kind: TargetKind::VersionAlias { group, artifact },
-                        });
-                    }
+                    let kind = match self.find_representative_coordinate(doc, name) {
+                        Some((group, artifact)) => TargetKind::VersionAlias { group, artifact },
+                        None => match self.find_representative_plugin(doc, name) {
+                            Some(plugin_id) => TargetKind::PluginVersionAlias { plugin_id },
+                            None => continue,
+                        },
+                    };
+                    candidates.push(TargetCandidate {
+                        name: name.to_string(),
+                        current_version: current_version.to_string(),
+                        kind,
+                        markers: EntryMarkers::for_version_alias(doc, name, self.rules),
+                    });
                 }
             }
         }
@@ -134,18 +148,37 @@ impl<'a> TargetedHandler<'a> {
         if let Some(plugins) = doc.get("plugins").and_then(|v| v.as_table()) {
             for (name, item) in plugins.iter() {
                 if matcher.matches(name) {
-                    if let Some(candidate) = self.build_plugin_candidate(name, item) {
+                    let markers = EntryMarkers::for_entry(plugins, name, self.rules);
+                    if let Some(candidate) = self.build_plugin_candidate(name, item, markers) {
                         candidates.push(candidate);
                     }
                 }
             }
         }
 
+        // Frozen entries are reported instead of offered
+        candidates.retain(|candidate| match candidate.markers.skip_reason() {
+            Some(reason) => {
+                outln!(
+                    "{}",
+                    format!("Skipping {}: {}", candidate.display_name(), reason).dimmed()
+                );
+                report.add_skipped(candidate.section(), candidate.name.clone(), reason);
+                false
+            }
+            None => true,
+        });
+
         candidates.sort_by_key(|candidate| candidate.display_name());
         Ok(candidates)
     }
 
-    fn build_library_candidate(&self, name: &str, item: &Item) -> Option<TargetCandidate> {
+    fn build_library_candidate(
+        &self,
+        name: &str,
+        item: &Item,
+        markers: EntryMarkers,
+    ) -> Option<TargetCandidate> {
         let details = TomlUtils::extract_library_details(item)?;
         let current_version = details.version?;
 
@@ -156,35 +189,24 @@ impl<'a> TargetedHandler<'a> {
                 group: details.group,
                 artifact: details.artifact,
             },
+            markers,
         })
     }
 
-    fn build_plugin_candidate(&self, name: &str, item: &Item) -> Option<TargetCandidate> {
-        if let Some(table) = item.as_table() {
-            let plugin_id = table.get("id").and_then(|v| v.as_str())?;
-            if let Some(current_version) = table.get("version").and_then(|v| v.as_str()) {
-                return Some(TargetCandidate {
-                    name: name.to_string(),
-                    current_version: current_version.to_string(),
-                    kind: TargetKind::Plugin {
-                        plugin_id: plugin_id.to_string(),
-                    },
-                });
-            }
-        } else if let Some(inline_table) = item.as_inline_table() {
-            let plugin_id = inline_table.get("id").and_then(|v| v.as_str())?;
-            if let Some(current_version) = inline_table.get("version").and_then(|v| v.as_str()) {
-                return Some(TargetCandidate {
-                    name: name.to_string(),
-                    current_version: current_version.to_string(),
-                    kind: TargetKind::Plugin {
-                        plugin_id: plugin_id.to_string(),
-                    },
-                });
-            }
-        }
-
-        None
+    fn build_plugin_candidate(
+        &self,
+        name: &str,
+        item: &Item,
+        markers: EntryMarkers,
+    ) -> Option<TargetCandidate> {
+        Some(TargetCandidate {
+            name: name.to_string(),
+            current_version: TomlUtils::extract_plugin_version(item)?,
+            kind: TargetKind::Plugin {
+                plugin_id: TomlUtils::extract_plugin_id(item)?,
+            },
+            markers,
+        })
     }
 
     fn find_representative_coordinate(
@@ -203,25 +225,34 @@ impl<'a> TargetedHandler<'a> {
         None
     }
 
+    /// Plugin id of the first plugin using `version_key`, for aliases no library references
+    fn find_representative_plugin(&self, doc: &DocumentMut, version_key: &str) -> Option<String> {
+        let plugins = doc.get("plugins").and_then(|v| v.as_table())?;
+        plugins
+            .iter()
+            .filter(|(_, item)| TomlUtils::uses_version_ref(item, version_key))
+            .find_map(|(_, item)| TomlUtils::extract_plugin_id(item))
+    }
+
     fn prompt_candidate_selection(&self, candidates: &[TargetCandidate]) -> Result<usize> {
         if candidates.len() == 1 {
-            println!(
+            outln!(
                 "{}",
                 format!("Found one match: {}", candidates[0].describe_with_version()).cyan()
             );
             return Ok(0);
         }
 
-        println!(
+        outln!(
             "{}",
             format!("Found {} matching dependencies:", candidates.len()).cyan()
         );
         for (idx, candidate) in candidates.iter().enumerate() {
-            println!("  {:>2}) {}", idx + 1, candidate.describe_with_version());
+            outln!("  {:>2}) {}", idx + 1, candidate.describe_with_version());
         }
 
         loop {
-            print!(
+            out!(
                 "Select dependency to update [1-{}] (or 'q' to cancel): ",
                 candidates.len()
             );
@@ -241,14 +272,14 @@ impl<'a> TargetedHandler<'a> {
                 }
             }
 
-            println!("{}", "Invalid selection. Please try again.".red());
+            outln!("{}", "Invalid selection. Please try again.".red());
         }
     }
 
     fn fetch_versions_for_candidate(
         &self,
         candidate: &TargetCandidate,
-        stable_only: bool,
+        policy: StabilityPolicy,
     ) -> Result<Vec<VersionEntry>> {
         let versions = match &candidate.kind {
             TargetKind::Library { group, artifact }
@@ -256,19 +287,22 @@ impl<'a> TargetedHandler<'a> {
                 let coordinate = Coordinate::new(group, artifact);
                 self.library_client.fetch_available_versions(&coordinate)?
             }
-            TargetKind::Plugin { plugin_id } => {
+            TargetKind::Plugin { plugin_id } | TargetKind::PluginVersionAlias { plugin_id } => {
                 let coordinate = Coordinate::plugin(plugin_id.as_str());
                 self.plugin_client.fetch_available_versions(&coordinate)?
             }
         };
 
+        let stable_only = candidate
+            .markers
+            .stable_only_for(policy, &candidate.current_version);
         let mut entries = Vec::with_capacity(versions.len());
         for raw in versions {
             let parsed = Version::parse(&raw);
             let is_stable = parsed.is_stable();
 
-            // Filter by stable_only if requested
-            if stable_only && !is_stable {
+            // Filter by the stability policy and markers for this entry
+            if (stable_only && !is_stable) || !candidate.markers.accepts(&raw) {
                 continue;
             }
 
@@ -287,7 +321,7 @@ impl<'a> TargetedHandler<'a> {
         candidate: &TargetCandidate,
         context: VersionSelectionContext,
     ) -> Result<String> {
-        println!(
+        outln!(
             "\n{}",
             format!("Available versions for {}:", candidate.display_name()).cyan()
         );
@@ -310,16 +344,16 @@ impl<'a> TargetedHandler<'a> {
                     format!(" ({})", labels.join(", "))
                 };
 
-                println!("  {:>2}) {}{}", idx + 1, entry.value.green(), label_str);
+                outln!("  {:>2}) {}{}", idx + 1, entry.value.green(), label_str);
             }
 
             if limit < context.entries.len() {
-                println!("  m ) Show more versions");
+                outln!("  m ) Show more versions");
             }
-            println!("  s ) Skip update");
-            println!("  q ) Cancel");
+            outln!("  s ) Skip update");
+            outln!("  q ) Cancel");
 
-            print!("Select version [1-{} | m/s/q]: ", limit);
+            out!("Select version [1-{} | m/s/q]: ", limit);
             std::io::stdout().flush()?;
 
             let mut input = String::new();
@@ -331,7 +365,7 @@ impl<'a> TargetedHandler<'a> {
                 "s" => return Ok(candidate.current_version.clone()),
                 "m" => {
                     if limit >= context.entries.len() {
-                        println!("{}", "All versions are already displayed.".yellow());
+                        outln!("{}", "All versions are already displayed.".yellow());
                     } else {
                         limit = min(limit + 10, context.entries.len());
                     }
@@ -341,7 +375,7 @@ impl<'a> TargetedHandler<'a> {
                         if (1..=limit).contains(&choice) {
                             let entry = &context.entries[choice - 1];
                             if entry.is_current {
-                                println!(
+                                outln!(
                                     "{}",
                                     "Selected version matches current version; choose another or skip."
                                         .yellow()
@@ -354,7 +388,7 @@ impl<'a> TargetedHandler<'a> {
                                 .strategy
                                 .is_upgrade(&candidate.current_version, &entry.value)
                             {
-                                println!(
+                                outln!(
                                     "{}",
                                     format!(
                                         "Version {} is not a valid upgrade from {} according to version strategy.",
@@ -367,7 +401,9 @@ impl<'a> TargetedHandler<'a> {
 
                             // Confirm with user if in interactive mode
                             let confirm = match &candidate.kind {
-                                TargetKind::Library { .. } | TargetKind::VersionAlias { .. } => {
+                                TargetKind::Library { .. }
+                                | TargetKind::VersionAlias { .. }
+                                | TargetKind::PluginVersionAlias { .. } => {
                                     context.interaction.confirm_library(
                                         &candidate.display_name(),
                                         &candidate.current_version,
@@ -388,7 +424,7 @@ impl<'a> TargetedHandler<'a> {
                             return Ok(entry.value.clone());
                         }
                     }
-                    println!("{}", "Invalid selection. Please try again.".red());
+                    outln!("{}", "Invalid selection. Please try again.".red());
                 }
             }
         }
@@ -402,7 +438,7 @@ impl<'a> TargetedHandler<'a> {
         report: &mut UpdateReport,
     ) -> Result<()> {
         match &candidate.kind {
-            TargetKind::VersionAlias { .. } => {
+            TargetKind::VersionAlias { .. } | TargetKind::PluginVersionAlias { .. } => {
                 self.apply_version_alias(doc, &candidate.name, new_version)?;
                 report.add_version_update(
                     candidate.name.clone(),
@@ -458,7 +494,7 @@ impl<'a> TargetedHandler<'a> {
             }
         }
 
-        println!(
+        outln!(
             "{}",
             format!(
                 "Updated {}: {} → {}",
@@ -525,13 +561,7 @@ impl<'a> TargetedHandler<'a> {
     }
 
     fn apply_plugin_version(&self, item: &mut Item, new_version: &str) -> Result<()> {
-        if let Some(table) = item.as_table_mut() {
-            table.insert("version", Item::Value(toml_edit::Value::from(new_version)));
-            return Ok(());
-        }
-
-        if let Some(inline_table) = item.as_inline_table_mut() {
-            inline_table.insert("version", toml_edit::Value::from(new_version));
+        if TomlUtils::set_plugin_version(item, new_version) {
             return Ok(());
         }
 
@@ -546,9 +576,20 @@ struct TargetCandidate {
     name: String,
     current_version: String,
     kind: TargetKind,
+    markers: EntryMarkers,
 }
 
 impl TargetCandidate {
+    fn section(&self) -> Section {
+        match &self.kind {
+            TargetKind::VersionAlias { .. } | TargetKind::PluginVersionAlias { .. } => {
+                Section::Versions
+            }
+            TargetKind::Library { .. } => Section::Libraries,
+            TargetKind::Plugin { .. } => Section::Plugins,
+        }
+    }
+
     fn display_name(&self) -> String {
         match &self.kind {
             TargetKind::VersionAlias { group, artifact } => {
@@ -557,6 +598,9 @@ impl TargetCandidate {
             TargetKind::Library { group, artifact } => {
                 format!("library '{}' ({}:{})", self.name, group, artifact)
             }
+            TargetKind::PluginVersionAlias { plugin_id } => {
+                format!("version alias '{}' ({})", self.name, plugin_id)
+            }
             TargetKind::Plugin { plugin_id } => {
                 format!("plugin '{}' ({})", self.name, plugin_id)
             }
@@ -574,9 +618,21 @@ impl TargetCandidate {
 
 #[derive(Clone)]
 enum TargetKind {
-    VersionAlias { group: String, artifact: String },
-    Library { group: String, artifact: String },
-    Plugin { plugin_id: String },
+    VersionAlias {
+        group: String,
+        artifact: String,
+    },
+    /// A `[versions]` alias referenced only by plugins
+    PluginVersionAlias {
+        plugin_id: String,
+    },
+    Library {
+        group: String,
+        artifact: String,
+    },
+    Plugin {
+        plugin_id: String,
+    },
 }
 
 #[derive(Clone)]
@@ -611,34 +667,11 @@ impl PatternMatcher {
             format!("*{}*", trimmed)
         };
 
-        let regex = Self::compile_glob(&adjusted)?;
+        let regex = compile_glob(&adjusted)?;
         Ok(Self { regex })
     }
 
     fn matches(&self, value: &str) -> bool {
         self.regex.is_match(value)
     }
-
-    fn compile_glob(pattern: &str) -> Result<Regex> {
-        let mut regex = String::from("(?i)^");
-        for ch in pattern.chars() {
-            match ch {
-                '*' => regex.push_str(".*"),
-                '?' => regex.push('.'),
-                '.' | '+' | '(' | ')' | '|' | '^' | '$' | '{' | '}' | '[' | ']' | '\\' => {
-                    regex.push('\\');
-                    regex.push(ch);
-                }
-                _ => regex.push(ch),
-            }
-        }
-        regex.push('$');
-
-        Regex::new(&regex).map_err(|e| {
-            crate::error::GvcError::ProjectValidation(format!(
-                "Invalid filter pattern '{}': {}",
-                pattern, e
-            ))
-        })
-    }
 }
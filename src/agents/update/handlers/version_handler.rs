@@ -1,35 +1,77 @@
-use crate::agents::update::context::UpdateReport;
+use crate::agents::catalog_editor::insert_entry;
+use crate::agents::update::context::{EntryLookup, Section, UpdateReport};
 use crate::agents::update::interaction::UpdateInteraction;
+use crate::agents::update::markers::{EntryMarkers, MarkerRules, Resolution, resolve_latest};
+use crate::agents::update::policy::StabilityPolicy;
 use crate::error::Result;
 use crate::repository::{Coordinate, RepositoryClient, VersionStrategy};
+use crate::utils::output::outln;
 use crate::utils::toml::TomlUtils;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::sync::Arc;
-use toml_edit::DocumentMut;
+use toml_edit::{DocumentMut, value};
+
+/// A library or plugin referencing a `[versions]` alias
+#[derive(Debug, Clone)]
+struct Member {
+    alias: String,
+    coordinate: Coordinate,
+    /// Looked up on the Gradle Plugin Portal instead of the Maven repositories
+    plugin: bool,
+}
+
+impl Member {
+    fn section(&self) -> Section {
+        if self.plugin {
+            Section::Plugins
+        } else {
+            Section::Libraries
+        }
+    }
+}
+
+/// Outcome of looking up every library that shares a version alias
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SharedLatest {
+    /// Nothing newer is available for the whole group
+    UpToDate,
+    /// Every member publishes this newer version
+    Common(String),
+    /// Members have newer versions but none they all share, with each member's
+    /// section, alias and newest version
+    Diverged(Vec<(Section, String, String)>),
+}
 
 /// Handles updates for the [versions] section of the version catalog
 ///
 /// This handler is responsible for checking and updating version aliases
-/// in the [versions] section. It works with library context to determine
-/// which version to check against.
+/// in the [versions] section. Every library referencing an alias is looked up,
+/// and only a version published for all of them is proposed. Aliases used only
+/// by plugins are resolved through the plugin client.
 pub struct VersionHandler<'a> {
     library_client: &'a (dyn RepositoryClient + Send + Sync),
+    plugin_client: &'a (dyn RepositoryClient + Send + Sync),
     version_strategy: Arc<dyn VersionStrategy>,
     interaction: &'a mut UpdateInteraction,
+    rules: &'a MarkerRules,
 }
 
 impl<'a> VersionHandler<'a> {
     /// Create a new VersionHandler
     pub fn new(
         library_client: &'a (dyn RepositoryClient + Send + Sync),
+        plugin_client: &'a (dyn RepositoryClient + Send + Sync),
         version_strategy: Arc<dyn VersionStrategy>,
         interaction: &'a mut UpdateInteraction,
+        rules: &'a MarkerRules,
     ) -> Self {
         Self {
             library_client,
+            plugin_client,
             version_strategy,
             interaction,
+            rules,
         }
     }
 
@@ -37,39 +79,26 @@ impl<'a> VersionHandler<'a> {
     ///
     /// This method:
     /// 1. Extracts version aliases from [versions] section
-    /// 2. For each version, finds a representative library
-    /// 3. Checks for updates to that library
+    /// 2. For each version, collects every library referencing it (or every
+    ///    plugin, when no library does)
+    /// 3. Looks for the newest version all of them publish
     /// 4. Prompts user for confirmation (if interactive)
-    /// 5. Updates the version alias if confirmed
-    pub fn update(&mut self, doc: &mut DocumentMut, stable_only: bool) -> Result<UpdateReport> {
+    /// 5. Updates the version alias if confirmed, or offers to split it when
+    ///    the libraries no longer share a newer version
+    pub fn update(
+        &mut self,
+        doc: &mut DocumentMut,
+        policy: StabilityPolicy,
+    ) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
 
         // Clone the data we need to read before mutating
-        let versions_data: Vec<(String, String)> =
-            if let Some(versions) = doc.get("versions").and_then(|v| v.as_table()) {
-                versions
-                    .iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.to_string(), s.to_string())))
-                    .collect()
-            } else {
-                return Ok(report);
-            };
-
-        let libraries_data: Vec<(String, toml_edit::Item)> =
-            if let Some(libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
-                libraries
-                    .iter()
-                    .map(|(k, v)| (k.to_string(), v.clone()))
-                    .collect()
-            } else {
-                return Ok(report);
-            };
-
+        let versions_data = read_versions(doc);
         if versions_data.is_empty() {
             return Ok(report);
         }
 
-        println!("\n{}", "Checking version updates...".cyan());
+        outln!("\n{}", "Checking version updates...".cyan());
 
         let pb = ProgressBar::new(versions_data.len() as u64);
         if self.interaction.is_enabled() {
@@ -85,28 +114,30 @@ impl<'a> VersionHandler<'a> {
         for (version_key, current_version) in versions_data {
             pb.set_message(format!("Checking {}", version_key));
 
-            // Find a library that uses this version reference
-            let representative_lib = libraries_data.iter().find_map(|(_name, lib_value)| {
-                if !TomlUtils::uses_version_ref(lib_value, &version_key) {
-                    return None;
-                }
-                TomlUtils::extract_library_details(lib_value)
-                    .map(|details| (details.group, details.artifact))
-            });
+            // If no libraries reference this version, skip
+            let members = members_of(doc, &version_key);
+            if members.is_empty() {
+                pb.inc(1);
+                continue;
+            }
 
-            if let Some((group, artifact)) = representative_lib {
-                let coordinate = Coordinate::new(group.as_str(), artifact.as_str());
-                if let Some(latest) = self
-                    .library_client
-                    .fetch_latest_version(&coordinate, stable_only)?
-                {
-                    if latest != current_version
-                        && self.version_strategy.is_upgrade(&current_version, &latest)
-                        && self.interaction.confirm_version(
-                            &version_key,
-                            &current_version,
-                            &latest,
-                        )?
+            let markers = EntryMarkers::for_version_alias(doc, &version_key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                report.add_skipped(Section::Versions, version_key.clone(), reason);
+                pb.inc(1);
+                continue;
+            }
+            let (shared, resolution) =
+                self.shared_latest(&members, &current_version, policy, &markers)?;
+            if let Some(lookup) = lookup(&version_key, &members, &current_version, resolution) {
+                report.add_lookup(lookup);
+            }
+            match shared {
+                SharedLatest::UpToDate => {}
+                SharedLatest::Common(latest) => {
+                    if self
+                        .interaction
+                        .confirm_version(&version_key, &current_version, &latest)?
                     {
                         if let Some(entry) = doc
                             .get_mut("versions")
@@ -118,6 +149,23 @@ impl<'a> VersionHandler<'a> {
                         }
                     }
                 }
+                SharedLatest::Diverged(latest) => {
+                    if self.interaction.confirm_split(&version_key, &latest)? {
+                        let updates = split_version_alias(doc, &version_key, &latest);
+                        for (alias, new_version) in updates {
+                            if new_version == current_version {
+                                continue;
+                            }
+                            report.add_version_update(alias, current_version.clone(), new_version);
+                        }
+                    } else {
+                        report.add_skipped(
+                            Section::Versions,
+                            version_key.clone(),
+                            diverged_reason(&latest),
+                        );
+                    }
+                }
             }
 
             pb.inc(1);
@@ -130,38 +178,19 @@ impl<'a> VersionHandler<'a> {
     /// Check versions section (read-only, no modifications)
     ///
     /// This method performs the same logic as update() but without
-    /// modifying the document or prompting the user.
-    pub fn check(&mut self, doc: &DocumentMut, stable_only: bool) -> Result<UpdateReport> {
+    /// modifying the document or prompting the user. Aliases whose libraries
+    /// share no newer version are reported as skipped.
+    pub fn check(&mut self, doc: &DocumentMut, policy: StabilityPolicy) -> Result<UpdateReport> {
         let mut report = UpdateReport::new();
 
-        let versions_data: Vec<(String, String)> =
-            if let Some(versions) = doc.get("versions").and_then(|v| v.as_table()) {
-                versions
-                    .iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.to_string(), s.to_string())))
-                    .collect()
-            } else {
-                return Ok(report);
-            };
-
-        let libraries_data: Vec<(String, toml_edit::Item)> =
-            if let Some(libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
-                libraries
-                    .iter()
-                    .map(|(k, v)| (k.to_string(), v.clone()))
-                    .collect()
-            } else {
-                return Ok(report);
-            };
-
+        let versions_data = read_versions(doc);
         if versions_data.is_empty() {
             return Ok(report);
         }
 
-        println!("\n{}", "Checking version variables...".cyan());
+        outln!("\n{}", "Checking version variables...".cyan());
 
-        let keys: Vec<String> = versions_data.iter().map(|(k, _)| k.clone()).collect();
-        let pb = ProgressBar::new(keys.len() as u64);
+        let pb = ProgressBar::new(versions_data.len() as u64);
         pb.set_style(
             ProgressStyle::default_bar()
                 .template("  [{bar:40}] {pos}/{len} {msg}")
@@ -169,51 +198,38 @@ impl<'a> VersionHandler<'a> {
                 .progress_chars("=>-"),
         );
 
-        for version_key in keys {
+        for (version_key, current_version) in versions_data {
             pb.set_message(format!("Checking {}", version_key));
 
-            // Get current version
-            let current_version = match versions_data
-                .iter()
-                .find(|(k, _)| k == &version_key)
-                .map(|(_, v)| v)
-            {
-                Some(v) => v,
-                None => {
-                    pb.inc(1);
-                    continue;
-                }
-            };
-
-            // Find the first library that references this version (as a representative)
-            let representative_lib = libraries_data.iter().find_map(|(_name, lib_value)| {
-                if !TomlUtils::uses_version_ref(lib_value, &version_key) {
-                    return None;
-                }
-                TomlUtils::extract_library_details(lib_value)
-                    .map(|details| (details.group, details.artifact))
-            });
-
             // If no libraries reference this version, skip
-            if representative_lib.is_none() {
+            let members = members_of(doc, &version_key);
+            if members.is_empty() {
                 pb.inc(1);
                 continue;
             }
 
-            // Query latest version for the representative library only
-            let (group, artifact) = representative_lib.unwrap();
-            let coordinate = Coordinate::new(group.as_str(), artifact.as_str());
-            if let Some(latest) = self
-                .library_client
-                .fetch_latest_version(&coordinate, stable_only)?
-            {
-                if latest != *current_version
-                    && self.version_strategy.is_upgrade(current_version, &latest)
-                {
-                    report.add_version_update(
+            let markers = EntryMarkers::for_version_alias(doc, &version_key, self.rules);
+            if let Some(reason) = markers.skip_reason() {
+                report.add_skipped(Section::Versions, version_key.clone(), reason);
+                pb.inc(1);
+                continue;
+            }
+
+            let (shared, resolution) =
+                self.shared_latest(&members, &current_version, policy, &markers)?;
+            if let Some(lookup) = lookup(&version_key, &members, &current_version, resolution) {
+                report.add_lookup(lookup);
+            }
+            match shared {
+                SharedLatest::UpToDate => {}
+                SharedLatest::Common(latest) => {
+                    report.add_version_update(version_key.clone(), current_version, latest);
+                }
+                SharedLatest::Diverged(latest) => {
+                    report.add_skipped(
+                        Section::Versions,
                         version_key.clone(),
-                        current_version.to_string(),
-                        latest,
+                        diverged_reason(&latest),
                     );
                 }
             }
@@ -224,4 +240,427 @@ impl<'a> VersionHandler<'a> {
 
         Ok(report)
     }
+
+    /// Find the newest version every member publishes
+    ///
+    /// The resolution lists the upgrades shared by all members and the
+    /// repository of the first one.
+    fn shared_latest(
+        &self,
+        members: &[Member],
+        current: &str,
+        policy: StabilityPolicy,
+        markers: &EntryMarkers,
+    ) -> Result<(SharedLatest, Resolution)> {
+        let strategy = self.version_strategy.as_ref();
+        let is_upgrade =
+            |candidate: &str| candidate != current && strategy.is_upgrade(current, candidate);
+
+        let client_for = |member: &Member| {
+            if member.plugin {
+                self.plugin_client
+            } else {
+                self.library_client
+            }
+        };
+
+        if let [member] = members {
+            let resolution = resolve_latest(
+                client_for(member),
+                strategy,
+                &member.coordinate,
+                current,
+                policy,
+                markers,
+            )?;
+            let shared = match &resolution.latest {
+                Some(latest) if is_upgrade(latest) => SharedLatest::Common(latest.clone()),
+                _ => SharedLatest::UpToDate,
+            };
+            return Ok((shared, resolution));
+        }
+
+        let stable_only = markers.stable_only_for(policy, current);
+        let mut common: Option<Vec<String>> = None;
+        let mut repository = None;
+        let mut newest = Vec::new();
+        for member in members {
+            let listing = client_for(member).fetch_version_listing(&member.coordinate)?;
+            if repository.is_none() {
+                repository = listing.repository;
+            }
+            let available: Vec<String> = listing
+                .versions
+                .into_iter()
+                .filter(|version| markers.accepts(version))
+                .collect();
+            newest.push((
+                member.section(),
+                member.alias.clone(),
+                strategy
+                    .select_latest(&available, stable_only)
+                    .unwrap_or_else(|| current.to_string()),
+            ));
+            common = Some(match common {
+                None => available,
+                Some(common) => common
+                    .into_iter()
+                    .filter(|version| available.contains(version))
+                    .collect(),
+            });
+        }
+
+        let resolution = Resolution::from_versions(
+            common.unwrap_or_default(),
+            repository,
+            strategy,
+            current,
+            stable_only,
+            markers,
+        );
+        let shared = match resolution
+            .latest
+            .as_deref()
+            .filter(|latest| is_upgrade(latest))
+        {
+            Some(latest) => SharedLatest::Common(latest.to_string()),
+            None if newest.iter().any(|(_, _, latest)| is_upgrade(latest)) => {
+                SharedLatest::Diverged(newest)
+            }
+            None => SharedLatest::UpToDate,
+        };
+        Ok((shared, resolution))
+    }
+}
+
+/// Lookup of a version alias, reported under its first member's coordinate
+fn lookup(
+    version_key: &str,
+    members: &[Member],
+    current: &str,
+    resolution: Resolution,
+) -> Option<EntryLookup> {
+    let first = members.first()?;
+    let coordinate = if first.plugin {
+        first.coordinate.group.clone()
+    } else {
+        format!("{}:{}", first.coordinate.group, first.coordinate.artifact)
+    };
+    Some(EntryLookup {
+        section: Section::Versions,
+        name: version_key.to_string(),
+        coordinate,
+        current: current.to_string(),
+        candidates: resolution.candidates,
+        repository: resolution.repository,
+    })
+}
+
+fn read_versions(doc: &DocumentMut) -> Vec<(String, String)> {
+    doc.get("versions")
+        .and_then(|v| v.as_table())
+        .map(|versions| {
+            versions
+                .iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.to_string(), s.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Libraries referencing `version_key` in catalog order, or its plugins when no library does
+///
+/// Libraries decide an alias they share with plugins: plugin markers are not
+/// always published where the plugin portal client looks.
+fn members_of(doc: &DocumentMut, version_key: &str) -> Vec<Member> {
+    let referencing = |section: &str| {
+        doc.get(section)
+            .and_then(|v| v.as_table())
+            .into_iter()
+            .flat_map(|table| table.iter())
+            .filter(|(_, item)| TomlUtils::uses_version_ref(item, version_key))
+    };
+
+    let libraries: Vec<Member> = referencing("libraries")
+        .filter_map(|(alias, item)| {
+            let details = TomlUtils::extract_library_details(item)?;
+            Some(Member {
+                alias: alias.to_string(),
+                coordinate: Coordinate::new(details.group, details.artifact),
+                plugin: false,
+            })
+        })
+        .collect();
+    if !libraries.is_empty() {
+        return libraries;
+    }
+
+    referencing("plugins")
+        .filter_map(|(alias, item)| {
+            Some(Member {
+                alias: alias.to_string(),
+                coordinate: Coordinate::plugin(TomlUtils::extract_plugin_id(item)?),
+                plugin: true,
+            })
+        })
+        .collect()
+}
+
+fn diverged_reason(latest: &[(Section, String, String)]) -> String {
+    let members: Vec<String> = latest
+        .iter()
+        .map(|(_, alias, version)| format!("{} → {}", alias, version))
+        .collect();
+    format!(
+        "no newer version shared by all entries using it ({}); run `gvc update --interactive` to split it",
+        members.join(", ")
+    )
+}
+
+/// Give each set of libraries with the same newest version its own alias
+///
+/// Libraries whose newest version matches the first library keep `version_key`;
+/// each other set moves to a new alias named after its first library. Only the
+/// listed entries of their own section are repointed. Returns the aliases with
+/// their new versions.
+fn split_version_alias(
+    doc: &mut DocumentMut,
+    version_key: &str,
+    latest: &[(Section, String, String)],
+) -> Vec<(String, String)> {
+    let mut sets: Vec<(String, Vec<(Section, &str)>)> = Vec::new();
+    for (section, alias, version) in latest {
+        match sets.iter_mut().find(|(v, _)| v == version) {
+            Some((_, aliases)) => aliases.push((*section, alias)),
+            None => sets.push((version.clone(), vec![(*section, alias)])),
+        }
+    }
+
+    let mut updates = Vec::new();
+    for (index, (version, aliases)) in sets.into_iter().enumerate() {
+        let Some(versions) = doc.get_mut("versions").and_then(|v| v.as_table_mut()) else {
+            break;
+        };
+        let key = if index == 0 {
+            version_key.to_string()
+        } else {
+            let base = aliases[0].1.to_string();
+            let taken = |candidate: &str| {
+                let candidate = TomlUtils::accessor_key(candidate);
+                versions
+                    .iter()
+                    .any(|(key, _)| TomlUtils::accessor_key(key) == candidate)
+            };
+            let key = std::iter::once(base.clone())
+                .chain((2..).map(|n| format!("{}-{}", base, n)))
+                .find(|candidate| !taken(candidate))
+                .expect("an unused suffix exists");
+            insert_entry(versions, &key, value(version.as_str()));
+            key
+        };
+        if let Some(entry) = versions.get_mut(&key) {
+            TomlUtils::update_version(entry, &version);
+        }
+
+        for (section, alias) in &aliases {
+            if let Some(item) = doc
+                .get_mut(section.as_str())
+                .and_then(|v| v.as_table_mut())
+                .and_then(|entries| entries.get_mut(alias))
+                .filter(|item| TomlUtils::uses_version_ref(item, version_key))
+            {
+                TomlUtils::set_version_ref(item, &key);
+            }
+        }
+        updates.push((key, version));
+    }
+
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::GvcError;
+    use crate::repository::DefaultVersionStrategy;
+    use std::collections::HashMap;
+
+    struct StubClient(HashMap<&'static str, Vec<&'static str>>);
+
+    impl RepositoryClient for StubClient {
+        fn fetch_available_versions(&self, coordinate: &Coordinate) -> Result<Vec<String>> {
+            self.0
+                .get(coordinate.artifact.as_str())
+                .map(|versions| versions.iter().map(|v| v.to_string()).collect())
+                .ok_or_else(|| GvcError::ProjectValidation("unknown artifact".into()))
+        }
+    }
+
+    const CATALOG: &str = r#"[versions]
+detekt = "1.23.0"
+ktor = "2.3.0"
+retrofit = "2.9.0"
+
+[libraries]
+ktor-core = { module = "io.ktor:core", version.ref = "ktor" }
+ktor-cio = { module = "io.ktor:cio", version.ref = "ktor" }
+retrofit = { module = "com.squareup.retrofit2:retrofit", version.ref = "retrofit" }
+retrofit-rx = { module = "com.squareup.retrofit2:adapter-rxjava", version.ref = "retrofit" }
+
+[plugins]
+detekt = { id = "io.gitlab.arturbosch.detekt", version.ref = "detekt" }
+"#;
+
+    fn client() -> StubClient {
+        StubClient(HashMap::from([
+            ("core", vec!["2.3.0", "2.3.12", "3.0.0"]),
+            ("cio", vec!["2.3.0", "2.3.12"]),
+            ("retrofit", vec!["2.9.0", "2.11.0"]),
+            ("adapter-rxjava", vec!["2.9.0"]),
+        ]))
+    }
+
+    #[test]
+    fn proposes_only_versions_every_library_publishes() {
+        let doc: DocumentMut = CATALOG.parse().unwrap();
+        let client = client();
+        let plugin_client = StubClient(HashMap::from([(
+            "io.gitlab.arturbosch.detekt",
+            vec!["1.23.0", "1.23.6"],
+        )]));
+        let rules = MarkerRules::default();
+        let mut interaction = UpdateInteraction::new(false);
+        let mut handler = VersionHandler::new(
+            &client,
+            &plugin_client,
+            DefaultVersionStrategy::shared(),
+            &mut interaction,
+            &rules,
+        );
+
+        let report = handler.check(&doc, StabilityPolicy::StableOnly).unwrap();
+        let updates: Vec<_> = report.updates(Section::Versions).collect();
+        // Used only by a plugin, detekt is resolved through the plugin client
+        assert_eq!(
+            updates,
+            vec![("detekt", "1.23.0", "1.23.6"), ("ktor", "2.3.0", "2.3.12")]
+        );
+        let skipped: Vec<_> = report.skipped().collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0, "retrofit");
+        assert!(skipped[0].1.contains("retrofit → 2.11.0"));
+    }
+
+    #[test]
+    fn splits_an_alias_whose_libraries_diverged() {
+        let mut doc: DocumentMut = CATALOG.parse().unwrap();
+        let updates = split_version_alias(
+            &mut doc,
+            "retrofit",
+            &[
+                (
+                    Section::Libraries,
+                    "retrofit".to_string(),
+                    "2.11.0".to_string(),
+                ),
+                (
+                    Section::Libraries,
+                    "retrofit-rx".to_string(),
+                    "2.9.0".to_string(),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            updates,
+            vec![
+                ("retrofit".to_string(), "2.11.0".to_string()),
+                ("retrofit-rx".to_string(), "2.9.0".to_string()),
+            ]
+        );
+        let updated = doc.to_string();
+        assert!(updated.contains("retrofit = \"2.11.0\""));
+        assert!(updated.contains("retrofit-rx = \"2.9.0\""));
+        assert!(updated.contains(
+            "retrofit-rx = { module = \"com.squareup.retrofit2:adapter-rxjava\", version.ref = \"retrofit-rx\" }"
+        ));
+    }
+
+    #[test]
+    fn split_repoints_only_entries_of_the_listed_section() {
+        let mut doc: DocumentMut = r#"[versions]
+agp = "8.0.0"
+retrofit = "2.9.0"
+zstd = "1.5.0"
+
+[libraries]
+retrofit = { module = "com.squareup.retrofit2:retrofit", version.ref = "retrofit" }
+retrofit-rx = { module = "com.squareup.retrofit2:adapter-rxjava", version.ref = "retrofit" }
+
+[plugins]
+retrofit-rx = { id = "com.example.retrofit", version.ref = "retrofit" }
+"#
+        .parse()
+        .unwrap();
+        split_version_alias(
+            &mut doc,
+            "retrofit",
+            &[
+                (
+                    Section::Libraries,
+                    "retrofit".to_string(),
+                    "2.11.0".to_string(),
+                ),
+                (
+                    Section::Libraries,
+                    "retrofit-rx".to_string(),
+                    "2.9.0".to_string(),
+                ),
+            ],
+        );
+
+        let updated = doc.to_string();
+        assert!(updated.contains(
+            "retrofit-rx = { module = \"com.squareup.retrofit2:adapter-rxjava\", version.ref = \"retrofit-rx\" }"
+        ));
+        // The same-named plugin is not one of the split entries
+        assert!(updated.contains(
+            "retrofit-rx = { id = \"com.example.retrofit\", version.ref = \"retrofit\" }"
+        ));
+        // [versions] was sorted, so the new alias lands in order
+        let keys: Vec<_> = doc["versions"]
+            .as_table()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .collect();
+        assert_eq!(keys, vec!["agp", "retrofit", "retrofit-rx", "zstd"]);
+    }
+
+    #[test]
+    fn update_and_check_skip_unreferenced_aliases_alike() {
+        let catalog = r#"[versions]
+# gvc:pin
+unused = "1.0.0"
+"#;
+        let client = client();
+        let rules = MarkerRules::default();
+        let mut interaction = UpdateInteraction::new(false);
+        let mut handler = VersionHandler::new(
+            &client,
+            &client,
+            DefaultVersionStrategy::shared(),
+            &mut interaction,
+            &rules,
+        );
+
+        let mut doc: DocumentMut = catalog.parse().unwrap();
+        let updated = handler
+            .update(&mut doc, StabilityPolicy::StableOnly)
+            .unwrap();
+        let checked = handler.check(&doc, StabilityPolicy::StableOnly).unwrap();
+        assert_eq!(updated.skipped().count(), 0);
+        assert_eq!(checked.skipped().count(), 0);
+        assert_eq!(doc.to_string(), catalog);
+    }
 }
@@ -1,4 +1,6 @@
+use crate::agents::update::context::Section;
 use crate::error::{GvcError, Result};
+use crate::utils::output::{out, outln};
 use colored::Colorize;
 use std::fmt;
 use std::io::{self, Write};
@@ -60,6 +62,40 @@ impl UpdateInteraction {
         self.confirm(UpdateCategory::Plugin, name, old, new)
     }
 
+    /// Ask whether to split a version alias whose libraries no longer share a newer version
+    ///
+    /// Splitting changes the catalog layout, so it is never done without a prompt and
+    /// is not covered by a previous "all" answer.
+    pub fn confirm_split(
+        &mut self,
+        name: &str,
+        latest: &[(Section, String, String)],
+    ) -> Result<bool> {
+        if !self.enabled {
+            return Ok(false);
+        }
+
+        outln!(
+            "\n{} {} {}",
+            "[Version]".cyan().bold(),
+            name.white().bold(),
+            "has no newer version published for all of its libraries:".dimmed()
+        );
+        for (_, alias, version) in latest {
+            outln!("  • {} {}", alias, version.green());
+        }
+
+        out!(
+            "{}",
+            "Split it so each group of libraries gets its own alias? [y/N]: ".bold()
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+
     /// Internal confirm method
     fn confirm(
         &mut self,
@@ -73,7 +109,7 @@ impl UpdateInteraction {
         }
 
         let category_label = format!("[{}]", category);
-        println!(
+        outln!(
             "\n{} {} {} {} to {}",
             category_label.cyan().bold(),
             name.white().bold(),
@@ -83,12 +119,12 @@ impl UpdateInteraction {
         );
 
         if self.apply_all {
-            println!("{}", "Auto-applying (previously selected 'all').".dimmed());
+            outln!("{}", "Auto-applying (previously selected 'all').".dimmed());
             return Ok(true);
         }
 
         loop {
-            print!("{}", "Apply this update? [Y/n/a/q]: ".bold());
+            out!("{}", "Apply this update? [Y/n/a/q]: ".bold());
             io::stdout().flush()?;
 
             let mut input = String::new();
@@ -100,11 +136,11 @@ impl UpdateInteraction {
                     return Ok(true);
                 }
                 "n" | "no" => {
-                    println!("{}", "Skipping this update.".dimmed());
+                    outln!("{}", "Skipping this update.".dimmed());
                     return Ok(false);
                 }
                 "a" | "all" => {
-                    println!(
+                    outln!(
                         "{}",
                         "Applying this and all remaining updates.".green().bold()
                     );
@@ -112,11 +148,11 @@ impl UpdateInteraction {
                     return Ok(true);
                 }
                 "q" | "quit" => {
-                    println!("{}", "Stopping update process at user request.".yellow());
+                    outln!("{}", "Stopping update process at user request.".yellow());
                     return Err(GvcError::UserCancelled);
                 }
                 _ => {
-                    println!(
+                    outln!(
                         "{}",
                         "Please answer with y(es), n(o), a(ll), or q(quit).".red()
                     );
@@ -0,0 +1,434 @@
+use crate::agents::update::policy::StabilityPolicy;
+use crate::config::RulesConfig;
+use crate::error::Result;
+use crate::maven::version::Version;
+use crate::repository::{Coordinate, RepositoryClient, VersionStrategy};
+use crate::utils::glob::compile_glob;
+use crate::utils::toml::TomlUtils;
+use regex::Regex;
+use std::sync::OnceLock;
+use toml_edit::{DocumentMut, Item, Table};
+
+/// Update directives attached to a catalog entry through `# gvc:...` comments
+///
+/// Supported markers:
+/// - `# gvc:pin` keeps the entry on its current version
+/// - `# gvc:ignore` excludes the entry from update checks entirely
+/// - `# gvc:max=2.x` never proposes versions above the given line (or exact version)
+/// - `# gvc:allow-prerelease` accepts alpha/beta/RC candidates for this entry
+///
+/// Markers are read from comment lines directly above the entry and from a
+/// trailing comment on the same line. `pin` and `ignore` hold a description of
+/// where they were set, as they may also come from configuration rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryMarkers {
+    pub pin: Option<String>,
+    pub ignore: Option<String>,
+    pub max: Option<String>,
+    pub allow_prerelease: bool,
+}
+
+impl EntryMarkers {
+    /// Read the markers attached to `key` inside `table`, including matching rules
+    pub fn for_entry(table: &Table, key: &str, rules: &MarkerRules) -> Self {
+        let Some((key_repr, item)) = table.get_key_value(key) else {
+            return Self::default();
+        };
+
+        let mut markers = Self::from_decor(key_repr, item);
+        rules.apply(&mut markers, key, item);
+        markers
+    }
+
+    fn from_decor(key: &toml_edit::Key, item: &Item) -> Self {
+        let mut comments = String::new();
+        if let Some(prefix) = key.leaf_decor().prefix().and_then(|raw| raw.as_str()) {
+            comments.push_str(prefix);
+        }
+
+        match item {
+            Item::Value(value) => {
+                if let Some(suffix) = value.decor().suffix().and_then(|raw| raw.as_str()) {
+                    comments.push_str(suffix);
+                }
+            }
+            Item::Table(table) => {
+                let decor = table.decor();
+                for raw in [decor.prefix(), decor.suffix()].into_iter().flatten() {
+                    if let Some(text) = raw.as_str() {
+                        comments.push_str(text);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Self::parse(&comments)
+    }
+
+    /// Read the markers of a `[versions]` alias, including those on every library
+    /// or plugin that references it (a pinned library freezes its shared version)
+    pub fn for_version_alias(doc: &DocumentMut, version_key: &str, rules: &MarkerRules) -> Self {
+        let mut markers = doc
+            .get("versions")
+            .and_then(|v| v.as_table())
+            .map(|versions| Self::for_entry(versions, version_key, rules))
+            .unwrap_or_default();
+
+        for section in ["libraries", "plugins"] {
+            if let Some(entries) = doc.get(section).and_then(|v| v.as_table()) {
+                for (name, item) in entries.iter() {
+                    if TomlUtils::uses_version_ref(item, version_key) {
+                        markers.merge(&Self::for_entry(entries, name, rules));
+                    }
+                }
+            }
+        }
+
+        markers
+    }
+
+    /// Parse every `gvc:` directive found in comment text
+    pub fn parse(comments: &str) -> Self {
+        static MARKER: OnceLock<Regex> = OnceLock::new();
+        let marker = MARKER.get_or_init(|| {
+            Regex::new(r"gvc:([a-z][a-z-]*)(?:\s*=\s*([^\s,#]+))?").expect("valid marker regex")
+        });
+
+        let mut markers = Self::default();
+        for line in comments.lines() {
+            let Some(comment_start) = line.find('#') else {
+                continue;
+            };
+
+            for cap in marker.captures_iter(&line[comment_start..]) {
+                let value = cap.get(2).map(|m| m.as_str().to_string());
+                match &cap[1] {
+                    "pin" => markers.pin = Some("# gvc:pin".to_string()),
+                    "ignore" => markers.ignore = Some("# gvc:ignore".to_string()),
+                    "allow-prerelease" => markers.allow_prerelease = true,
+                    "max" if value.is_some() => markers.max = value,
+                    _ => {}
+                }
+            }
+        }
+
+        markers
+    }
+
+    /// Combine markers from a related entry (e.g. a library sharing a version alias)
+    pub fn merge(&mut self, other: &EntryMarkers) {
+        if self.pin.is_none() {
+            self.pin = other.pin.clone();
+        }
+        if self.ignore.is_none() {
+            self.ignore = other.ignore.clone();
+        }
+        self.allow_prerelease |= other.allow_prerelease;
+        if self.max.is_none() {
+            self.max = other.max.clone();
+        }
+    }
+
+    /// Reason for leaving the entry untouched, if any
+    pub fn skip_reason(&self) -> Option<String> {
+        if let Some(source) = &self.ignore {
+            Some(format!("ignored ({})", source))
+        } else {
+            self.pin
+                .as_ref()
+                .map(|source| format!("pinned ({})", source))
+        }
+    }
+
+    /// Whether only stable candidates may be offered for this entry
+    pub fn stable_only_for(&self, policy: StabilityPolicy, current: &str) -> bool {
+        !self.allow_prerelease && policy.stable_only_for(current)
+    }
+
+    /// Whether `candidate` satisfies the `max` constraint
+    pub fn accepts(&self, candidate: &str) -> bool {
+        match &self.max {
+            Some(max) => within_max(candidate, max),
+            None => true,
+        }
+    }
+}
+
+/// Ignore/pin rules from the gvc configuration, applied on top of comment markers
+///
+/// Patterns match an entry's alias, its `group:artifact` coordinate or its plugin id.
+#[derive(Debug, Clone, Default)]
+pub struct MarkerRules {
+    ignore: Vec<(String, Regex)>,
+    pin: Vec<(String, Regex)>,
+    allow_prerelease: Vec<(String, Regex)>,
+}
+
+impl MarkerRules {
+    pub fn from_config(config: &RulesConfig) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<(String, Regex)>> {
+            patterns
+                .iter()
+                .map(|pattern| Ok((pattern.clone(), compile_glob(pattern)?)))
+                .collect()
+        };
+
+        Ok(Self {
+            ignore: compile(&config.ignore)?,
+            pin: compile(&config.pin)?,
+            allow_prerelease: compile(&config.allow_prerelease)?,
+        })
+    }
+
+    fn apply(&self, markers: &mut EntryMarkers, alias: &str, item: &Item) {
+        let mut identifiers = vec![alias.to_string()];
+        if let Some((group, artifact)) = TomlUtils::extract_group_artifact(item) {
+            identifiers.push(format!("{}:{}", group, artifact));
+        }
+        if let Some(id) = item
+            .as_table_like()
+            .and_then(|table| table.get("id"))
+            .and_then(|id| id.as_str())
+        {
+            identifiers.push(id.to_string());
+        }
+
+        let matching = |rules: &[(String, Regex)]| {
+            rules
+                .iter()
+                .find(|(_, regex)| identifiers.iter().any(|id| regex.is_match(id)))
+                .map(|(pattern, _)| format!("config rule '{}'", pattern))
+        };
+
+        if markers.ignore.is_none() {
+            markers.ignore = matching(&self.ignore);
+        }
+        if markers.pin.is_none() {
+            markers.pin = matching(&self.pin);
+        }
+        if !markers.allow_prerelease {
+            markers.allow_prerelease = matching(&self.allow_prerelease).is_some();
+        }
+    }
+}
+
+/// Versions found for an entry after applying the policy and its markers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resolution {
+    /// Newest allowed version, which may be the current one
+    pub latest: Option<String>,
+    /// Allowed versions newer than the current one, oldest first
+    pub candidates: Vec<String>,
+    /// Repository the versions were read from
+    pub repository: Option<String>,
+}
+
+impl Resolution {
+    /// Pick the newest of `versions` and the upgrades over `current` that `markers` allow
+    pub fn from_versions(
+        versions: Vec<String>,
+        repository: Option<String>,
+        strategy: &dyn VersionStrategy,
+        current: &str,
+        stable_only: bool,
+        markers: &EntryMarkers,
+    ) -> Self {
+        let allowed: Vec<String> = versions
+            .into_iter()
+            .filter(|version| markers.accepts(version))
+            .collect();
+        let latest = strategy.select_latest(&allowed, stable_only);
+
+        let mut candidates: Vec<Version> = allowed
+            .iter()
+            .filter(|version| *version != current && strategy.is_upgrade(current, version))
+            .map(|version| Version::parse(version))
+            .filter(|version| !stable_only || version.is_stable())
+            .collect();
+        candidates.sort();
+
+        Self {
+            latest,
+            candidates: candidates.into_iter().map(|v| v.original).collect(),
+            repository,
+        }
+    }
+}
+
+/// Fetch the versions of `coordinate` allowed by both the policy and the markers
+pub fn resolve_latest(
+    client: &(dyn RepositoryClient + Send + Sync),
+    strategy: &dyn VersionStrategy,
+    coordinate: &Coordinate,
+    current: &str,
+    policy: StabilityPolicy,
+    markers: &EntryMarkers,
+) -> Result<Resolution> {
+    let listing = client.fetch_version_listing(coordinate)?;
+    Ok(Resolution::from_versions(
+        listing.versions,
+        listing.repository,
+        strategy,
+        current,
+        markers.stable_only_for(policy, current),
+        markers,
+    ))
+}
+
+/// Compare a candidate against a `max` marker such as `2.x`, `2.3.*` or `2.3.1`
+fn within_max(candidate: &str, max: &str) -> bool {
+    let parts: Vec<&str> = max.split('.').collect();
+    let wildcard = parts
+        .iter()
+        .position(|part| part.eq_ignore_ascii_case("x") || *part == "*");
+
+    let Some(wildcard) = wildcard else {
+        return Version::parse(candidate) <= Version::parse(max);
+    };
+
+    let limit: Vec<u64> = parts[..wildcard]
+        .iter()
+        .map(|part| leading_number(part).unwrap_or(0))
+        .collect();
+
+    let components: Vec<u64> = candidate
+        .split(['.', '-'])
+        .take(limit.len())
+        .map(|part| leading_number(part).unwrap_or(0))
+        .collect();
+
+    components <= limit
+}
+
+fn leading_number(part: &str) -> Option<u64> {
+    let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_markers_from_leading_and_trailing_comments() {
+        let doc: DocumentMut = r#"
+[versions]
+# gvc:pin
+kotlin = "1.9.0"
+ktor = "2.3.12" # gvc:max=2.x gvc:allow-prerelease
+
+[libraries]
+okhttp = { module = "com.squareup.okhttp3:okhttp", version = "4.12.0" } # gvc:ignore
+"#
+        .parse()
+        .unwrap();
+
+        let rules = MarkerRules::default();
+        let versions = doc["versions"].as_table().unwrap();
+        assert!(
+            EntryMarkers::for_entry(versions, "kotlin", &rules)
+                .pin
+                .is_some()
+        );
+
+        let ktor = EntryMarkers::for_entry(versions, "ktor", &rules);
+        assert_eq!(ktor.max.as_deref(), Some("2.x"));
+        assert!(ktor.allow_prerelease);
+        assert!(ktor.pin.is_none());
+
+        let libraries = doc["libraries"].as_table().unwrap();
+        let okhttp = EntryMarkers::for_entry(libraries, "okhttp", &rules);
+        assert!(okhttp.ignore.is_some());
+        assert_eq!(
+            okhttp.skip_reason().as_deref(),
+            Some("ignored (# gvc:ignore)")
+        );
+    }
+
+    #[test]
+    fn reads_markers_above_table_headers() {
+        let doc: DocumentMut = r#"
+[libraries]
+plain = "com.test:plain:1.0.0"
+
+# gvc:pin
+[libraries.frozen]
+module = "com.test:frozen"
+version = "1.0.0"
+"#
+        .parse()
+        .unwrap();
+
+        let rules = MarkerRules::default();
+        let libraries = doc["libraries"].as_table().unwrap();
+        assert!(
+            EntryMarkers::for_entry(libraries, "frozen", &rules)
+                .pin
+                .is_some()
+        );
+        assert_eq!(
+            EntryMarkers::for_entry(libraries, "plain", &rules),
+            EntryMarkers::default()
+        );
+    }
+
+    #[test]
+    fn config_rules_match_alias_or_coordinate() {
+        let doc: DocumentMut = r#"
+[versions]
+kotlin = "2.0.0"
+
+[libraries]
+okhttp = { module = "com.squareup.okhttp3:okhttp", version = "4.12.0" }
+kotlin-stdlib = { module = "org.jetbrains.kotlin:kotlin-stdlib", version.ref = "kotlin" }
+"#
+        .parse()
+        .unwrap();
+
+        let rules = MarkerRules::from_config(&RulesConfig {
+            ignore: vec!["com.squareup.*".to_string()],
+            pin: vec!["kotlin-stdlib".to_string()],
+            allow_prerelease: Vec::new(),
+        })
+        .unwrap();
+
+        let libraries = doc["libraries"].as_table().unwrap();
+        let okhttp = EntryMarkers::for_entry(libraries, "okhttp", &rules);
+        assert_eq!(
+            okhttp.skip_reason().as_deref(),
+            Some("ignored (config rule 'com.squareup.*')")
+        );
+
+        // Pinning a library freezes the version alias it references
+        let kotlin = EntryMarkers::for_version_alias(&doc, "kotlin", &rules);
+        assert_eq!(
+            kotlin.skip_reason().as_deref(),
+            Some("pinned (config rule 'kotlin-stdlib')")
+        );
+    }
+
+    #[test]
+    fn max_marker_limits_release_lines() {
+        let markers = EntryMarkers::parse("# gvc:max=2.x");
+        assert!(markers.accepts("2.3.12"));
+        assert!(markers.accepts("2.99.0-beta1"));
+        assert!(!markers.accepts("3.0.0"));
+
+        let minor = EntryMarkers::parse("# gvc:max=1.9.*");
+        assert!(minor.accepts("1.9.24"));
+        assert!(!minor.accepts("1.10.0"));
+
+        let exact = EntryMarkers::parse("# gvc:max=4.12.0");
+        assert!(exact.accepts("4.11.0"));
+        assert!(exact.accepts("4.12.0"));
+        assert!(!exact.accepts("4.12.1"));
+    }
+
+    #[test]
+    fn allow_prerelease_overrides_stable_policy() {
+        let markers = EntryMarkers::parse("# gvc:allow-prerelease");
+        assert!(!markers.stable_only_for(StabilityPolicy::StableOnly, "1.0.0"));
+        assert!(EntryMarkers::default().stable_only_for(StabilityPolicy::StableOnly, "1.0.0"));
+    }
+}
@@ -8,10 +8,15 @@
 // - UpdateReport: Tracks changes made during updates
 // - Handlers: Specific handlers for different update types (versions, libraries, plugins)
 // - UpdateInteraction: Manages user interaction for interactive updates
+// - StabilityPolicy: Decides which release channels are eligible per entry
+// - EntryMarkers: `# gvc:...` comment directives that pin or constrain entries
 pub mod context;
 pub mod handlers;
 pub mod interaction;
+pub mod markers;
+pub mod policy;
 
 pub use context::UpdateReport;
+pub use policy::StabilityPolicy;
 // Note: UpdateContext and UpdateType are intentionally not exported
 // as they're internal implementation details
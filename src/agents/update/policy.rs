@@ -0,0 +1,68 @@
+use crate::maven::version::Version;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Controls which release channels are eligible when looking for newer versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StabilityPolicy {
+    /// Only stable releases are considered
+    #[default]
+    StableOnly,
+    /// Any release is considered, including alpha, beta and RC builds
+    IncludeUnstable,
+    /// Entries already on a pre-release may move to newer pre-releases (or the
+    /// stable release once it ships); entries on a stable release stay stable
+    TrackChannel,
+}
+
+impl StabilityPolicy {
+    /// Whether only stable candidates may be offered for an entry currently on `current`
+    pub fn stable_only_for(self, current: &str) -> bool {
+        match self {
+            StabilityPolicy::StableOnly => true,
+            StabilityPolicy::IncludeUnstable => false,
+            StabilityPolicy::TrackChannel => Version::parse(current).is_stable(),
+        }
+    }
+}
+
+impl fmt::Display for StabilityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            StabilityPolicy::StableOnly => "stable",
+            StabilityPolicy::IncludeUnstable => "all",
+            StabilityPolicy::TrackChannel => "current channel",
+        };
+        f.write_str(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_kebab_case_names() {
+        #[derive(serde::Deserialize)]
+        struct Wrapper {
+            stability: StabilityPolicy,
+        }
+        let parsed: Wrapper = toml::from_str("stability = \"track-channel\"").unwrap();
+        assert_eq!(parsed.stability, StabilityPolicy::TrackChannel);
+    }
+
+    #[test]
+    fn track_channel_keeps_stable_entries_on_stable() {
+        let policy = StabilityPolicy::TrackChannel;
+        assert!(policy.stable_only_for("1.7.5"));
+        assert!(!policy.stable_only_for("1.8.0-beta01"));
+        assert!(!policy.stable_only_for("2.0.0-RC1"));
+    }
+
+    #[test]
+    fn fixed_policies_ignore_current_version() {
+        assert!(StabilityPolicy::StableOnly.stable_only_for("1.8.0-beta01"));
+        assert!(!StabilityPolicy::IncludeUnstable.stable_only_for("1.7.5"));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::agents::catalog_editor::read_bundles;
+use crate::error::{GvcError, Result};
+use crate::gradle::build_scripts::CatalogReference;
+use crate::gradle::{BuildScriptScanner, catalog_accessor};
+use crate::utils::toml::TomlUtils;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+/// Catalog entries that no build script or convention plugin references
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnusedEntries {
+    pub libraries: Vec<String>,
+    pub plugins: Vec<String>,
+    pub bundles: Vec<String>,
+    pub versions: Vec<String>,
+}
+
+impl UnusedEntries {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.libraries.len() + self.plugins.len() + self.bundles.len() + self.versions.len()
+    }
+}
+
+/// UsageAnalyzer maps accessor usages in build logic back to catalog aliases
+pub struct UsageAnalyzer {
+    project_path: PathBuf,
+    catalog_path: PathBuf,
+    catalog_name: String,
+}
+
+impl UsageAnalyzer {
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(project_path: P, catalog_path: Q) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            catalog_path: catalog_path.as_ref().to_path_buf(),
+            catalog_name: "libs".to_string(),
+        }
+    }
+
+    /// Find entries that are never referenced
+    pub fn find_unused(&self) -> Result<UnusedEntries> {
+        let content = fs::read_to_string(&self.catalog_path)?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| GvcError::TomlParsing(format!("Failed to parse catalog: {}", e)))?;
+
+        let references =
+            BuildScriptScanner::new(&self.project_path).catalog_references(&self.catalog_name)?;
+        Ok(analyze(&doc, &references))
+    }
+}
+
+/// Work out which aliases are unused given the references found in build logic
+///
+/// Bundle members count as used when the bundle is, and a version alias counts as
+/// used when a used entry references it or build logic reads it directly.
+pub fn analyze(doc: &DocumentMut, references: &[CatalogReference]) -> UnusedEntries {
+    let aliases = |section: &str| -> Vec<String> {
+        doc.get(section)
+            .and_then(|v| v.as_table())
+            .map(|table| table.iter().map(|(key, _)| key.to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    let mut used: HashSet<(&str, String)> = HashSet::new();
+    for section in ["libraries", "plugins", "versions", "bundles"] {
+        let candidates = aliases(section);
+        for reference in references.iter().filter(|r| r.section == section) {
+            if let Some(alias) = resolve_reference(&candidates, section, reference) {
+                used.insert((section, alias));
+            }
+        }
+    }
+
+    // Members of used bundles are used
+    let libraries = aliases("libraries");
+    let members: Vec<String> = read_bundles(doc)
+        .into_iter()
+        .filter(|bundle| used.contains(&("bundles", bundle.name.clone())))
+        .flat_map(|bundle| bundle.members)
+        .collect();
+    for member in members {
+        let key = TomlUtils::accessor_key(&member);
+        if let Some(alias) = libraries
+            .iter()
+            .find(|alias| TomlUtils::accessor_key(alias) == key)
+        {
+            used.insert(("libraries", alias.clone()));
+        }
+    }
+
+    // Version aliases referenced by used libraries and plugins are used
+    for section in ["libraries", "plugins"] {
+        let Some(table) = doc.get(section).and_then(|v| v.as_table()) else {
+            continue;
+        };
+        for (alias, item) in table.iter() {
+            if !used.contains(&(section, alias.to_string())) {
+                continue;
+            }
+            if let Some(version_ref) = TomlUtils::extract_version_ref(item) {
+                used.insert(("versions", version_ref));
+            }
+        }
+    }
+
+    let unused = |section: &'static str| -> Vec<String> {
+        aliases(section)
+            .into_iter()
+            .filter(|alias| !used.contains(&(section, alias.clone())))
+            .collect()
+    };
+
+    UnusedEntries {
+        libraries: unused("libraries"),
+        plugins: unused("plugins"),
+        bundles: unused("bundles"),
+        versions: unused("versions"),
+    }
+}
+
+/// Pick the alias a reference points to; the longest accessor wins (`libs.ktor.core`
+/// uses `ktor-core`, not `ktor`)
+pub(crate) fn resolve_reference(
+    aliases: &[String],
+    section: &str,
+    reference: &CatalogReference,
+) -> Option<String> {
+    if reference.exact {
+        let wanted = TomlUtils::accessor_key(&reference.path);
+        return aliases
+            .iter()
+            .find(|alias| TomlUtils::accessor_key(alias) == wanted)
+            .cloned();
+    }
+
+    let prefix_len = catalog_accessor("", section, "").len();
+    aliases
+        .iter()
+        .filter(|alias| {
+            let dotted = &catalog_accessor("", section, alias)[prefix_len..];
+            reference
+                .path
+                .strip_prefix(dotted)
+                .is_some_and(|tail| tail.is_empty() || tail.starts_with('.'))
+        })
+        .max_by_key(|alias| alias.len())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(section: &'static str, path: &str) -> CatalogReference {
+        CatalogReference {
+            section,
+            path: path.to_string(),
+            exact: false,
+        }
+    }
+
+    const CATALOG: &str = r#"[versions]
+ktor = "2.3.12"
+okhttp = "4.12.0"
+jvm-target = "17"
+kotlin = "2.0.0"
+
+[libraries]
+ktor = { module = "io.ktor:ktor-bom", version.ref = "ktor" }
+ktor-core = { module = "io.ktor:ktor-client-core", version.ref = "ktor" }
+ktor-cio = { module = "io.ktor:ktor-client-cio", version.ref = "ktor" }
+okhttp = { module = "com.squareup.okhttp3:okhttp", version.ref = "okhttp" }
+
+[bundles]
+network = ["ktor-cio"]
+legacy = ["okhttp"]
+
+[plugins]
+kotlin-jvm = { id = "org.jetbrains.kotlin.jvm", version.ref = "kotlin" }
+"#;
+
+    #[test]
+    fn reports_entries_without_references() {
+        let doc: DocumentMut = CATALOG.parse().unwrap();
+        let unused = analyze(
+            &doc,
+            &[
+                reference("libraries", "ktor.core"),
+                reference("bundles", "network"),
+                reference("versions", "jvm.target.get"),
+            ],
+        );
+
+        assert_eq!(unused.libraries, vec!["ktor", "okhttp"]);
+        assert_eq!(unused.plugins, vec!["kotlin-jvm"]);
+        assert_eq!(unused.bundles, vec!["legacy"]);
+        assert_eq!(unused.versions, vec!["okhttp", "kotlin"]);
+    }
+
+    #[test]
+    fn exact_references_use_accessor_normalization() {
+        let doc: DocumentMut = CATALOG.parse().unwrap();
+        let unused = analyze(
+            &doc,
+            &[CatalogReference {
+                section: "plugins",
+                path: "kotlin.jvm".to_string(),
+                exact: true,
+            }],
+        );
+
+        assert!(unused.plugins.is_empty());
+        assert!(!unused.versions.contains(&"kotlin".to_string()));
+    }
+}
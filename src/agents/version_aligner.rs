@@ -0,0 +1,478 @@
+use crate::agents::catalog_editor::entry_version;
+use crate::agents::update::markers::{EntryMarkers, MarkerRules};
+use crate::agents::update::policy::StabilityPolicy;
+use crate::error::{GvcError, Result};
+use crate::gradle::GradleBuild;
+use crate::maven::version::Version;
+use crate::utils::diff::FileEdit;
+use crate::utils::toml::TomlUtils;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Value};
+
+/// One catalog entry declaring a dependency shared between builds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedEntry {
+    pub build: String,
+    pub catalog: PathBuf,
+    pub section: &'static str,
+    pub alias: String,
+    pub version: String,
+    /// Why the entry keeps its version, when markers or rules hold it back
+    pub held: Option<String>,
+}
+
+/// A dependency declared with different versions in different builds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misalignment {
+    /// `group:artifact` for libraries, the plugin id for plugins
+    pub module: String,
+    /// Highest version in use that the stability policy lets the others move to
+    pub target: String,
+    pub entries: Vec<SharedEntry>,
+}
+
+impl Misalignment {
+    /// Entries that are behind the target version
+    pub fn lagging(&self) -> impl Iterator<Item = &SharedEntry> {
+        let target = Version::parse(&self.target);
+        self.entries
+            .iter()
+            .filter(move |entry| Version::parse(&entry.version) < target)
+    }
+
+    /// Lagging entries that no marker or rule holds back
+    pub fn alignable(&self) -> impl Iterator<Item = &SharedEntry> {
+        self.lagging().filter(|entry| entry.held.is_none())
+    }
+}
+
+/// VersionAligner keeps dependencies shared between builds of a workspace on one version
+pub struct VersionAligner<'a> {
+    builds: &'a [GradleBuild],
+    pending: &'a [FileEdit],
+    rules: MarkerRules,
+    policy: StabilityPolicy,
+}
+
+impl<'a> VersionAligner<'a> {
+    pub fn new(builds: &'a [GradleBuild]) -> Self {
+        Self {
+            builds,
+            pending: &[],
+            rules: MarkerRules::default(),
+            policy: StabilityPolicy::default(),
+        }
+    }
+
+    /// Only align to a pre-release when the update path would offer it to every lagging entry
+    pub fn with_policy(mut self, policy: StabilityPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Honour ignore/pin rules from the configuration besides `# gvc:` markers
+    pub fn with_rules(mut self, rules: MarkerRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Read catalogs through edits that have not been written yet, as a dry run does
+    pub fn with_pending(mut self, edits: &'a [FileEdit]) -> Self {
+        self.pending = edits;
+        self
+    }
+
+    /// Dependencies and plugins used by several builds at different versions, by module
+    pub fn find_misalignments(&self) -> Result<Vec<Misalignment>> {
+        let mut modules: BTreeMap<String, Vec<(SharedEntry, EntryMarkers)>> = BTreeMap::new();
+
+        for build in self.builds {
+            for catalog in &build.catalogs {
+                let doc = self.load(&catalog.path)?;
+                for section in ["libraries", "plugins"] {
+                    let Some(table) = doc.get(section).and_then(|v| v.as_table()) else {
+                        continue;
+                    };
+                    for (alias, item) in table.iter() {
+                        let (Some(module), Some(version)) =
+                            (module_key(section, item), entry_version(&doc, item))
+                        else {
+                            continue;
+                        };
+                        let markers = match TomlUtils::extract_version_ref(item) {
+                            Some(version_ref) => {
+                                EntryMarkers::for_version_alias(&doc, &version_ref, &self.rules)
+                            }
+                            None => EntryMarkers::for_entry(table, alias, &self.rules),
+                        };
+                        let entry = SharedEntry {
+                            build: build.name.clone(),
+                            catalog: catalog.path.clone(),
+                            section,
+                            alias: alias.to_string(),
+                            version,
+                            held: None,
+                        };
+                        modules.entry(module).or_default().push((entry, markers));
+                    }
+                }
+            }
+        }
+
+        Ok(modules
+            .into_iter()
+            .filter(|(_, entries)| {
+                let (first, _) = &entries[0];
+                entries.iter().any(|(entry, _)| entry.build != first.build)
+                    && entries
+                        .iter()
+                        .any(|(entry, _)| entry.version != first.version)
+            })
+            .filter_map(|(module, entries)| {
+                let target = self.target(&entries)?;
+                let entries = entries
+                    .into_iter()
+                    .map(|(entry, markers)| SharedEntry {
+                        held: self.held_reason(&markers, &entry.version, &target),
+                        ..entry
+                    })
+                    .collect();
+                Some(Misalignment {
+                    module,
+                    target,
+                    entries,
+                })
+            })
+            .collect())
+    }
+
+    /// Plan moving lagging entries to the target version, with how many entries change
+    ///
+    /// Entries using `version.ref` update their `[versions]` alias, so libraries
+    /// sharing that alias in the same catalog move along. Held entries stay put.
+    pub fn plan_alignment(&self, misalignments: &[Misalignment]) -> Result<(usize, Vec<FileEdit>)> {
+        let mut by_catalog: BTreeMap<&PathBuf, Vec<(&SharedEntry, &str)>> = BTreeMap::new();
+        for misalignment in misalignments {
+            for entry in misalignment.alignable() {
+                by_catalog
+                    .entry(&entry.catalog)
+                    .or_default()
+                    .push((entry, &misalignment.target));
+            }
+        }
+
+        let mut changed = 0;
+        let mut edits = Vec::new();
+        for (path, entries) in by_catalog {
+            let original = self.read(path)?;
+            let mut doc = parse(path, &original)?;
+            for (entry, target) in entries {
+                if set_entry_version(&mut doc, entry.section, &entry.alias, target) {
+                    changed += 1;
+                }
+            }
+            edits.push(FileEdit {
+                path: path.clone(),
+                original,
+                updated: doc.to_string(),
+            });
+        }
+
+        Ok((changed, edits))
+    }
+
+    /// The highest version in use that is stable, or that every entry below it may take
+    /// as a pre-release; the highest one when none qualifies
+    fn target(&self, entries: &[(SharedEntry, EntryMarkers)]) -> Option<String> {
+        let mut versions: Vec<Version> = entries
+            .iter()
+            .map(|(entry, _)| Version::parse(&entry.version))
+            .collect();
+        versions.sort();
+        let allowed = |target: &Version| {
+            target.is_stable()
+                || entries.iter().all(|(entry, markers)| {
+                    Version::parse(&entry.version) >= *target
+                        || !markers.stable_only_for(self.policy, &entry.version)
+                })
+        };
+        versions
+            .iter()
+            .rev()
+            .find(|version| allowed(version))
+            .or(versions.last())
+            .map(|version| version.original.clone())
+    }
+
+    /// Why `markers` or the stability policy keep an entry on `current` instead of `target`
+    fn held_reason(&self, markers: &EntryMarkers, current: &str, target: &str) -> Option<String> {
+        markers.skip_reason().or_else(|| {
+            if let Some(max) = markers.max.as_ref().filter(|_| !markers.accepts(target)) {
+                Some(format!("capped (# gvc:max={})", max))
+            } else if !Version::parse(target).is_stable()
+                && markers.stable_only_for(self.policy, current)
+            {
+                Some("pre-release (stability policy)".to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    fn read(&self, path: &Path) -> Result<String> {
+        match self.pending.iter().find(|edit| edit.path == path) {
+            Some(edit) => Ok(edit.updated.clone()),
+            None => Ok(fs::read_to_string(path)?),
+        }
+    }
+
+    fn load(&self, path: &Path) -> Result<DocumentMut> {
+        parse(path, &self.read(path)?)
+    }
+}
+
+fn parse(path: &Path, content: &str) -> Result<DocumentMut> {
+    content.parse::<DocumentMut>().map_err(|e| {
+        GvcError::TomlParsing(format!(
+            "Failed to parse catalog '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn module_key(section: &str, item: &Item) -> Option<String> {
+    if section == "plugins" {
+        return TomlUtils::extract_plugin_id(item);
+    }
+
+    TomlUtils::extract_group_artifact(item).map(|(group, artifact)| format!("{group}:{artifact}"))
+}
+
+fn set_entry_version(doc: &mut DocumentMut, section: &str, alias: &str, version: &str) -> bool {
+    let version_ref = doc
+        .get(section)
+        .and_then(|table| table.get(alias))
+        .and_then(TomlUtils::extract_version_ref);
+
+    if let Some(version_ref) = version_ref {
+        return doc
+            .get_mut("versions")
+            .and_then(|versions| versions.get_mut(&version_ref))
+            .is_some_and(|item| TomlUtils::update_version(item, version));
+    }
+
+    let Some(item) = doc.get_mut(section).and_then(|table| table.get_mut(alias)) else {
+        return false;
+    };
+    match item.as_str().and_then(|raw| raw.rsplit_once(':')) {
+        Some((coordinate, _)) => {
+            *item = Item::Value(Value::from(format!("{}:{}", coordinate, version)));
+            true
+        }
+        None => TomlUtils::update_version(item, version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gradle::CatalogFile;
+    use tempfile::tempdir;
+
+    #[test]
+    fn aligns_shared_dependencies_to_the_highest_version() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("app.versions.toml");
+        let tools = dir.path().join("tools.versions.toml");
+        fs::write(
+            &app,
+            "[versions]\nokhttp = \"4.12.0\"\n\n[libraries]\nokhttp = { module = \"com.squareup.okhttp3:okhttp\", version.ref = \"okhttp\" }\nonly-here = \"com.example:local:1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &tools,
+            "[libraries]\nhttp = \"com.squareup.okhttp3:okhttp:4.9.3\"\nlocal = \"com.example:local:1.0\"\n",
+        )
+        .unwrap();
+
+        let builds = vec![
+            GradleBuild {
+                name: "app".into(),
+                path: dir.path().to_path_buf(),
+                catalogs: vec![CatalogFile {
+                    name: "libs".into(),
+                    path: app.clone(),
+                }],
+            },
+            GradleBuild {
+                name: "tools".into(),
+                path: dir.path().to_path_buf(),
+                catalogs: vec![CatalogFile {
+                    name: "libs".into(),
+                    path: tools.clone(),
+                }],
+            },
+        ];
+
+        let aligner = VersionAligner::new(&builds);
+        let misalignments = aligner.find_misalignments().unwrap();
+        assert_eq!(misalignments.len(), 1);
+        assert_eq!(misalignments[0].module, "com.squareup.okhttp3:okhttp");
+        assert_eq!(misalignments[0].target, "4.12.0");
+
+        let (changed, edits) = aligner.plan_alignment(&misalignments).unwrap();
+        assert_eq!(changed, 1);
+        for edit in &edits {
+            edit.write().unwrap();
+        }
+        assert!(
+            fs::read_to_string(&tools)
+                .unwrap()
+                .contains("http = \"com.squareup.okhttp3:okhttp:4.12.0\"")
+        );
+        assert!(aligner.find_misalignments().unwrap().is_empty());
+    }
+
+    #[test]
+    fn plans_alignment_through_pending_edits_without_writing() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("app.versions.toml");
+        let tools = dir.path().join("tools.versions.toml");
+        fs::write(
+            &app,
+            "[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.9.3\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &tools,
+            "[libraries]\nhttp = \"com.squareup.okhttp3:okhttp:4.9.3\"\n",
+        )
+        .unwrap();
+        let build = |name: &str, path: &PathBuf| GradleBuild {
+            name: name.into(),
+            path: dir.path().to_path_buf(),
+            catalogs: vec![CatalogFile {
+                name: "libs".into(),
+                path: path.clone(),
+            }],
+        };
+        let builds = vec![build("app", &app), build("tools", &tools)];
+        let pending = vec![FileEdit {
+            path: app.clone(),
+            original: fs::read_to_string(&app).unwrap(),
+            updated: "[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\n".into(),
+        }];
+
+        let aligner = VersionAligner::new(&builds).with_pending(&pending);
+        let misalignments = aligner.find_misalignments().unwrap();
+        let (changed, edits) = aligner.plan_alignment(&misalignments).unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(edits[0].path, tools);
+        assert!(edits[0].updated.contains("okhttp:4.12.0"));
+        assert!(fs::read_to_string(&tools).unwrap().contains("okhttp:4.9.3"));
+    }
+
+    #[test]
+    fn leaves_entries_held_by_markers_and_rules() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("app.versions.toml");
+        let tools = dir.path().join("tools.versions.toml");
+        fs::write(
+            &app,
+            "[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\nktor = \"io.ktor:ktor-client-core:2.3.12\"\ncoil = \"io.coil-kt:coil:2.6.0\"\nretrofit = \"com.squareup.retrofit2:retrofit:2.11.0\"\n",
+        )
+        .unwrap();
+        let tools_catalog = "[versions]\nktor = \"2.0.0\" # gvc:max=2.2.x\n\n[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.9.3\" # gvc:pin\nktor = { module = \"io.ktor:ktor-client-core\", version.ref = \"ktor\" }\ncoil = \"io.coil-kt:coil:2.5.0\"\nretrofit = \"com.squareup.retrofit2:retrofit:2.9.0\"\n";
+        fs::write(&tools, tools_catalog).unwrap();
+        let build = |name: &str, path: &PathBuf| GradleBuild {
+            name: name.into(),
+            path: dir.path().to_path_buf(),
+            catalogs: vec![CatalogFile {
+                name: "libs".into(),
+                path: path.clone(),
+            }],
+        };
+        let builds = vec![build("app", &app), build("tools", &tools)];
+        let rules = MarkerRules::from_config(&crate::config::RulesConfig {
+            ignore: vec!["io.coil-kt:*".into()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let aligner = VersionAligner::new(&builds).with_rules(rules);
+        let misalignments = aligner.find_misalignments().unwrap();
+        assert_eq!(misalignments.len(), 4);
+        let held: Vec<(&str, Option<&str>)> = misalignments
+            .iter()
+            .flat_map(|misalignment| misalignment.lagging())
+            .map(|entry| (entry.alias.as_str(), entry.held.as_deref()))
+            .collect();
+        assert_eq!(
+            held,
+            vec![
+                ("okhttp", Some("pinned (# gvc:pin)")),
+                ("retrofit", None),
+                ("coil", Some("ignored (config rule 'io.coil-kt:*')")),
+                ("ktor", Some("capped (# gvc:max=2.2.x)")),
+            ]
+        );
+
+        let (changed, edits) = aligner.plan_alignment(&misalignments).unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(
+            edits[0].updated,
+            tools_catalog.replace("retrofit:2.9.0", "retrofit:2.11.0")
+        );
+    }
+
+    #[test]
+    fn aligns_to_a_pre_release_only_when_the_policy_allows_it() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("app.versions.toml");
+        let tools = dir.path().join("tools.versions.toml");
+        let lib = dir.path().join("lib.versions.toml");
+        fs::write(
+            &app,
+            "[libraries]\nkoin = \"io.insert-koin:koin-core:4.1.0-alpha01\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &tools,
+            "[libraries]\nkoin = \"io.insert-koin:koin-core:4.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            &lib,
+            "[libraries]\nkoin = \"io.insert-koin:koin-core:3.5.6\"\n",
+        )
+        .unwrap();
+        let build = |name: &str, path: &PathBuf| GradleBuild {
+            name: name.into(),
+            path: dir.path().to_path_buf(),
+            catalogs: vec![CatalogFile {
+                name: "libs".into(),
+                path: path.clone(),
+            }],
+        };
+        let builds = vec![
+            build("app", &app),
+            build("tools", &tools),
+            build("lib", &lib),
+        ];
+
+        let stable = VersionAligner::new(&builds).find_misalignments().unwrap();
+        assert_eq!(stable[0].target, "4.0.0");
+        let lagging: Vec<&str> = stable[0].lagging().map(|e| e.build.as_str()).collect();
+        assert_eq!(lagging, vec!["lib"]);
+
+        let unstable = VersionAligner::new(&builds)
+            .with_policy(StabilityPolicy::IncludeUnstable)
+            .find_misalignments()
+            .unwrap();
+        assert_eq!(unstable[0].target, "4.1.0-alpha01");
+        assert_eq!(unstable[0].alignable().count(), 2);
+    }
+}
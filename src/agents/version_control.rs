@@ -1,18 +1,142 @@
+use crate::config::GitConfig;
 use crate::error::{GvcError, Result};
+use crate::utils::diff::FileEdit;
 use crate::utils::path_validator::PathValidator;
 use jiff::Zoned;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 
 /// VersionControlAgent handles Git operations with hardened input validation.
 pub struct VersionControlAgent {
     project_path: PathBuf,
+    branch_template: String,
+    commit_message: String,
+    step_message: String,
+    commit_body: Option<String>,
+    bumps: Vec<Bump>,
+    existing_branch: ExistingBranch,
+    conventional: bool,
+    signoff: bool,
+    sign: bool,
 }
 
+/// One version change, for branch names and commit messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bump {
+    pub name: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// One commit of an update split by `--commit-strategy`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitStep {
+    /// Configured group the bumps belong to
+    pub group: Option<String>,
+    pub bumps: Vec<Bump>,
+    /// Catalog contents before and after this commit
+    pub files: Vec<FileEdit>,
+}
+
+/// What to do when the update branch already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExistingBranch {
+    /// Create `<branch>-2`, `<branch>-3`, ... instead
+    #[default]
+    Suffix,
+    /// Check the branch out and add the commit on top of it
+    Reuse,
+}
+
+/// How `gvc update` deals with uncommitted changes outside the catalogs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirtyTree {
+    /// Stop and ask for the changes to be committed or stashed
+    #[default]
+    Refuse,
+    /// Stash them, update, then return to the branch and restore them
+    Stash,
+    /// Update in a temporary `git worktree` off HEAD, leaving the working tree alone
+    Worktree,
+}
+
+/// A stash or worktree keeping the developer's changes out of the update
+///
+/// Dropping it pops its own stash entry on the original branch, or removes the worktree.
+pub struct IsolatedTree {
+    repository: PathBuf,
+    kind: Isolation,
+}
+
+enum Isolation {
+    Stash {
+        branch: Option<String>,
+        /// Commit of the stash entry, popped by position even if newer entries exist
+        commit: String,
+    },
+    Worktree {
+        path: PathBuf,
+    },
+}
+
+const DEFAULT_BRANCH_TEMPLATE: &str = "deps/update-{date}";
+const DEFAULT_COMMIT_MESSAGE: &str = "chore(deps): update dependencies to latest versions";
+const DEFAULT_STEP_MESSAGE: &str = "chore(deps): bump {group}";
+
 impl VersionControlAgent {
     pub fn new<P: AsRef<Path>>(project_path: P) -> Result<Self> {
         let project_path = Self::validate_git_path(project_path.as_ref())?;
-        Ok(Self { project_path })
+        Ok(Self {
+            project_path,
+            branch_template: DEFAULT_BRANCH_TEMPLATE.to_string(),
+            commit_message: DEFAULT_COMMIT_MESSAGE.to_string(),
+            step_message: DEFAULT_STEP_MESSAGE.to_string(),
+            commit_body: None,
+            bumps: Vec::new(),
+            existing_branch: ExistingBranch::default(),
+            conventional: false,
+            signoff: false,
+            sign: false,
+        })
+    }
+
+    /// Apply the branch, commit and signing settings of the `[git]` configuration
+    pub fn with_config(mut self, config: &GitConfig) -> Self {
+        self = self.with_templates(&config.branch_template, &config.commit_message);
+        if !config.step_commit_message.trim().is_empty() {
+            self.step_message = config.step_commit_message.clone();
+        }
+        self.existing_branch = config.existing_branch;
+        self.conventional = config.conventional;
+        self.signoff = config.signoff;
+        self.sign = config.sign;
+        self
+    }
+
+    /// The version changes being committed, for `{count}` and `{list}` and the commit body
+    pub fn with_bumps(mut self, bumps: Vec<Bump>) -> Self {
+        self.bumps = bumps;
+        self
+    }
+
+    /// Use a custom branch name template (`{date}` is expanded) and commit message
+    pub fn with_templates(mut self, branch_template: &str, commit_message: &str) -> Self {
+        if !branch_template.trim().is_empty() {
+            self.branch_template = branch_template.to_string();
+        }
+        if !commit_message.trim().is_empty() {
+            self.commit_message = commit_message.to_string();
+        }
+        self
+    }
+
+    /// Add a body (such as the Markdown update summary) below the commit message
+    pub fn with_commit_body(mut self, body: Option<String>) -> Self {
+        self.commit_body = body.filter(|body| !body.trim().is_empty());
+        self
     }
 
     /// Check if the working directory is clean
@@ -22,40 +146,251 @@ impl VersionControlAgent {
         Ok(output.stdout.is_empty())
     }
 
-    /// Create a new branch for the update
+    /// Files with uncommitted changes, untracked ones included, as absolute paths
+    pub fn dirty_paths(&self) -> Result<Vec<PathBuf>> {
+        let toplevel = self.toplevel()?;
+        let output = self.run_git(&["status", "--porcelain", "-z", "--untracked-files=all"])?;
+        Self::ensure_success(&output, "git status")?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut records = stdout.split('\0').filter(|record| !record.is_empty());
+        let mut paths = Vec::new();
+        while let Some(record) = records.next() {
+            let Some((status, path)) = record.split_at_checked(3) else {
+                continue;
+            };
+            paths.push(toplevel.join(path));
+            // Renames and copies are followed by their source path
+            if status.contains(['R', 'C']) {
+                paths.extend(records.next().map(|source| toplevel.join(source)));
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Stash every uncommitted change until the returned guard is dropped
+    ///
+    /// Returns `None` when git saved nothing (e.g. only a submodule is modified),
+    /// so that no older stash entry is popped in its place.
+    pub fn stash_changes(&self) -> Result<Option<IsolatedTree>> {
+        let branch = self.current_branch()?;
+        let before = self.stash_tip()?;
+        let output = self.run_git(&["stash", "push", "--include-untracked", "-m", "gvc update"])?;
+        Self::ensure_success(&output, "git stash push")?;
+        let Some(commit) = self.stash_tip()?.filter(|tip| Some(tip) != before.as_ref()) else {
+            return Ok(None);
+        };
+        Ok(Some(IsolatedTree {
+            repository: self.project_path.clone(),
+            kind: Isolation::Stash { branch, commit },
+        }))
+    }
+
+    /// Commit `refs/stash` points at, if any stash entry exists
+    fn stash_tip(&self) -> Result<Option<String>> {
+        let output = self.run_git(&["rev-parse", "--verify", "--quiet", "refs/stash"])?;
+        let tip = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(tip).filter(|tip| output.status.success() && !tip.is_empty()))
+    }
+
+    /// Position of the stash entry with `commit` in `git stash list`
+    fn stash_index(&self, commit: &str) -> Result<Option<usize>> {
+        let output = self.run_git(&["stash", "list", "--format=%H"])?;
+        Self::ensure_success(&output, "git stash list")?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .position(|entry| entry.trim() == commit))
+    }
+
+    /// Check HEAD out into a temporary worktree, removed when the returned guard is dropped
+    pub fn add_worktree(&self) -> Result<IsolatedTree> {
+        let path = std::env::temp_dir().join(format!("gvc-update-{}", std::process::id()));
+        let worktree = path.to_string_lossy().into_owned();
+        let output = self.run_git(&["worktree", "add", "--detach", &worktree, "HEAD"])?;
+        Self::ensure_success(&output, "git worktree add")?;
+        Ok(IsolatedTree {
+            repository: self.project_path.clone(),
+            kind: Isolation::Worktree { path },
+        })
+    }
+
+    fn toplevel(&self) -> Result<PathBuf> {
+        let output = self.run_git(&["rev-parse", "--show-toplevel"])?;
+        Self::ensure_success(&output, "git rev-parse --show-toplevel")?;
+        let toplevel = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        Ok(toplevel.canonicalize().unwrap_or(toplevel))
+    }
+
+    /// Create a new branch for the update, or reuse or suffix an existing one
+    ///
+    /// Call it before writing the catalogs: a reused branch may hold other content.
     pub fn create_update_branch(&self) -> Result<String> {
-        let branch_name = self.create_safe_branch_name();
+        let base = self.create_safe_branch_name();
+        if self.branch_exists(&base)? && self.existing_branch == ExistingBranch::Reuse {
+            if self.current_branch()?.as_deref() != Some(base.as_str()) {
+                let output = self.run_git(&["checkout", &base])?;
+                Self::ensure_success(&output, "git checkout")?;
+            }
+            return Ok(base);
+        }
+
+        let mut branch_name = base.clone();
+        let mut suffix = 2;
+        while self.branch_exists(&branch_name)? {
+            branch_name = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
         let output = self.run_git(&["checkout", "-b", &branch_name])?;
         Self::ensure_success(&output, "git checkout -b")?;
         Ok(branch_name)
     }
 
-    /// Stage the modified libs.versions.toml file
-    pub fn stage_version_catalog(&self) -> Result<()> {
-        let catalog_path = self.project_path.join("gradle/libs.versions.toml");
-        PathValidator::validate_file_path(&catalog_path, &self.project_path).map_err(|err| {
-            GvcError::GitOperation(format!("Refusing to stage unsafe path: {err}"))
-        })?;
+    fn branch_exists(&self, branch: &str) -> Result<bool> {
+        let reference = format!("refs/heads/{}", branch);
+        let output = self.run_git(&["rev-parse", "--verify", "--quiet", &reference])?;
+        Ok(output.status.success())
+    }
+
+    /// The checked out branch, `None` on a detached HEAD
+    pub fn current_branch(&self) -> Result<Option<String>> {
+        let output = self.run_git(&["branch", "--show-current"])?;
+        Self::ensure_success(&output, "git branch --show-current")?;
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(branch).filter(|branch| !branch.is_empty()))
+    }
+
+    /// Stage the modified version catalog files
+    pub fn stage_catalogs(&self, catalogs: &[PathBuf]) -> Result<()> {
+        for catalog_path in catalogs {
+            let canonical = PathValidator::validate_file_path(catalog_path, &self.project_path)
+                .map_err(|err| {
+                    GvcError::GitOperation(format!("Refusing to stage unsafe path: {err}"))
+                })?;
+            let relative = canonical
+                .strip_prefix(&self.project_path)
+                .unwrap_or(&canonical)
+                .to_string_lossy()
+                .into_owned();
 
-        let output = self.run_git(&["add", "gradle/libs.versions.toml"])?;
-        Self::ensure_success(&output, "git add")?;
+            let output = self.run_git(&["add", "--", &relative])?;
+            Self::ensure_success(&output, "git add")?;
+        }
         Ok(())
     }
 
-    /// Commit the changes with a standard message
+    /// Commit the changes with the configured message
     pub fn commit_updates(&self) -> Result<()> {
-        let message = "chore(deps): update dependencies to latest versions";
-        let output = self.run_git(&["commit", "-m", message])?;
-        Self::ensure_success(&output, "git commit")?;
+        let subject = self.commit_subject(&self.commit_message, &self.bumps, None);
+        let body = self
+            .commit_body
+            .clone()
+            .or_else(|| self.bump_list(&self.bumps));
+        self.commit(&subject, body.as_deref())
+    }
+
+    /// Commit every step on the checked out branch, writing its catalogs first
+    ///
+    /// Step subjects use the step commit message, where `{group}` is the group name or,
+    /// for a single bump, `<alias> from <old> to <new>`.
+    pub fn commit_steps(&self, steps: &[CommitStep]) -> Result<()> {
+        for step in steps {
+            let mut paths = Vec::new();
+            for file in &step.files {
+                file.write()?;
+                paths.push(file.path.clone());
+            }
+            self.stage_catalogs(&paths)?;
+            let subject =
+                self.commit_subject(&self.step_message, &step.bumps, step.group.as_deref());
+            self.commit(&subject, self.bump_list(&step.bumps).as_deref())?;
+        }
         Ok(())
     }
 
-    /// Full workflow: create branch, stage, and commit
-    pub fn commit_to_new_branch(&self) -> Result<String> {
-        let branch_name = self.create_update_branch()?;
-        self.stage_version_catalog()?;
-        self.commit_updates()?;
-        Ok(branch_name)
+    /// Commit HEAD points at, `None` before the first commit
+    pub fn head_commit(&self) -> Result<Option<String>> {
+        let output = self.run_git(&["rev-parse", "--verify", "--quiet", "HEAD"])?;
+        let head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(head).filter(|head| output.status.success() && !head.is_empty()))
+    }
+
+    /// Every local branch with the commit it points at
+    pub fn branch_tips(&self) -> Result<Vec<(String, String)>> {
+        let output = self.run_git(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(objectname)",
+            "refs/heads",
+        ])?;
+        Self::ensure_success(&output, "git for-each-ref")?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(branch, commit)| (branch.to_string(), commit.to_string()))
+            .collect())
+    }
+
+    /// Whether `branch` has an upstream or its tip is on any remote-tracking branch
+    pub fn is_pushed(&self, branch: &str) -> Result<bool> {
+        let reference = format!("refs/heads/{}", branch);
+        let output = self.run_git(&["for-each-ref", "--format=%(upstream)", &reference])?;
+        Self::ensure_success(&output, "git for-each-ref")?;
+        if !output.stdout.trim_ascii().is_empty() {
+            return Ok(true);
+        }
+        let output = self.run_git(&["branch", "--remotes", "--contains", &reference])?;
+        Ok(output.status.success() && !output.stdout.trim_ascii().is_empty())
+    }
+
+    /// Switch to an existing branch
+    pub fn checkout(&self, branch: &str) -> Result<()> {
+        let output = self.run_git(&["checkout", branch])?;
+        Self::ensure_success(&output, "git checkout")
+    }
+
+    /// Check out `commit` without a branch
+    pub fn checkout_detached(&self, commit: &str) -> Result<()> {
+        let output = self.run_git(&["checkout", "--detach", commit])?;
+        Self::ensure_success(&output, "git checkout --detach")
+    }
+
+    /// Delete a local branch, merged or not
+    pub fn delete_branch(&self, branch: &str) -> Result<()> {
+        let output = self.run_git(&["branch", "-D", branch])?;
+        Self::ensure_success(&output, "git branch -D")
+    }
+
+    /// Push `branch` to `remote` and set it as upstream
+    pub fn push(&self, remote: &str, branch: &str) -> Result<()> {
+        let output = self.run_git(&["push", "--set-upstream", remote, branch])?;
+        Self::ensure_success(&output, "git push")
+    }
+
+    /// URL of a configured remote
+    pub fn remote_url(&self, remote: &str) -> Result<String> {
+        let output = self.run_git(&["remote", "get-url", remote])?;
+        Self::ensure_success(&output, "git remote get-url")?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Title for a pull request of the update: the single-commit subject
+    pub fn pull_request_title(&self) -> String {
+        self.commit_subject(&self.commit_message, &self.bumps, None)
+    }
+
+    fn commit(&self, subject: &str, body: Option<&str>) -> Result<()> {
+        let mut args = vec!["commit", "-m", subject];
+        if let Some(body) = body {
+            args.extend(["-m", body]);
+        }
+        if self.signoff {
+            args.push("--signoff");
+        }
+        if self.sign {
+            args.push("--gpg-sign");
+        }
+        let output = self.run_git(&args)?;
+        Self::ensure_success(&output, "git commit")?;
+        Ok(())
     }
 
     fn run_git(&self, args: &[&str]) -> Result<Output> {
@@ -103,9 +438,57 @@ impl VersionControlAgent {
             .map_err(|err| GvcError::GitOperation(format!("Invalid Git path: {}", err)))
     }
 
-    fn create_safe_branch_name(&self) -> String {
+    /// Expand `{date}`, `{count}`, `{list}` and `{group}` in a template
+    fn expand(
+        &self,
+        template: &str,
+        list_separator: &str,
+        bumps: &[Bump],
+        group: Option<&str>,
+    ) -> String {
         let date = Zoned::now().strftime("%Y-%m-%d").to_string();
-        let mut branch_name = format!("deps/update-{date}");
+        let list = bumps
+            .iter()
+            .map(|bump| bump.name.as_str())
+            .collect::<Vec<_>>()
+            .join(list_separator);
+        let group = match (group, bumps) {
+            (Some(group), _) => group.to_string(),
+            (None, [bump]) => format!("{} from {} to {}", bump.name, bump.old, bump.new),
+            (None, _) => list.clone(),
+        };
+        template
+            .replace("{date}", &date)
+            .replace("{count}", &bumps.len().to_string())
+            .replace("{list}", &list)
+            .replace("{group}", &group)
+    }
+
+    /// First line of the commit message, with a Conventional Commits type when enabled
+    fn commit_subject(&self, template: &str, bumps: &[Bump], group: Option<&str>) -> String {
+        let subject = self.expand(template, ", ", bumps, group);
+        if self.conventional && !is_conventional(&subject) {
+            return format!("chore(deps): {}", subject);
+        }
+        subject
+    }
+
+    /// With Conventional Commits, a body listing every bump
+    fn bump_list(&self, bumps: &[Bump]) -> Option<String> {
+        if !self.conventional || bumps.is_empty() {
+            return None;
+        }
+        Some(
+            bumps
+                .iter()
+                .map(|bump| format!("- bump {} from {} to {}", bump.name, bump.old, bump.new))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn create_safe_branch_name(&self) -> String {
+        let mut branch_name = self.expand(&self.branch_template, "-", &self.bumps, None);
 
         branch_name = branch_name
             .chars()
@@ -114,6 +497,9 @@ impl VersionControlAgent {
                 _ => '-',
             })
             .collect();
+        branch_name = branch_name
+            .trim_matches(|c| c == '-' || c == '/')
+            .to_string();
 
         if branch_name.len() > 50 {
             branch_name.truncate(50);
@@ -123,6 +509,74 @@ impl VersionControlAgent {
     }
 }
 
+impl IsolatedTree {
+    /// Root of the temporary worktree the update runs in, if any
+    pub fn worktree(&self) -> Option<&Path> {
+        match &self.kind {
+            Isolation::Worktree { path } => Some(path),
+            Isolation::Stash { .. } => None,
+        }
+    }
+
+    fn restore(&self) -> Result<()> {
+        let agent = VersionControlAgent::new(&self.repository)?;
+        match &self.kind {
+            Isolation::Stash { branch, commit } => {
+                if let Some(branch) = branch {
+                    if agent.current_branch()?.as_ref() != Some(branch) {
+                        let output = agent.run_git(&["checkout", branch])?;
+                        VersionControlAgent::ensure_success(&output, "git checkout")?;
+                    }
+                }
+                let index = agent.stash_index(commit)?.ok_or_else(|| {
+                    GvcError::GitOperation(format!("Stash entry {} no longer exists", commit))
+                })?;
+                let entry = format!("stash@{{{}}}", index);
+                let output = agent.run_git(&["stash", "pop", &entry])?;
+                VersionControlAgent::ensure_success(&output, "git stash pop")
+            }
+            Isolation::Worktree { path } => {
+                let worktree = path.to_string_lossy().into_owned();
+                let output = agent.run_git(&["worktree", "remove", "--force", &worktree])?;
+                VersionControlAgent::ensure_success(&output, "git worktree remove")
+            }
+        }
+    }
+}
+
+impl Drop for IsolatedTree {
+    fn drop(&mut self) {
+        if let Err(err) = self.restore() {
+            let hint = match &self.kind {
+                Isolation::Stash { commit, .. } => {
+                    format!("restore your changes with `git stash apply {}`", commit)
+                }
+                Isolation::Worktree { path } => {
+                    format!(
+                        "remove it with `git worktree remove --force {}`",
+                        path.display()
+                    )
+                }
+            };
+            eprintln!("⚠ Could not restore the working tree ({}); {}", err, hint);
+        }
+    }
+}
+
+/// `type(scope)!: description` as defined by Conventional Commits
+fn is_conventional(subject: &str) -> bool {
+    let Some((prefix, description)) = subject.split_once(": ") else {
+        return false;
+    };
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let kind = match prefix.split_once('(') {
+        Some((kind, scope)) => scope.ends_with(')').then_some(kind),
+        None => Some(prefix),
+    };
+    kind.is_some_and(|kind| !kind.is_empty() && kind.chars().all(|c| c.is_ascii_alphabetic()))
+        && !description.trim().is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +613,315 @@ mod tests {
                 .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '/'))
         );
     }
+
+    #[test]
+    fn branch_template_is_expanded_and_sanitized() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let agent = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_templates("chore/deps {date}!", "");
+        let branch = agent.create_safe_branch_name();
+        assert!(branch.starts_with("chore/deps-20"));
+        assert!(!branch.ends_with('-'));
+        assert_eq!(agent.commit_message, DEFAULT_COMMIT_MESSAGE);
+    }
+
+    #[test]
+    fn expands_placeholders_and_lists_bumps_in_conventional_commits() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let bump = |name: &str, old: &str, new: &str| Bump {
+            name: name.into(),
+            old: old.into(),
+            new: new.into(),
+        };
+        let config = GitConfig {
+            branch_template: "deps/{count}-{list}".into(),
+            commit_message: "update {count} dependencies ({list})".into(),
+            conventional: true,
+            ..GitConfig::default()
+        };
+        let agent = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_config(&config)
+            .with_bumps(vec![
+                bump("okhttp", "4.11.0", "4.12.0"),
+                bump("junit", "4.12", "4.13.2"),
+            ]);
+
+        assert_eq!(agent.create_safe_branch_name(), "deps/2-okhttp-junit");
+        assert_eq!(
+            agent.commit_subject(&agent.commit_message, &agent.bumps, None),
+            "chore(deps): update 2 dependencies (okhttp, junit)"
+        );
+        assert_eq!(
+            agent.commit_subject(&agent.step_message, &agent.bumps[..1], None),
+            "chore(deps): bump okhttp from 4.11.0 to 4.12.0"
+        );
+        assert_eq!(
+            agent.bump_list(&agent.bumps).unwrap(),
+            "- bump okhttp from 4.11.0 to 4.12.0\n- bump junit from 4.12 to 4.13.2"
+        );
+        assert!(is_conventional("fix(deps)!: bump okhttp"));
+        assert!(!is_conventional("Update okhttp: 4.12.0"));
+    }
+
+    #[test]
+    fn suffixes_or_reuses_an_existing_branch() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&canonical)
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+
+        let config = GitConfig {
+            branch_template: "deps/update".into(),
+            ..GitConfig::default()
+        };
+        let agent = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_config(&config);
+        assert_eq!(agent.create_update_branch().unwrap(), "deps/update");
+        git(&["checkout", "-q", "main"]);
+        assert_eq!(agent.create_update_branch().unwrap(), "deps/update-2");
+
+        let reuse = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_config(&GitConfig {
+                existing_branch: ExistingBranch::Reuse,
+                ..config
+            });
+        assert_eq!(reuse.create_update_branch().unwrap(), "deps/update");
+        assert_eq!(
+            reuse.current_branch().unwrap().as_deref(),
+            Some("deps/update")
+        );
+    }
+
+    #[test]
+    fn reuses_a_branch_whose_catalog_differs_from_head() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&canonical)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        let catalog = canonical.join("libs.versions.toml");
+        fs::write(&catalog, "a = \"1\"\nb = \"1\"\n").unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        git(&["checkout", "-q", "-b", "deps/update"]);
+        fs::write(&catalog, "a = \"2\"\nb = \"1\"\n").unwrap();
+        git(&["commit", "-q", "-am", "bump a"]);
+        git(&["checkout", "-q", "main"]);
+
+        let agent = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_config(&GitConfig {
+                branch_template: "deps/update".into(),
+                existing_branch: ExistingBranch::Reuse,
+                ..GitConfig::default()
+            });
+        assert_eq!(agent.create_update_branch().unwrap(), "deps/update");
+        // The update is planned on the branch's catalog, not the one from main
+        assert_eq!(
+            fs::read_to_string(&catalog).unwrap(),
+            "a = \"2\"\nb = \"1\"\n"
+        );
+        FileEdit {
+            path: catalog.clone(),
+            original: "a = \"2\"\nb = \"1\"\n".into(),
+            updated: "a = \"2\"\nb = \"2\"\n".into(),
+        }
+        .write()
+        .unwrap();
+        agent
+            .stage_catalogs(std::slice::from_ref(&catalog))
+            .unwrap();
+        agent.commit_updates().unwrap();
+
+        assert_eq!(git(&["rev-list", "--count", "main..deps/update"]), "2\n");
+        assert_eq!(
+            git(&["show", "main:libs.versions.toml"]),
+            "a = \"1\"\nb = \"1\"\n"
+        );
+        assert!(agent.is_working_directory_clean().unwrap());
+    }
+
+    #[test]
+    fn commits_each_step_on_the_update_branch() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&canonical)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        let catalog = canonical.join("libs.versions.toml");
+        std::fs::write(&catalog, "a = \"1\"\nb = \"1\"\n").unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        let step = |name: &str, original: &str, updated: &str| CommitStep {
+            group: None,
+            bumps: vec![Bump {
+                name: name.into(),
+                old: "1".into(),
+                new: "2".into(),
+            }],
+            files: vec![FileEdit {
+                path: catalog.clone(),
+                original: original.into(),
+                updated: updated.into(),
+            }],
+        };
+        let agent = VersionControlAgent::new(&canonical)
+            .unwrap()
+            .with_templates("deps/update", "");
+        let branch = agent.create_update_branch().unwrap();
+        agent
+            .commit_steps(&[
+                step("a", "a = \"1\"\nb = \"1\"\n", "a = \"2\"\nb = \"1\"\n"),
+                step("b", "a = \"2\"\nb = \"1\"\n", "a = \"2\"\nb = \"2\"\n"),
+            ])
+            .unwrap();
+
+        assert_eq!(branch, "deps/update");
+        assert_eq!(
+            git(&["log", "--format=%s", "main..deps/update"]),
+            "chore(deps): bump b from 1 to 2\nchore(deps): bump a from 1 to 2\n"
+        );
+        assert_eq!(
+            git(&["show", "HEAD~1:libs.versions.toml"]),
+            "a = \"2\"\nb = \"1\"\n"
+        );
+        assert!(agent.is_working_directory_clean().unwrap());
+    }
+
+    #[test]
+    fn isolates_and_restores_uncommitted_changes() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&canonical)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+        };
+        std::fs::write(canonical.join("build.gradle.kts"), "plugins {}\n").unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        std::fs::write(canonical.join("build.gradle.kts"), "plugins { java }\n").unwrap();
+        std::fs::write(canonical.join("notes.txt"), "wip\n").unwrap();
+
+        let agent = VersionControlAgent::new(&canonical).unwrap();
+        let mut dirty = agent.dirty_paths().unwrap();
+        dirty.sort();
+        assert_eq!(
+            dirty,
+            vec![
+                canonical.join("build.gradle.kts"),
+                canonical.join("notes.txt")
+            ]
+        );
+
+        let stash = agent.stash_changes().unwrap().unwrap();
+        assert!(agent.is_working_directory_clean().unwrap());
+        git(&["checkout", "-q", "-b", "deps/update"]);
+        drop(stash);
+        assert_eq!(agent.current_branch().unwrap().as_deref(), Some("main"));
+        assert_eq!(agent.dirty_paths().unwrap().len(), 2);
+
+        let tree = agent.add_worktree().unwrap();
+        let worktree = tree.worktree().unwrap().to_path_buf();
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("build.gradle.kts")).unwrap(),
+            "plugins {}\n"
+        );
+        drop(tree);
+        assert!(!worktree.exists());
+        assert_eq!(agent.dirty_paths().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn stash_guard_pops_only_its_own_entry() {
+        let dir = tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&canonical)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+            String::from_utf8(output.stdout).unwrap()
+        };
+        std::fs::write(canonical.join("build.gradle.kts"), "plugins {}\n").unwrap();
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        std::fs::write(canonical.join("older.txt"), "older\n").unwrap();
+        git(&["stash", "push", "-q", "--include-untracked", "-m", "older"]);
+
+        // Nothing to save: no guard, and the older entry is left alone
+        let agent = VersionControlAgent::new(&canonical).unwrap();
+        assert!(agent.stash_changes().unwrap().is_none());
+        assert_eq!(git(&["stash", "list", "--format=%s"]).lines().count(), 1);
+
+        std::fs::write(canonical.join("notes.txt"), "wip\n").unwrap();
+        let stash = agent.stash_changes().unwrap().unwrap();
+        // An entry stashed during the update sits above ours
+        std::fs::write(canonical.join("newer.txt"), "newer\n").unwrap();
+        git(&["stash", "push", "-q", "--include-untracked", "-m", "newer"]);
+        drop(stash);
+
+        assert!(canonical.join("notes.txt").exists());
+        assert!(!canonical.join("newer.txt").exists());
+        assert!(!canonical.join("older.txt").exists());
+        let remaining = git(&["stash", "list", "--format=%s"]);
+        assert!(remaining.contains("newer"), "{remaining}");
+        assert!(remaining.contains("older"), "{remaining}");
+        assert_eq!(remaining.lines().count(), 2);
+    }
 }
@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -32,13 +33,93 @@ pub enum Commands {
         #[arg(long, value_name = "GLOB")]
         filter: Option<String>,
 
-        /// Only update to stable versions (no alpha, beta, RC) - enabled by default
-        #[arg(short, long, default_value_t = true)]
+        /// Only update to stable versions (no alpha, beta, RC); the default unless
+        /// `update.stability` says otherwise
+        #[arg(short, long, conflicts_with_all = ["include_unstable", "track_channel"])]
         stable_only: bool,
 
+        /// Include unstable versions (alpha, beta, RC)
+        #[arg(long)]
+        include_unstable: bool,
+
+        /// Stay on the current release channel: pre-release entries may move to newer
+        /// pre-releases, stable entries only receive stable releases
+        #[arg(long, conflicts_with = "include_unstable")]
+        track_channel: bool,
+
         /// Skip Git operations (don't create branch or commit)
         #[arg(long)]
         no_git: bool,
+
+        /// With uncommitted changes outside the catalogs, stash them during the update
+        /// and restore them afterwards
+        #[arg(long, conflicts_with = "worktree")]
+        stash: bool,
+
+        /// With uncommitted changes outside the catalogs, update in a temporary
+        /// `git worktree` off HEAD and leave the working tree untouched
+        #[arg(long)]
+        worktree: bool,
+
+        /// Print the changes as a unified diff without writing files or touching Git
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Save the dry-run diff to this file, as a patch for `git apply`
+        #[arg(long, value_name = "FILE", requires = "dry_run")]
+        output_patch: Option<PathBuf>,
+
+        /// Write a Markdown summary of the updates (for pull request bodies) to FILE,
+        /// or to stdout with `-`
+        #[arg(long, value_name = "FILE")]
+        summary_md: Option<PathBuf>,
+
+        /// Use the Markdown summary as the body of the update commit
+        #[arg(long)]
+        summary_body: bool,
+
+        /// Commit everything at once (`single`), or each update (`per-dependency`) or
+        /// configured group (`per-group`) as its own commit on the update branch
+        #[arg(
+            long,
+            value_name = "STRATEGY",
+            value_parser = ["single", "per-dependency", "per-group"]
+        )]
+        commit_strategy: Option<String>,
+
+        /// Add a `Signed-off-by` trailer to the update commit
+        #[arg(long)]
+        signoff: bool,
+
+        /// GPG-sign the update commit
+        #[arg(short = 'S', long)]
+        gpg_sign: bool,
+
+        /// Push the update branch to the remote (`forge.remote`, `origin` by default)
+        #[arg(long)]
+        push: bool,
+
+        /// Push the update branch and open a pull request (merge request on GitLab)
+        /// with the update summary as its description
+        #[arg(long)]
+        open_pr: bool,
+
+        /// Only work on this version catalog (e.g. `libs`, `testLibs`); all catalogs by default
+        #[arg(long, value_name = "NAME")]
+        catalog: Option<String>,
+
+        /// Work on every Gradle build under the project path (included builds and
+        /// sibling projects), each with its own repositories
+        #[arg(long)]
+        all_builds: bool,
+
+        /// Keep dependencies shared between builds on the same (highest) version
+        #[arg(long, requires = "all_builds")]
+        align: bool,
+
+        /// Output format: `text`, or `json` for a machine-readable report on stdout
+        #[arg(long, value_name = "FORMAT", value_parser = ["text", "json"])]
+        format: Option<String>,
     },
 
     /// Check for available dependency updates without applying them
@@ -46,10 +127,57 @@ pub enum Commands {
         /// Include unstable versions (alpha, beta, RC)
         #[arg(long)]
         include_unstable: bool,
+
+        /// Stay on the current release channel: pre-release entries may move to newer
+        /// pre-releases, stable entries only receive stable releases
+        #[arg(long, conflicts_with = "include_unstable")]
+        track_channel: bool,
+
+        /// Only work on this version catalog (e.g. `libs`, `testLibs`); all catalogs by default
+        #[arg(long, value_name = "NAME")]
+        catalog: Option<String>,
+
+        /// Work on every Gradle build under the project path (included builds and
+        /// sibling projects), each with its own repositories
+        #[arg(long)]
+        all_builds: bool,
+
+        /// Keep dependencies shared between builds on the same (highest) version
+        #[arg(long, requires = "all_builds")]
+        align: bool,
+
+        /// Output format: `text`, or `json` for a machine-readable report on stdout
+        #[arg(long, value_name = "FORMAT", value_parser = ["text", "json"])]
+        format: Option<String>,
+
+        /// Exit with a failure code when entries match: `updates` (any update, exit 2),
+        /// `major` (major updates, exit 2) or `security` (known vulnerabilities, exit 3)
+        #[arg(long, value_name = "CONDITION", value_parser = ["updates", "major", "security"])]
+        fail_on: Option<String>,
+
+        /// Write a JUnit XML report with one test case per dependency
+        #[arg(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+
+        /// Write a SARIF report locating outdated entries in the catalog
+        #[arg(long, value_name = "FILE")]
+        sarif: Option<PathBuf>,
+
+        /// Print GitHub Actions annotations for outdated entries (not with `--format json`)
+        #[arg(long)]
+        github_annotations: bool,
     },
 
     /// List all dependencies in the version catalog
-    List,
+    List {
+        /// Only list this version catalog; all catalogs by default
+        #[arg(long, value_name = "NAME")]
+        catalog: Option<String>,
+
+        /// Output format: `text`, or `json` for a machine-readable report on stdout
+        #[arg(long, value_name = "FORMAT", value_parser = ["text", "json"])]
+        format: Option<String>,
+    },
 
     /// Add a dependency or plugin entry to the version catalog
     Add {
@@ -73,8 +201,135 @@ pub enum Commands {
         #[arg(long = "version-alias")]
         version_alias: Option<String>,
 
+        /// Also add the new library to this bundle (created if missing)
+        #[arg(long, value_name = "NAME", conflicts_with = "plugin")]
+        bundle: Option<String>,
+
         /// Prefer stable versions when resolving `:latest` coordinates (use `--no-stable-only` to include pre-releases)
         #[arg(long = "no-stable-only", action = clap::ArgAction::SetFalse, default_value_t = true)]
         stable_only: bool,
+
+        /// Version catalog to add the entry to (defaults to `libs`)
+        #[arg(long, value_name = "NAME")]
+        catalog: Option<String>,
+    },
+
+    /// Remove a library or plugin entry from the version catalog
+    Remove {
+        /// Alias of the entry to remove
+        #[arg(value_name = "ALIAS")]
+        alias: String,
+
+        /// Only look for the alias in [plugins]
+        #[arg(short = 'p', long = "plugin", conflicts_with = "library")]
+        plugin: bool,
+
+        /// Only look for the alias in [libraries]
+        #[arg(short = 'l', long = "library", conflicts_with = "plugin")]
+        library: bool,
+    },
+
+    /// Validate the version catalog and report problems (non-zero exit when any remain)
+    Lint {
+        /// Apply safe fixes (remove unused version aliases, expand string notation)
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Move hard-coded coordinates and plugin versions from build scripts into the catalog
+    Migrate {
+        /// Print a diff of the changes without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Rename a catalog alias and rewrite its accessors in build scripts
+    Rename {
+        /// Current alias
+        old: String,
+
+        /// New alias
+        new: String,
+
+        /// Catalog section to look in when the alias exists in several
+        #[arg(long, value_parser = ["libraries", "plugins", "bundles", "versions"])]
+        section: Option<String>,
+
+        /// Apply the changes without asking for confirmation
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Share one `[versions]` alias between libraries of a group that repeat a literal version
+    DedupeVersions {
+        /// Consolidate every group without asking
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Report catalog entries that no build script references
+    Unused {
+        /// Delete the unused entries from the catalog
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Sort and normalize the version catalog
+    Fmt {
+        /// Only check formatting; exit non-zero if the catalog would change
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Revert the last catalog change gvc made, deleting the update branch it created
+    /// unless that branch was pushed
+    Undo {
+        /// Only restore the catalog files; leave branches alone
+        #[arg(long)]
+        no_git: bool,
+    },
+
+    /// Manage [bundles] in the version catalog
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommands,
+    },
+
+    /// Inspect the layered gvc configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
 }
+
+#[derive(Subcommand, Debug)]
+pub enum BundleCommands {
+    /// Add library aliases to a bundle, creating the bundle if needed
+    Add {
+        /// Bundle name
+        bundle: String,
+
+        /// Library aliases from [libraries]
+        #[arg(required = true, value_name = "ALIAS")]
+        aliases: Vec<String>,
+    },
+
+    /// Remove aliases from a bundle, or the whole bundle when no alias is given
+    Remove {
+        /// Bundle name
+        bundle: String,
+
+        /// Library aliases to remove
+        #[arg(value_name = "ALIAS")]
+        aliases: Vec<String>,
+    },
+
+    /// List bundles and their members
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Show the effective configuration and where each value comes from
+    Show,
+}
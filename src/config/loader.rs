@@ -0,0 +1,380 @@
+use crate::config::GvcConfig;
+use crate::error::{GvcError, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Project-level configuration files, in lookup order
+const PROJECT_CONFIG_FILES: &[&str] = &[".gvc.toml", "gradle/gvc.toml"];
+
+/// Environment variables mapped onto configuration keys
+const ENV_KEYS: &[(&str, &str, EnvKind)] = &[
+    ("GVC_STABILITY", "update.stability", EnvKind::Text),
+    ("GVC_INTERACTIVE", "update.interactive", EnvKind::Flag),
+    ("GVC_NO_GIT", "git.enabled", EnvKind::InvertedFlag),
+    ("GVC_BRANCH_TEMPLATE", "git.branch_template", EnvKind::Text),
+    ("GVC_COMMIT_MESSAGE", "git.commit_message", EnvKind::Text),
+    ("GVC_SUMMARY_BODY", "git.summary_body", EnvKind::Flag),
+    ("GVC_COMMIT_STRATEGY", "git.commit_strategy", EnvKind::Text),
+    ("GVC_DIRTY_TREE", "git.dirty_tree", EnvKind::Text),
+    ("GVC_BASE_BRANCH", "forge.base_branch", EnvKind::Text),
+    ("GVC_FORGE_API_URL", "forge.api_url", EnvKind::Text),
+    ("GVC_OUTPUT_FORMAT", "output.format", EnvKind::Text),
+    ("GVC_COLOR", "output.color", EnvKind::Text),
+];
+
+#[derive(Clone, Copy)]
+enum EnvKind {
+    Text,
+    Flag,
+    InvertedFlag,
+}
+
+/// Where an effective configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    Project(PathBuf),
+    User(PathBuf),
+    Env(String),
+    Cli(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => f.write_str("default"),
+            ConfigSource::Project(path) => write!(f, "project ({})", path.display()),
+            ConfigSource::User(path) => write!(f, "user ({})", path.display()),
+            ConfigSource::Env(name) => write!(f, "env ({})", name),
+            ConfigSource::Cli(flag) => write!(f, "cli ({})", flag),
+        }
+    }
+}
+
+/// Values set explicitly on the command line
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    entries: Vec<(String, Value, String)>,
+}
+
+impl CliOverrides {
+    /// Override `key` (dotted path) with `value`, attributing it to `flag`
+    pub fn set(&mut self, key: &str, value: impl Into<Value>, flag: &str) {
+        self.entries
+            .push((key.to_string(), value.into(), flag.to_string()));
+    }
+}
+
+/// Merged configuration together with the origin of every value
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: GvcConfig,
+    /// Flattened `key -> (value, source)` view of the effective configuration
+    pub values: BTreeMap<String, (Value, ConfigSource)>,
+}
+
+/// Resolves the layered gvc configuration for a project
+pub struct ConfigLoader {
+    project_path: PathBuf,
+    user_config: Option<PathBuf>,
+    env: Vec<(String, String)>,
+}
+
+impl ConfigLoader {
+    pub fn new<P: AsRef<Path>>(project_path: P) -> Self {
+        Self {
+            project_path: project_path.as_ref().to_path_buf(),
+            user_config: Self::default_user_config(),
+            env: std::env::vars()
+                .filter(|(k, _)| k.starts_with("GVC_"))
+                .collect(),
+        }
+    }
+
+    /// Merge every layer and validate the result
+    pub fn load(&self, cli: CliOverrides) -> Result<LoadedConfig> {
+        let mut merged = Table::new();
+        let mut sources = BTreeMap::new();
+
+        let defaults = Table::try_from(GvcConfig::default())
+            .map_err(|e| GvcError::Config(format!("Failed to serialize defaults: {}", e)))?;
+        merge_layer(
+            &mut merged,
+            defaults,
+            &ConfigSource::Default,
+            &mut sources,
+            "",
+        );
+
+        if let Some(path) = self.project_config() {
+            let layer = read_layer(&path)?;
+            merge_layer(
+                &mut merged,
+                layer,
+                &ConfigSource::Project(path),
+                &mut sources,
+                "",
+            );
+        }
+
+        if let Some(path) = self.user_config.as_ref().filter(|path| path.is_file()) {
+            let layer = read_layer(path)?;
+            let source = ConfigSource::User(path.clone());
+            merge_layer(&mut merged, layer, &source, &mut sources, "");
+        }
+
+        for (variable, key, kind) in ENV_KEYS {
+            let Some((_, raw)) = self.env.iter().find(|(name, _)| name == variable) else {
+                continue;
+            };
+            let value = match kind {
+                EnvKind::Text => Value::String(raw.clone()),
+                EnvKind::Flag => Value::Boolean(parse_flag(variable, raw)?),
+                EnvKind::InvertedFlag => Value::Boolean(!parse_flag(variable, raw)?),
+            };
+            let source = ConfigSource::Env(variable.to_string());
+            merge_layer(
+                &mut merged,
+                single_value(key, value),
+                &source,
+                &mut sources,
+                "",
+            );
+        }
+
+        for (key, value, flag) in cli.entries {
+            let source = ConfigSource::Cli(flag);
+            merge_layer(
+                &mut merged,
+                single_value(&key, value),
+                &source,
+                &mut sources,
+                "",
+            );
+        }
+
+        let config: GvcConfig = merged
+            .clone()
+            .try_into()
+            .map_err(|e| GvcError::Config(format!("Invalid configuration: {}", e)))?;
+
+        let mut values = BTreeMap::new();
+        flatten(&merged, "", &mut |key, value| {
+            let source = sources.get(key).cloned().unwrap_or(ConfigSource::Default);
+            values.insert(key.to_string(), (value.clone(), source));
+        });
+
+        Ok(LoadedConfig { config, values })
+    }
+
+    /// The project configuration file in use, if any
+    pub fn project_config(&self) -> Option<PathBuf> {
+        PROJECT_CONFIG_FILES
+            .iter()
+            .map(|name| self.project_path.join(name))
+            .find(|path| path.is_file())
+    }
+
+    fn default_user_config() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .or_else(|| std::env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+        Some(base.join("gvc").join("config.toml"))
+    }
+}
+
+fn read_layer(path: &Path) -> Result<Table> {
+    let content = fs::read_to_string(path)?;
+    content
+        .parse::<Table>()
+        .map_err(|e| GvcError::Config(format!("Failed to parse '{}': {}", path.display(), e)))
+}
+
+fn parse_flag(variable: &str, raw: &str) -> Result<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" | "" => Ok(false),
+        _ => Err(GvcError::Config(format!(
+            "Environment variable '{}' must be true or false, got '{}'",
+            variable, raw
+        ))),
+    }
+}
+
+/// Build a table containing a single dotted `key`
+fn single_value(key: &str, value: Value) -> Table {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let leaf = parts.pop().unwrap_or(key);
+
+    let mut table = Table::new();
+    table.insert(leaf.to_string(), value);
+    for part in parts.into_iter().rev() {
+        let mut parent = Table::new();
+        parent.insert(part.to_string(), Value::Table(table));
+        table = parent;
+    }
+    table
+}
+
+/// Deep-merge `layer` into `target`, recording the source of every leaf it sets
+fn merge_layer(
+    target: &mut Table,
+    layer: Table,
+    source: &ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+    prefix: &str,
+) {
+    for (key, value) in layer {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match value {
+            Value::Table(child) => {
+                let entry = target
+                    .entry(key)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                if !entry.is_table() {
+                    *entry = Value::Table(Table::new());
+                }
+                if let Value::Table(existing) = entry {
+                    merge_layer(existing, child, source, sources, &path);
+                }
+            }
+            other => {
+                target.insert(key, other);
+                sources.insert(path, source.clone());
+            }
+        }
+    }
+}
+
+fn flatten(table: &Table, prefix: &str, visit: &mut dyn FnMut(&str, &Value)) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match value {
+            Value::Table(child) => flatten(child, &path, visit),
+            other => visit(&path, other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::StabilityPolicy;
+    use tempfile::tempdir;
+
+    fn loader(project: &Path, user: Option<PathBuf>, env: &[(&str, &str)]) -> ConfigLoader {
+        ConfigLoader {
+            project_path: project.to_path_buf(),
+            user_config: user,
+            env: env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn defaults_apply_without_files() {
+        let dir = tempdir().unwrap();
+        let loaded = loader(dir.path(), None, &[])
+            .load(CliOverrides::default())
+            .unwrap();
+
+        assert!(loaded.config.git.enabled);
+        assert_eq!(loaded.config.update.stability, StabilityPolicy::StableOnly);
+        assert_eq!(loaded.values["git.enabled"].1, ConfigSource::Default);
+    }
+
+    #[test]
+    fn layers_override_in_order() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".gvc.toml"),
+            "[update]\nstability = \"track-channel\"\ninteractive = true\n[git]\nbranch_template = \"chore/deps-{date}\"\n",
+        )
+        .unwrap();
+        let user = dir.path().join("user.toml");
+        fs::write(&user, "[update]\ninteractive = false\n").unwrap();
+
+        let mut cli = CliOverrides::default();
+        cli.set("update.stability", "include-unstable", "--include-unstable");
+
+        let loaded = loader(dir.path(), Some(user.clone()), &[("GVC_NO_GIT", "1")])
+            .load(cli)
+            .unwrap();
+
+        assert_eq!(
+            loaded.config.update.stability,
+            StabilityPolicy::IncludeUnstable
+        );
+        assert!(!loaded.config.update.interactive);
+        assert!(!loaded.config.git.enabled);
+        assert_eq!(loaded.config.git.branch_template, "chore/deps-{date}");
+
+        assert_eq!(
+            loaded.values["update.interactive"].1,
+            ConfigSource::User(user)
+        );
+        assert_eq!(
+            loaded.values["git.enabled"].1,
+            ConfigSource::Env("GVC_NO_GIT".to_string())
+        );
+        assert_eq!(
+            loaded.values["git.branch_template"].1,
+            ConfigSource::Project(dir.path().join(".gvc.toml"))
+        );
+        assert_eq!(
+            loaded.values["update.stability"].1,
+            ConfigSource::Cli("--include-unstable".to_string())
+        );
+    }
+
+    #[test]
+    fn gradle_directory_config_is_found() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("gradle")).unwrap();
+        fs::write(
+            dir.path().join("gradle/gvc.toml"),
+            "[rules]\npin = [\"kotlin\"]\n",
+        )
+        .unwrap();
+
+        let loaded = loader(dir.path(), None, &[])
+            .load(CliOverrides::default())
+            .unwrap();
+        assert_eq!(loaded.config.rules.pin, vec!["kotlin".to_string()]);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(".gvc.toml"),
+            "[update]\nstabilty = \"all\"\n",
+        )
+        .unwrap();
+
+        let err = loader(dir.path(), None, &[])
+            .load(CliOverrides::default())
+            .unwrap_err();
+        assert!(matches!(err, GvcError::Config(_)));
+    }
+}
@@ -0,0 +1,365 @@
+// Configuration module - layered gvc settings
+//
+// Settings are resolved from several layers, each overriding the previous one:
+// 1. Built-in defaults
+// 2. Project file: `.gvc.toml` (or `gradle/gvc.toml`)
+// 3. User file: `$XDG_CONFIG_HOME/gvc/config.toml` (or `~/.config/gvc/config.toml`)
+// 4. `GVC_*` environment variables
+// 5. Command-line flags
+pub mod loader;
+
+pub use loader::{CliOverrides, ConfigLoader, LoadedConfig};
+
+use crate::agents::StabilityPolicy;
+use crate::agents::commit_planner::{CommitGroup, CommitStrategy};
+use crate::agents::version_control::{DirtyTree, ExistingBranch};
+use crate::error::{GvcError, Result};
+use crate::forge::ForgeKind;
+use crate::gradle::{Repository, RepositoryCredentials};
+use serde::{Deserialize, Serialize};
+
+/// Effective gvc configuration after all layers are merged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GvcConfig {
+    pub repositories: RepositoriesConfig,
+    pub rules: RulesConfig,
+    pub update: UpdateConfig,
+    pub git: GitConfig,
+    pub forge: ForgeConfig,
+    pub output: OutputConfig,
+}
+
+/// Repository discovery and additional repositories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepositoriesConfig {
+    /// Read repositories declared in the Gradle build files
+    pub from_gradle: bool,
+    /// Extra repositories, or credentials for repositories Gradle already declares
+    pub custom: Vec<RepositoryConfig>,
+}
+
+impl Default for RepositoriesConfig {
+    fn default() -> Self {
+        Self {
+            from_gradle: true,
+            custom: Vec::new(),
+        }
+    }
+}
+
+/// A repository declared in the gvc configuration
+///
+/// Secrets are never stored in the file; `*_env` keys name the environment
+/// variables holding them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepositoryConfig {
+    pub name: String,
+    pub url: String,
+    /// Regex patterns restricting which groups are looked up in this repository
+    pub groups: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+/// Entries to skip or constrain, matched by alias, `group:artifact` or plugin id (globs allowed)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulesConfig {
+    pub ignore: Vec<String>,
+    pub pin: Vec<String>,
+    pub allow_prerelease: Vec<String>,
+}
+
+/// Update behaviour
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpdateConfig {
+    pub stability: StabilityPolicy,
+    pub interactive: bool,
+}
+
+/// Git integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    pub enabled: bool,
+    /// Branch name template; `{date}`, `{count}` and `{list}` (updated aliases) are expanded
+    pub branch_template: String,
+    /// Commit message template, with the same placeholders as the branch name
+    pub commit_message: String,
+    /// Use the Markdown update summary as the commit body
+    pub summary_body: bool,
+    /// One commit for the whole update, or one per dependency or group
+    pub commit_strategy: CommitStrategy,
+    /// Groups committed together by the `per-group` strategy
+    pub groups: Vec<CommitGroup>,
+    /// Message template of split commits; `{group}` is the group name or the single bump
+    pub step_commit_message: String,
+    /// Suffix or reuse a branch that already exists
+    pub existing_branch: ExistingBranch,
+    /// Refuse, stash or use a worktree when files outside the catalogs have uncommitted changes
+    pub dirty_tree: DirtyTree,
+    /// Conventional Commits subject, with a body listing each bump
+    pub conventional: bool,
+    /// Add a `Signed-off-by` trailer
+    pub signoff: bool,
+    /// GPG-sign the commit
+    pub sign: bool,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            branch_template: "deps/update-{date}".to_string(),
+            commit_message: "chore(deps): update dependencies to latest versions".to_string(),
+            summary_body: false,
+            commit_strategy: CommitStrategy::Single,
+            groups: Vec::new(),
+            step_commit_message: "chore(deps): bump {group}".to_string(),
+            existing_branch: ExistingBranch::Suffix,
+            dirty_tree: DirtyTree::Refuse,
+            conventional: false,
+            signoff: false,
+            sign: false,
+        }
+    }
+}
+
+/// Pushing the update branch and opening a pull request on GitHub, GitLab or Gitea
+///
+/// The token is never stored in the file; `token_env` names the environment
+/// variable holding it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ForgeConfig {
+    /// Push the update branch to `remote`
+    pub push: bool,
+    /// Push and open a pull request (merge request on GitLab)
+    pub open_pr: bool,
+    pub remote: String,
+    /// Target branch of the pull request; the branch checked out before the update by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Detected from the remote host when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ForgeKind>,
+    /// REST API root, e.g. `https://gitea.example.com/api/v1`; derived from the remote host when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// `owner/repo` (or GitLab project path); read from the remote URL when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// Defaults to `GITHUB_TOKEN`, `GITLAB_TOKEN` or `GITEA_TOKEN`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            push: false,
+            open_pr: false,
+            remote: "origin".to_string(),
+            base_branch: None,
+            kind: None,
+            api_url: None,
+            repository: None,
+            token_env: None,
+        }
+    }
+}
+
+/// Console output
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub format: OutputFormat,
+    pub color: ColorChoice,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// Versioned JSON on stdout for `check`, `list` and `update`; progress goes to stderr
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Apply the choice to the global `colored` settings
+    pub fn apply(self) {
+        match self {
+            ColorChoice::Auto => colored::control::unset_override(),
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+        }
+    }
+}
+
+impl RepositoriesConfig {
+    /// Combine repositories detected from Gradle with the configured ones
+    ///
+    /// Configured repositories are queried first. A configured repository whose
+    /// URL matches a detected one only contributes its credentials and groups.
+    pub fn resolve(&self, detected: Vec<Repository>) -> Result<Vec<Repository>> {
+        let mut detected = if self.from_gradle {
+            detected
+        } else {
+            Vec::new()
+        };
+        let mut configured = Vec::new();
+
+        for entry in &self.custom {
+            let repository = entry.to_repository()?;
+            let url = repository.url.trim_end_matches('/');
+            match detected
+                .iter_mut()
+                .find(|repo| repo.url.trim_end_matches('/') == url)
+            {
+                Some(existing) => {
+                    existing.credentials = repository.credentials;
+                    if !repository.group_filters.is_empty() {
+                        existing.group_filters = repository.group_filters;
+                    }
+                }
+                None => configured.push(repository),
+            }
+        }
+
+        configured.append(&mut detected);
+        Ok(configured)
+    }
+}
+
+impl RepositoryConfig {
+    fn to_repository(&self) -> Result<Repository> {
+        if self.url.trim().is_empty() {
+            return Err(GvcError::Config(format!(
+                "Repository '{}' is missing a url",
+                self.name
+            )));
+        }
+
+        let credentials = match (&self.token_env, &self.username_env, &self.password_env) {
+            (Some(token), _, _) => Some(RepositoryCredentials::Bearer(self.read_env(token)?)),
+            (None, Some(username), Some(password)) => Some(RepositoryCredentials::Basic {
+                username: self.read_env(username)?,
+                password: self.read_env(password)?,
+            }),
+            (None, None, None) => None,
+            _ => {
+                return Err(GvcError::Config(format!(
+                    "Repository '{}' needs both username_env and password_env",
+                    self.name
+                )));
+            }
+        };
+
+        let name = if self.name.is_empty() {
+            self.url.clone()
+        } else {
+            self.name.clone()
+        };
+
+        Ok(Repository {
+            name,
+            url: self.url.trim_end_matches('/').to_string(),
+            group_filters: self.groups.clone(),
+            credentials,
+        })
+    }
+
+    fn read_env(&self, variable: &str) -> Result<String> {
+        std::env::var(variable).map_err(|_| {
+            GvcError::Config(format!(
+                "Environment variable '{}' referenced by repository '{}' is not set",
+                variable, self.name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(url: &str) -> Repository {
+        Repository {
+            name: "Detected".to_string(),
+            url: url.to_string(),
+            group_filters: Vec::new(),
+            credentials: None,
+        }
+    }
+
+    #[test]
+    fn configured_repositories_come_first() {
+        let config = RepositoriesConfig {
+            from_gradle: true,
+            custom: vec![RepositoryConfig {
+                name: "Internal".to_string(),
+                url: "https://nexus.example.com/maven/".to_string(),
+                ..Default::default()
+            }],
+        };
+
+        let repos = config
+            .resolve(vec![detected("https://repo1.maven.org/maven2")])
+            .unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].url, "https://nexus.example.com/maven");
+    }
+
+    #[test]
+    fn matching_url_only_adds_groups() {
+        let config = RepositoriesConfig {
+            from_gradle: true,
+            custom: vec![RepositoryConfig {
+                url: "https://repo1.maven.org/maven2/".to_string(),
+                groups: vec!["com\\.example.*".to_string()],
+                ..Default::default()
+            }],
+        };
+
+        let repos = config
+            .resolve(vec![detected("https://repo1.maven.org/maven2")])
+            .unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "Detected");
+        assert_eq!(repos[0].group_filters, vec!["com\\.example.*".to_string()]);
+    }
+
+    #[test]
+    fn missing_credentials_variable_is_an_error() {
+        let config = RepositoriesConfig {
+            from_gradle: false,
+            custom: vec![RepositoryConfig {
+                name: "Internal".to_string(),
+                url: "https://nexus.example.com/maven".to_string(),
+                token_env: Some("GVC_TEST_TOKEN_THAT_IS_NOT_SET".to_string()),
+                ..Default::default()
+            }],
+        };
+
+        let err = config.resolve(Vec::new()).unwrap_err();
+        assert!(matches!(err, GvcError::Config(_)));
+    }
+}
@@ -25,8 +25,37 @@ pub enum GvcError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Pull request failed: {0}")]
+    PullRequest(String),
+
+    #[error("Catalog '{0}' is not formatted; run `gvc fmt` to fix it")]
+    NotFormatted(String),
+
+    #[error("Lint found {0} problem(s)")]
+    LintFailed(usize),
+
     #[error("Operation cancelled by user")]
     UserCancelled,
+
+    #[error("{0} dependencies have updates matching --fail-on")]
+    UpdatesAvailable(usize),
+
+    #[error("{0} dependencies have known vulnerabilities")]
+    VulnerableDependencies(usize),
+}
+
+impl GvcError {
+    /// Process exit code: 2 and 3 report a `--fail-on` gate, everything else is 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GvcError::UpdatesAvailable(_) => 2,
+            GvcError::VulnerableDependencies(_) => 3,
+            _ => 1,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, GvcError>;
@@ -0,0 +1,272 @@
+use super::{ForgeKind, RemoteRepository};
+use crate::config::ForgeConfig;
+use crate::error::{GvcError, Result};
+use reqwest::blocking::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use url::form_urlencoded::byte_serialize;
+
+/// A pull request (merge request on GitLab) to open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    pub title: String,
+    pub body: String,
+    /// Branch with the changes
+    pub head: String,
+    /// Branch the changes should be merged into
+    pub base: String,
+}
+
+/// Opens pull requests through the REST API of GitHub, GitLab or Gitea
+pub struct ForgeClient {
+    client: Client,
+    kind: ForgeKind,
+    api_url: String,
+    repository: String,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct Created {
+    #[serde(alias = "web_url")]
+    html_url: String,
+}
+
+impl ForgeClient {
+    pub fn new(kind: ForgeKind, api_url: &str, repository: &str, token: String) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("gvc")
+            .build()
+            .map_err(|e| GvcError::Io(std::io::Error::other(e)))?;
+
+        Ok(Self {
+            client,
+            kind,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            repository: repository.to_string(),
+            token,
+        })
+    }
+
+    /// Client for the repository behind `remote_url`, with settings from `[forge]` taking precedence
+    pub fn from_config(config: &ForgeConfig, remote_url: &str) -> Result<Self> {
+        let remote = RemoteRepository::parse(remote_url)?;
+        let kind = config
+            .kind
+            .or_else(|| ForgeKind::detect(&remote.host))
+            .ok_or_else(|| {
+                GvcError::Config(format!(
+                    "Cannot tell which forge hosts {}; set forge.kind to github, gitlab or gitea",
+                    remote.host
+                ))
+            })?;
+        let token_env = config
+            .token_env
+            .as_deref()
+            .unwrap_or(kind.default_token_env());
+        let token = std::env::var(token_env)
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| {
+                GvcError::Config(format!(
+                    "Set {} to a token that may open pull requests",
+                    token_env
+                ))
+            })?;
+        let api_url = config
+            .api_url
+            .clone()
+            .unwrap_or_else(|| kind.default_api_url(&remote.host));
+        let repository = config.repository.as_deref().unwrap_or(&remote.path);
+
+        Self::new(kind, &api_url, repository, token)
+    }
+
+    /// Create the pull request and return its web URL
+    pub fn open(&self, pull_request: &PullRequest) -> Result<String> {
+        let request = match self.kind {
+            ForgeKind::Github => self
+                .client
+                .post(format!("{}/repos/{}/pulls", self.api_url, self.repository))
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .json(&json!({
+                    "title": pull_request.title,
+                    "body": pull_request.body,
+                    "head": pull_request.head,
+                    "base": pull_request.base,
+                })),
+            ForgeKind::Gitlab => {
+                let project: String = byte_serialize(self.repository.as_bytes()).collect();
+                self.client
+                    .post(format!(
+                        "{}/projects/{}/merge_requests",
+                        self.api_url, project
+                    ))
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .json(&json!({
+                        "title": pull_request.title,
+                        "description": pull_request.body,
+                        "source_branch": pull_request.head,
+                        "target_branch": pull_request.base,
+                    }))
+            }
+            ForgeKind::Gitea => self
+                .client
+                .post(format!("{}/repos/{}/pulls", self.api_url, self.repository))
+                .header("Authorization", format!("token {}", self.token))
+                .json(&json!({
+                    "title": pull_request.title,
+                    "body": pull_request.body,
+                    "head": pull_request.head,
+                    "base": pull_request.base,
+                })),
+        };
+
+        self.send(request)
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<String> {
+        let response = request.send().map_err(|e| {
+            GvcError::PullRequest(format!("Failed to reach {}: {}", self.api_url, e))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().unwrap_or_default();
+            return Err(GvcError::PullRequest(format!(
+                "{} answered {}: {}",
+                self.api_url,
+                status,
+                detail.trim()
+            )));
+        }
+        let created: Created = response.json().map_err(|e| {
+            GvcError::PullRequest(format!("Unexpected response from {}: {}", self.api_url, e))
+        })?;
+        Ok(created.html_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Answer one request with `response` and hand back the raw request
+    fn mock_server(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    fn update_request() -> PullRequest {
+        PullRequest {
+            title: "Update dependencies".into(),
+            body: "## Dependency updates".into(),
+            head: "deps/update".into(),
+            base: "main".into(),
+        }
+    }
+
+    #[test]
+    fn opens_a_pull_request_on_github() {
+        let (url, server) = mock_server(r#"{"html_url":"https://github.test/team/app/pull/7"}"#);
+        let client =
+            ForgeClient::new(ForgeKind::Github, &url, "team/app", "secret".into()).unwrap();
+
+        let created = client.open(&update_request()).unwrap();
+        assert_eq!(created, "https://github.test/team/app/pull/7");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /repos/team/app/pulls HTTP/1.1"));
+        let lowercase = request.to_ascii_lowercase();
+        assert!(lowercase.contains("authorization: bearer secret"));
+        assert!(lowercase.contains("accept: application/vnd.github+json"));
+        assert!(request.contains(r#""head":"deps/update""#));
+        assert!(request.contains(r#""base":"main""#));
+    }
+
+    #[test]
+    fn opens_a_pull_request_on_gitea() {
+        let (url, server) = mock_server(r#"{"html_url":"https://gitea.test/team/app/pulls/7"}"#);
+        let client = ForgeClient::new(
+            ForgeKind::Gitea,
+            &format!("{}/api/v1", url),
+            "team/app",
+            "secret".into(),
+        )
+        .unwrap();
+
+        let created = client.open(&update_request()).unwrap();
+        assert_eq!(created, "https://gitea.test/team/app/pulls/7");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /api/v1/repos/team/app/pulls HTTP/1.1"));
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("authorization: token secret")
+        );
+        assert!(request.contains(r#""head":"deps/update""#));
+        assert!(request.contains(r#""base":"main""#));
+    }
+
+    #[test]
+    fn opens_a_merge_request_on_gitlab() {
+        let (url, server) =
+            mock_server(r#"{"web_url":"https://gitlab.test/team/app/-/merge_requests/7"}"#);
+        let client = ForgeClient::new(
+            ForgeKind::Gitlab,
+            &format!("{}/api/v4/", url),
+            "team/app",
+            "secret".into(),
+        )
+        .unwrap();
+
+        let created = client.open(&update_request()).unwrap();
+        assert_eq!(created, "https://gitlab.test/team/app/-/merge_requests/7");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /api/v4/projects/team%2Fapp/merge_requests HTTP/1.1"));
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("private-token: secret")
+        );
+        assert!(request.contains(r#""source_branch":"deps/update""#));
+        assert!(request.contains(r#""target_branch":"main""#));
+    }
+}
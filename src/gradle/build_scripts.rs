@@ -0,0 +1,446 @@
+use crate::error::Result;
+use crate::utils::diff::FileEdit;
+use crate::utils::toml::TomlUtils;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for build scripts
+const SKIPPED_DIRS: &[&str] = &[".git", ".gradle", ".idea", "build", "out", "node_modules"];
+
+/// Convention plugin sources in buildSrc and included builds
+const SOURCE_EXTENSIONS: &[&str] = &["kt", "groovy", "java"];
+
+/// Settings files that may declare included builds
+const SETTINGS_FILES: &[&str] = &["settings.gradle.kts", "settings.gradle"];
+
+/// Catalog sections addressed by accessors, keyed by their accessor namespace
+const ACCESSOR_NAMESPACES: &[(&str, &str)] = &[
+    ("plugins", "plugins"),
+    ("versions", "versions"),
+    ("bundles", "bundles"),
+];
+
+/// `VersionCatalog.find*` lookups and the catalog section they search
+const FINDER_KINDS: &[(&str, &str)] = &[
+    ("Library", "libraries"),
+    ("Plugin", "plugins"),
+    ("Version", "versions"),
+    ("Bundle", "bundles"),
+];
+
+/// A line in a build script that references a catalog accessor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptUsage {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+}
+
+/// A catalog entry referenced from build logic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogReference {
+    /// Catalog section: `libraries`, `plugins`, `versions` or `bundles`
+    pub section: &'static str,
+    /// Dotted accessor path after the section namespace, e.g. `ktor.core.get`
+    pub path: String,
+    /// The path names an alias exactly (`findLibrary("ktor-core")`) rather than an accessor chain
+    pub exact: bool,
+}
+
+/// Finds Gradle build scripts below a project root and searches them for catalog accessors
+pub struct BuildScriptScanner {
+    root: PathBuf,
+}
+
+impl BuildScriptScanner {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        let root = root.as_ref();
+        Self {
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+        }
+    }
+
+    /// All `*.gradle` and `*.gradle.kts` files, including those of included builds
+    pub fn scripts(&self) -> Result<Vec<PathBuf>> {
+        let mut scripts = Vec::new();
+        collect_files(&self.root, &mut scripts, &is_script)?;
+        for build in self.included_builds() {
+            if !build.starts_with(&self.root) {
+                collect_files(&build, &mut scripts, &is_script)?;
+            }
+        }
+        scripts.sort();
+        scripts.dedup();
+        Ok(scripts)
+    }
+
+    /// Build scripts plus convention plugin sources from buildSrc and included builds
+    pub fn build_logic_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = self.scripts()?;
+        let mut roots = self.included_builds();
+        roots.push(self.root.join("buildSrc"));
+
+        for root in roots.iter().filter(|root| root.is_dir()) {
+            collect_files(root, &mut files, &|name| {
+                SOURCE_EXTENSIONS
+                    .iter()
+                    .any(|ext| name.ends_with(&format!(".{}", ext)))
+            })?;
+        }
+
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Directories of builds included from the root settings via `includeBuild(...)`
+    pub fn included_builds(&self) -> Vec<PathBuf> {
+        let pattern = Regex::new(r#"includeBuild\s*\(?\s*["']([^"']+)["']"#)
+            .expect("valid includeBuild regex");
+
+        SETTINGS_FILES
+            .iter()
+            .filter_map(|name| fs::read_to_string(self.root.join(name)).ok())
+            .flat_map(|content| {
+                pattern
+                    .captures_iter(&content)
+                    .map(|caps| self.root.join(&caps[1]))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|path| path.is_dir())
+            .filter_map(|path| path.canonicalize().ok())
+            .collect()
+    }
+
+    /// Every reference to the `catalog` accessor found in build logic
+    ///
+    /// Covers `libs.x.y`, `libs.plugins.x`, `libs.versions.x.get()`, `libs.bundles.x`
+    /// and the string-based `findLibrary("x")` family used in convention plugins.
+    pub fn catalog_references(&self, catalog: &str) -> Result<Vec<CatalogReference>> {
+        let accessor = Regex::new(&format!(
+            r"\b{}((?:\.[A-Za-z_][A-Za-z0-9_]*)+)",
+            regex::escape(catalog)
+        ))
+        .expect("escaped catalog name is a valid regex");
+        let finder = Regex::new(r#"\bfind(Library|Plugin|Version|Bundle)\s*\(\s*"([^"]+)"\s*\)"#)
+            .expect("valid finder regex");
+
+        let mut references = Vec::new();
+        for path in self.build_logic_files()? {
+            let content = fs::read_to_string(&path)?;
+
+            for caps in accessor.captures_iter(&content) {
+                let chain = caps[1].trim_start_matches('.');
+                let (section, rest) = ACCESSOR_NAMESPACES
+                    .iter()
+                    .find_map(|(namespace, section)| {
+                        chain
+                            .strip_prefix(namespace)
+                            .and_then(|rest| rest.strip_prefix('.'))
+                            .map(|rest| (*section, rest))
+                    })
+                    .unwrap_or(("libraries", chain));
+
+                references.push(CatalogReference {
+                    section,
+                    path: rest.to_string(),
+                    exact: false,
+                });
+            }
+
+            for caps in finder.captures_iter(&content) {
+                let section = FINDER_KINDS
+                    .iter()
+                    .find(|(kind, _)| *kind == &caps[1])
+                    .map(|(_, section)| *section)
+                    .unwrap_or("libraries");
+                references.push(CatalogReference {
+                    section,
+                    path: caps[2].to_string(),
+                    exact: true,
+                });
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Find references to `accessor` (e.g. `libs.ktor.core`)
+    ///
+    /// `longer` lists other accessors that start with `accessor`; a match that is
+    /// really one of those (such as `libs.ktor.core.ktx`) is not reported.
+    pub fn find_usages(&self, accessor: &str, longer: &[String]) -> Result<Vec<ScriptUsage>> {
+        let pattern = accessor_pattern(accessor);
+
+        let mut usages = Vec::new();
+        for path in self.scripts()? {
+            let content = fs::read_to_string(&path)?;
+            for (index, line) in content.lines().enumerate() {
+                let matched = pattern.captures_iter(line).any(|caps| {
+                    let rest = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+                    !longer
+                        .iter()
+                        .any(|other| is_prefix_segment(&format!("{}{}", accessor, rest), other))
+                });
+
+                if matched {
+                    usages.push(ScriptUsage {
+                        path: path.clone(),
+                        line: index + 1,
+                        text: line.trim().to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(usages)
+    }
+
+    /// Rewrite accessor usages of a renamed alias in build scripts and build logic sources
+    ///
+    /// `libs.old.alias` becomes `libs.new.alias` (trailing calls such as `.get()` are
+    /// kept) and `findLibrary("old-alias")` style lookups are renamed too. Accessors
+    /// in `longer` belong to other aliases and are left alone. Only changed files
+    /// are returned; nothing is written.
+    pub fn rewrite_accessor(
+        &self,
+        catalog: &str,
+        section: &str,
+        old: &str,
+        new: &str,
+        longer: &[String],
+    ) -> Result<Vec<FileEdit>> {
+        let old_accessor = catalog_accessor(catalog, section, old);
+        let new_accessor = catalog_accessor(catalog, section, new);
+        let pattern = accessor_pattern(&old_accessor);
+        let finder = Regex::new(&format!(
+            r#"\b(find{}\s*\(\s*)"([^"]+)""#,
+            FINDER_KINDS
+                .iter()
+                .find(|(_, kind_section)| *kind_section == section)
+                .map(|(kind, _)| *kind)
+                .unwrap_or("Library")
+        ))
+        .expect("valid finder regex");
+        let wanted = TomlUtils::accessor_key(old);
+
+        let mut edits = Vec::new();
+        for path in self.build_logic_files()? {
+            let original = fs::read_to_string(&path)?;
+
+            let updated = pattern.replace_all(&original, |caps: &regex::Captures| {
+                let rest = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+                let used = format!("{}{}", old_accessor, rest);
+                if longer.iter().any(|other| is_prefix_segment(&used, other)) {
+                    caps[0].to_string()
+                } else {
+                    format!("{}{}", new_accessor, rest)
+                }
+            });
+            let updated = finder.replace_all(&updated, |caps: &regex::Captures| {
+                if TomlUtils::accessor_key(&caps[2]) == wanted {
+                    format!("{}\"{}\"", &caps[1], new)
+                } else {
+                    caps[0].to_string()
+                }
+            });
+
+            if updated != original {
+                edits.push(FileEdit {
+                    updated: updated.into_owned(),
+                    path,
+                    original,
+                });
+            }
+        }
+
+        Ok(edits)
+    }
+}
+
+/// Matches `accessor` plus any further segments, but not a longer identifier
+fn accessor_pattern(accessor: &str) -> Regex {
+    Regex::new(&format!(
+        r"\b{}((?:\.[A-Za-z][A-Za-z0-9]*)*)\b",
+        regex::escape(accessor)
+    ))
+    .expect("escaped accessor is a valid regex")
+}
+
+/// Gradle accessor for a catalog alias, e.g. `ktor-core` -> `libs.ktor.core`
+pub fn catalog_accessor(catalog: &str, section: &str, alias: &str) -> String {
+    let path: String = alias
+        .chars()
+        .map(|c| if matches!(c, '-' | '_') { '.' } else { c })
+        .collect();
+
+    match section {
+        "plugins" => format!("{}.plugins.{}", catalog, path),
+        "versions" => format!("{}.versions.{}", catalog, path),
+        "bundles" => format!("{}.bundles.{}", catalog, path),
+        _ => format!("{}.{}", catalog, path),
+    }
+}
+
+/// Whether `other` equals `used` or continues it with further accessor segments
+fn is_prefix_segment(used: &str, other: &str) -> bool {
+    used.strip_prefix(other)
+        .is_some_and(|tail| tail.is_empty() || tail.starts_with('.'))
+}
+
+fn is_script(name: &str) -> bool {
+    name.ends_with(".gradle") || name.ends_with(".gradle.kts")
+}
+
+pub(crate) fn collect_files(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+    accept: &dyn Fn(&str) -> bool,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if entry.file_type()?.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_ref()) {
+                collect_files(&path, files, accept)?;
+            }
+        } else if accept(&name) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn builds_accessors_per_section() {
+        assert_eq!(
+            catalog_accessor("libs", "libraries", "ktor-core"),
+            "libs.ktor.core"
+        );
+        assert_eq!(
+            catalog_accessor("libs", "plugins", "kotlin_jvm"),
+            "libs.plugins.kotlin.jvm"
+        );
+    }
+
+    #[test]
+    fn finds_usages_but_not_longer_accessors() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("app/build")).unwrap();
+        fs::write(
+            dir.path().join("app/build.gradle.kts"),
+            "dependencies {\n    implementation(libs.ktor.core)\n    implementation(libs.ktor.core.ktx)\n}\n",
+        )
+        .unwrap();
+        // Generated output is never searched
+        fs::write(
+            dir.path().join("app/build/generated.gradle.kts"),
+            "implementation(libs.ktor.core)\n",
+        )
+        .unwrap();
+
+        let scanner = BuildScriptScanner::new(dir.path());
+        let usages = scanner
+            .find_usages("libs.ktor.core", &["libs.ktor.core.ktx".to_string()])
+            .unwrap();
+
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].line, 2);
+        assert_eq!(usages[0].text, "implementation(libs.ktor.core)");
+    }
+
+    #[test]
+    fn rewrites_renamed_accessors_only() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("buildSrc/src/main/kotlin")).unwrap();
+        fs::write(
+            dir.path().join("build.gradle"),
+            "dependencies {\n    implementation libs.ktor.core\n    implementation libs.ktor.core.ktx\n    implementation libs.ktor.coreutils\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("buildSrc/src/main/kotlin/Deps.kt"),
+            "val core = libs.findLibrary(\"ktor_core\").get()\nval other = libs.findLibrary(\"okhttp\").get()\n",
+        )
+        .unwrap();
+
+        let edits = BuildScriptScanner::new(dir.path())
+            .rewrite_accessor(
+                "libs",
+                "libraries",
+                "ktor-core",
+                "ktor-client-core",
+                &["libs.ktor.core.ktx".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(edits.len(), 2);
+        let script = edits
+            .iter()
+            .find(|edit| edit.path.ends_with("build.gradle"))
+            .unwrap();
+        assert_eq!(
+            script.updated,
+            "dependencies {\n    implementation libs.ktor.client.core\n    implementation libs.ktor.core.ktx\n    implementation libs.ktor.coreutils\n}\n"
+        );
+        let source = edits
+            .iter()
+            .find(|edit| edit.path.ends_with("Deps.kt"))
+            .unwrap();
+        assert!(source.updated.contains("findLibrary(\"ktor-client-core\")"));
+        assert!(source.updated.contains("findLibrary(\"okhttp\")"));
+    }
+
+    #[test]
+    fn collects_references_from_scripts_and_build_logic() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("project");
+        let shared = dir.path().join("shared-logic");
+        fs::create_dir_all(root.join("buildSrc/src/main/kotlin")).unwrap();
+        fs::create_dir_all(shared.join("src/main/kotlin")).unwrap();
+
+        fs::write(
+            root.join("settings.gradle.kts"),
+            "pluginManagement { includeBuild(\"../shared-logic\") }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("build.gradle.kts"),
+            "plugins { alias(libs.plugins.kotlin.jvm) }\ndependencies { implementation(libs.bundles.ktor) }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("buildSrc/src/main/kotlin/Versions.kt"),
+            "val target = libs.versions.jvm.target.get()\n",
+        )
+        .unwrap();
+        fs::write(
+            shared.join("src/main/kotlin/Convention.kt"),
+            "catalog.findLibrary(\"okhttp\").get()\n",
+        )
+        .unwrap();
+
+        let references = BuildScriptScanner::new(&root)
+            .catalog_references("libs")
+            .unwrap();
+        let found: Vec<(&str, &str, bool)> = references
+            .iter()
+            .map(|r| (r.section, r.path.as_str(), r.exact))
+            .collect();
+
+        assert!(found.contains(&("plugins", "kotlin.jvm", false)));
+        assert!(found.contains(&("bundles", "ktor", false)));
+        assert!(found.contains(&("versions", "jvm.target.get", false)));
+        assert!(found.contains(&("libraries", "okhttp", true)));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::gradle::build_scripts::collect_files;
+use crate::gradle::{BuildScriptScanner, CatalogFile, discover_catalogs};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Settings files that mark the root of a Gradle build
+const SETTINGS_FILES: &[&str] = &["settings.gradle.kts", "settings.gradle"];
+
+/// A Gradle build with its own settings, repositories and version catalogs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradleBuild {
+    /// Path relative to the workspace root, or the directory name for the root itself
+    pub name: String,
+    pub path: PathBuf,
+    pub catalogs: Vec<CatalogFile>,
+}
+
+/// Find every Gradle build of a workspace that has a version catalog
+///
+/// Covers the root build, builds pulled in with `includeBuild(...)` (also from
+/// included builds) and standalone builds in subdirectories. `buildSrc` is part of
+/// the build that owns it and is never reported on its own. The root comes first,
+/// the others are sorted by path.
+pub fn discover_builds(root: &Path) -> Vec<GradleBuild> {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+    let mut settings = Vec::new();
+    // An unreadable directory only hides the builds below it
+    let _ = collect_files(&root, &mut settings, &|name| SETTINGS_FILES.contains(&name));
+
+    let mut queue: VecDeque<PathBuf> = std::iter::once(root.clone())
+        .chain(
+            settings
+                .iter()
+                .filter_map(|file| file.parent().map(Path::to_path_buf)),
+        )
+        .collect();
+    let mut seen: Vec<PathBuf> = Vec::new();
+
+    while let Some(dir) = queue.pop_front() {
+        if seen.contains(&dir) || dir.file_name().is_some_and(|name| name == "buildSrc") {
+            continue;
+        }
+        queue.extend(BuildScriptScanner::new(&dir).included_builds());
+        seen.push(dir);
+    }
+
+    let mut builds: Vec<GradleBuild> = seen
+        .into_iter()
+        .filter_map(|path| {
+            let catalogs = discover_catalogs(&path);
+            if catalogs.is_empty() {
+                return None;
+            }
+            let name = match path.strip_prefix(&root) {
+                Ok(relative) if !relative.as_os_str().is_empty() => relative.display().to_string(),
+                _ => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string()),
+            };
+            Some(GradleBuild {
+                name,
+                path,
+                catalogs,
+            })
+        })
+        .collect();
+
+    builds.sort_by(|a, b| (a.path != root, &a.path).cmp(&(b.path != root, &b.path)));
+    builds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write(path: PathBuf, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn finds_root_included_and_sibling_builds() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("workspace");
+        write(
+            root.join("settings.gradle.kts"),
+            "includeBuild(\"../shared\")\n",
+        );
+        write(root.join("gradle/libs.versions.toml"), "");
+        write(root.join("buildSrc/settings.gradle.kts"), "");
+        write(root.join("buildSrc/gradle/libs.versions.toml"), "");
+        write(root.join("tools/cli/settings.gradle"), "");
+        write(root.join("tools/cli/gradle/libs.versions.toml"), "");
+        write(root.join("docs/settings.gradle"), "");
+        write(dir.path().join("shared/settings.gradle.kts"), "");
+        write(dir.path().join("shared/gradle/libs.versions.toml"), "");
+
+        let names: Vec<String> = discover_builds(&root)
+            .into_iter()
+            .map(|build| build.name)
+            .collect();
+
+        assert_eq!(names, vec!["workspace", "shared", "tools/cli"]);
+    }
+}
@@ -0,0 +1,154 @@
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the catalog Gradle creates from `gradle/libs.versions.toml`
+pub const DEFAULT_CATALOG: &str = "libs";
+
+/// Suffix of catalog files picked up from the `gradle/` directory
+const CATALOG_SUFFIX: &str = ".versions.toml";
+
+/// A version catalog file and the accessor name Gradle generates for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogFile {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Find every version catalog of a build
+///
+/// Catalogs declared in settings through `versionCatalogs { create("x") { from(files(...)) } }`
+/// keep their declared name; other `gradle/*.versions.toml` files are named after the file.
+/// The default `libs` catalog comes first, the rest follow in declaration order and then
+/// by file name.
+pub fn discover_catalogs(project_path: &Path) -> Vec<CatalogFile> {
+    let mut catalogs = declared_catalogs(project_path);
+
+    let mut convention: Vec<PathBuf> = fs::read_dir(project_path.join("gradle"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && catalog_stem(path).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    convention.sort();
+
+    for path in convention {
+        let declared = catalogs
+            .iter()
+            .any(|catalog| same_file(&catalog.path, &path));
+        if let Some(name) = catalog_stem(&path).filter(|_| !declared) {
+            catalogs.push(CatalogFile {
+                name: name.to_string(),
+                path,
+            });
+        }
+    }
+
+    // Put `libs` first; the stable sort keeps the rest in order
+    catalogs.sort_by_key(|catalog| catalog.name != DEFAULT_CATALOG);
+    catalogs
+}
+
+/// Catalogs created from local files in `settings.gradle(.kts)`
+fn declared_catalogs(project_path: &Path) -> Vec<CatalogFile> {
+    let pattern = Regex::new(
+        r#"(?:create\s*\(\s*["']([A-Za-z][A-Za-z0-9]*)["']\s*\)|\b([A-Za-z][A-Za-z0-9]*))\s*\{\s*from\s*\(?\s*files\s*\(\s*["']([^"']+)["']"#,
+    )
+    .expect("valid versionCatalogs regex");
+
+    let mut catalogs: Vec<CatalogFile> = Vec::new();
+    for name in ["settings.gradle.kts", "settings.gradle"] {
+        let Ok(content) = fs::read_to_string(project_path.join(name)) else {
+            continue;
+        };
+        for caps in pattern.captures_iter(&content) {
+            let Some(name) = caps.get(1).or_else(|| caps.get(2)) else {
+                continue;
+            };
+            let path = project_path.join(&caps[3]);
+            if path.is_file() && !catalogs.iter().any(|c| c.name == name.as_str()) {
+                catalogs.push(CatalogFile {
+                    name: name.as_str().to_string(),
+                    path,
+                });
+            }
+        }
+    }
+    catalogs
+}
+
+/// `testlibs` for `testlibs.versions.toml`
+fn catalog_stem(path: &Path) -> Option<&str> {
+    path.file_name()?
+        .to_str()?
+        .strip_suffix(CATALOG_SUFFIX)
+        .filter(|stem| !stem.is_empty())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn combines_settings_declarations_and_convention_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("gradle")).unwrap();
+        for file in [
+            "libs.versions.toml",
+            "testlibs.versions.toml",
+            "tools.versions.toml",
+            "notes.toml",
+        ] {
+            fs::write(dir.path().join("gradle").join(file), "").unwrap();
+        }
+        fs::write(
+            dir.path().join("settings.gradle.kts"),
+            r#"dependencyResolutionManagement {
+    versionCatalogs {
+        create("testLibs") {
+            from(files("gradle/testlibs.versions.toml"))
+        }
+        create("remote") {
+            from("com.example:catalog:1.0")
+        }
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let names: Vec<String> = discover_catalogs(dir.path())
+            .into_iter()
+            .map(|catalog| catalog.name)
+            .collect();
+        assert_eq!(names, vec!["libs", "testLibs", "tools"]);
+    }
+
+    #[test]
+    fn reads_groovy_declarations() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("deps")).unwrap();
+        fs::write(dir.path().join("deps/android.toml"), "").unwrap();
+        fs::write(
+            dir.path().join("settings.gradle"),
+            "dependencyResolutionManagement {\n    versionCatalogs {\n        androidLibs {\n            from files('deps/android.toml')\n        }\n    }\n}\n",
+        )
+        .unwrap();
+
+        let catalogs = discover_catalogs(dir.path());
+        assert_eq!(catalogs.len(), 1);
+        assert_eq!(catalogs[0].name, "androidLibs");
+        assert!(catalogs[0].path.ends_with("deps/android.toml"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::{GvcError, Result};
+use crate::utils::output::outln;
 use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -10,6 +11,29 @@ pub struct Repository {
     pub url: String,
     /// Regex patterns for group filtering (from mavenContent.includeGroupByRegex)
     pub group_filters: Vec<String>,
+    /// Credentials resolved from the gvc configuration, if any
+    pub credentials: Option<RepositoryCredentials>,
+}
+
+/// Credentials used when querying a private repository
+#[derive(Clone, PartialEq, Eq)]
+pub enum RepositoryCredentials {
+    Basic { username: String, password: String },
+    Bearer(String),
+}
+
+impl std::fmt::Debug for RepositoryCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never leak secrets into verbose logs
+        match self {
+            RepositoryCredentials::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"***")
+                .finish(),
+            RepositoryCredentials::Bearer(_) => f.debug_tuple("Bearer").field(&"***").finish(),
+        }
+    }
 }
 
 /// Gradle project configuration
@@ -56,7 +80,7 @@ impl GradleConfigParser {
 
         if repositories.is_empty() {
             // Fall back to defaults when no repositories are configured explicitly
-            println!("⚠️  No repositories found in Gradle config, using defaults");
+            outln!("⚠️  No repositories found in Gradle config, using defaults");
             repositories = self.get_default_repositories();
         }
 
@@ -120,6 +144,7 @@ impl GradleConfigParser {
                 name: "Maven Central".to_string(),
                 url: "https://repo1.maven.org/maven2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             });
         }
 
@@ -133,6 +158,7 @@ impl GradleConfigParser {
                     ".*android.*".to_string(),
                     ".*androidx.*".to_string(),
                 ],
+                credentials: None,
             });
         }
 
@@ -142,6 +168,7 @@ impl GradleConfigParser {
                 name: "Gradle Plugin Portal".to_string(),
                 url: "https://plugins.gradle.org/m2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             });
         }
 
@@ -156,6 +183,7 @@ impl GradleConfigParser {
                     name: format!("Custom ({})", Self::shorten_url(url.as_str())),
                     url: url.as_str().to_string(),
                     group_filters: Vec::new(),
+                    credentials: None,
                 });
             }
         }
@@ -170,6 +198,7 @@ impl GradleConfigParser {
                     name: format!("Custom ({})", Self::shorten_url(url.as_str())),
                     url: url.as_str().to_string(),
                     group_filters: Vec::new(),
+                    credentials: None,
                 });
             }
         }
@@ -187,6 +216,7 @@ impl GradleConfigParser {
                 name: "Maven Central".to_string(),
                 url: "https://repo1.maven.org/maven2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             });
         }
 
@@ -200,6 +230,7 @@ impl GradleConfigParser {
                     ".*android.*".to_string(),
                     ".*androidx.*".to_string(),
                 ],
+                credentials: None,
             });
         }
 
@@ -209,6 +240,7 @@ impl GradleConfigParser {
                 name: "JCenter (Deprecated)".to_string(),
                 url: "https://jcenter.bintray.com".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             });
         }
 
@@ -222,6 +254,7 @@ impl GradleConfigParser {
                     name: format!("Custom ({})", Self::shorten_url(url.as_str())),
                     url: url.as_str().to_string(),
                     group_filters: Vec::new(),
+                    credentials: None,
                 });
             }
         }
@@ -236,6 +269,7 @@ impl GradleConfigParser {
                     name: format!("Custom ({})", Self::shorten_url(url.as_str())),
                     url: url.as_str().to_string(),
                     group_filters: Vec::new(),
+                    credentials: None,
                 });
             }
         }
@@ -250,6 +284,7 @@ impl GradleConfigParser {
                 name: "Maven Central".to_string(),
                 url: "https://repo1.maven.org/maven2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             },
             Repository {
                 name: "Google Maven".to_string(),
@@ -259,6 +294,7 @@ impl GradleConfigParser {
                     ".*android.*".to_string(),
                     ".*androidx.*".to_string(),
                 ],
+                credentials: None,
             },
         ]
     }
@@ -277,6 +313,7 @@ impl GradleConfigParser {
                     name: repo.name,
                     url: normalized_url,
                     group_filters: repo.group_filters,
+                    credentials: repo.credentials,
                 });
             }
         }
@@ -347,16 +384,19 @@ repositories {
                 name: "Maven Central".to_string(),
                 url: "https://repo1.maven.org/maven2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             },
             Repository {
                 name: "Maven Central".to_string(),
                 url: "https://repo1.maven.org/maven2/".to_string(), // trailing slash
                 group_filters: Vec::new(),
+                credentials: None,
             },
             Repository {
                 name: "Google".to_string(),
                 url: "https://dl.google.com/dl/android/maven2".to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             },
         ];
 
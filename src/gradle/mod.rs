@@ -1,3 +1,9 @@
+pub mod build_scripts;
+pub mod builds;
+pub mod catalogs;
 pub mod config_parser;
 
-pub use config_parser::{GradleConfigParser, Repository};
+pub use build_scripts::{BuildScriptScanner, catalog_accessor};
+pub use builds::{GradleBuild, discover_builds};
+pub use catalogs::{CatalogFile, discover_catalogs};
+pub use config_parser::{GradleConfigParser, Repository, RepositoryCredentials};
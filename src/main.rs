@@ -1,16 +1,23 @@
 mod agents;
 mod cli;
+mod config;
 mod error;
+mod forge;
 mod gradle;
 mod maven;
+mod report;
 mod repository;
 mod utils;
 mod workflow;
 
+use agents::journal::Snapshot;
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{BundleCommands, Cli, Commands, ConfigCommands};
 use colored::Colorize;
+use config::{CliOverrides, ConfigLoader};
+use report::FailOn;
 use std::process;
+use utils::path_validator::PathValidator;
 
 fn main() {
     let cli = Cli::parse();
@@ -21,37 +28,258 @@ fn main() {
         }
     }
 
+    let overrides = cli_overrides(&cli.command);
+    let loaded = match ConfigLoader::new(&cli.path).load(overrides) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            process::exit(1);
+        }
+    };
+    loaded.config.output.color.apply();
+    let config = &loaded.config;
+
+    let snapshot = journaled_command(&cli.command).and_then(|command| {
+        let build_logic = matches!(
+            cli.command,
+            Commands::Migrate { .. } | Commands::Rename { .. }
+        );
+        let snapshot = PathValidator::validate_project_path(&cli.path)
+            .and_then(|path| Snapshot::take(&path, build_logic));
+        match snapshot {
+            Ok(snapshot) => Some((command, snapshot)),
+            Err(e) => {
+                eprintln!("{} gvc undo is unavailable: {}", "Warning:".yellow(), e);
+                None
+            }
+        }
+    });
+
     let result = match cli.command {
         Commands::Update {
-            interactive,
             filter,
-            stable_only,
-            no_git,
-        } => workflow::execute_update(&cli.path, interactive, filter, stable_only, no_git),
-        Commands::Check { include_unstable } => {
-            workflow::execute_check(&cli.path, !include_unstable)
+            catalog,
+            all_builds,
+            align,
+            dry_run,
+            output_patch,
+            summary_md,
+            ..
+        } => workflow::execute_update(
+            &cli.path,
+            config,
+            filter,
+            workflow::BuildScope {
+                catalog: catalog.as_deref(),
+                all_builds,
+                align,
+            },
+            workflow::DryRun {
+                enabled: dry_run,
+                output_patch: output_patch.as_deref(),
+            },
+            summary_md.as_deref(),
+        ),
+        Commands::Check {
+            catalog,
+            all_builds,
+            align,
+            fail_on,
+            junit,
+            sarif,
+            github_annotations,
+            ..
+        } => workflow::execute_check(
+            &cli.path,
+            config,
+            workflow::BuildScope {
+                catalog: catalog.as_deref(),
+                all_builds,
+                align,
+            },
+            workflow::CheckOutputs {
+                fail_on: fail_on.as_deref().and_then(FailOn::parse),
+                junit: junit.as_deref(),
+                sarif: sarif.as_deref(),
+                github_annotations,
+            },
+        ),
+        Commands::List { catalog, .. } => {
+            workflow::execute_list(&cli.path, catalog.as_deref(), config.output.format)
         }
-        Commands::List => workflow::execute_list(&cli.path),
         Commands::Add {
             plugin,
-            library,
             coordinate,
             alias,
             version_alias,
+            bundle,
             stable_only,
+            catalog,
+            ..
         } => workflow::execute_add(
             &cli.path,
             plugin,
-            library,
             &coordinate,
-            alias.as_deref(),
-            version_alias.as_deref(),
-            stable_only,
+            workflow::AddOptions {
+                alias: alias.as_deref(),
+                version_alias: version_alias.as_deref(),
+                bundle: bundle.as_deref(),
+                stable_only,
+                catalog: catalog.as_deref(),
+            },
+            config,
         ),
+        Commands::Remove {
+            alias,
+            plugin,
+            library,
+        } => workflow::execute_remove(&cli.path, &alias, plugin, library),
+        Commands::Lint { fix } => workflow::execute_lint(&cli.path, fix),
+        Commands::Migrate { dry_run } => workflow::execute_migrate(&cli.path, dry_run),
+        Commands::Rename {
+            old,
+            new,
+            section,
+            yes,
+        } => workflow::execute_rename(&cli.path, &old, &new, section.as_deref(), yes),
+        Commands::DedupeVersions { yes } => workflow::execute_dedupe_versions(&cli.path, yes),
+        Commands::Unused { remove } => workflow::execute_unused(&cli.path, remove),
+        Commands::Fmt { check } => workflow::execute_fmt(&cli.path, check),
+        Commands::Bundle { command } => match command {
+            BundleCommands::Add { bundle, aliases } => {
+                workflow::execute_bundle_add(&cli.path, &bundle, &aliases)
+            }
+            BundleCommands::Remove { bundle, aliases } => {
+                workflow::execute_bundle_remove(&cli.path, &bundle, &aliases)
+            }
+            BundleCommands::List => workflow::execute_bundle_list(&cli.path),
+        },
+        Commands::Undo { no_git } => workflow::execute_undo(&cli.path, no_git),
+        Commands::Config {
+            command: ConfigCommands::Show,
+        } => workflow::execute_config_show(&loaded),
     };
 
+    // Journal the changes even of a failed command, so they can be undone
+    if let Some((command, snapshot)) = snapshot {
+        if let Err(e) = snapshot.record(command) {
+            eprintln!(
+                "{} failed to journal the changes: {}",
+                "Warning:".yellow(),
+                e
+            );
+        }
+    }
+
     if let Err(e) = result {
         eprintln!("{} {}", "Error:".red().bold(), e);
-        process::exit(1);
+        process::exit(e.exit_code());
+    }
+}
+
+/// Name under which a command that may change catalogs is journaled for `gvc undo`
+fn journaled_command(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Update { dry_run: false, .. } => Some("update"),
+        Commands::Add { .. } => Some("add"),
+        Commands::Remove { .. } => Some("remove"),
+        Commands::Lint { fix: true } => Some("lint --fix"),
+        Commands::Migrate { dry_run: false } => Some("migrate"),
+        Commands::Rename { .. } => Some("rename"),
+        Commands::DedupeVersions { .. } => Some("dedupe-versions"),
+        Commands::Unused { remove: true } => Some("unused --remove"),
+        Commands::Fmt { check: false } => Some("fmt"),
+        Commands::Bundle {
+            command: BundleCommands::Add { .. } | BundleCommands::Remove { .. },
+        } => Some("bundle"),
+        _ => None,
+    }
+}
+
+/// Translate explicitly passed flags into configuration overrides
+fn cli_overrides(command: &Commands) -> CliOverrides {
+    let mut overrides = CliOverrides::default();
+    match command {
+        Commands::Update {
+            interactive,
+            stable_only,
+            include_unstable,
+            track_channel,
+            no_git,
+            format,
+            summary_body,
+            commit_strategy,
+            signoff,
+            gpg_sign,
+            push,
+            open_pr,
+            stash,
+            worktree,
+            ..
+        } => {
+            set_format(&mut overrides, format);
+            if *summary_body {
+                overrides.set("git.summary_body", true, "--summary-body");
+            }
+            if let Some(strategy) = commit_strategy {
+                overrides.set(
+                    "git.commit_strategy",
+                    strategy.as_str(),
+                    "--commit-strategy",
+                );
+            }
+            if *signoff {
+                overrides.set("git.signoff", true, "--signoff");
+            }
+            if *gpg_sign {
+                overrides.set("git.sign", true, "--gpg-sign");
+            }
+            if *stash {
+                overrides.set("git.dirty_tree", "stash", "--stash");
+            } else if *worktree {
+                overrides.set("git.dirty_tree", "worktree", "--worktree");
+            }
+            if *push {
+                overrides.set("forge.push", true, "--push");
+            }
+            if *open_pr {
+                overrides.set("forge.open_pr", true, "--open-pr");
+            }
+            if *interactive {
+                overrides.set("update.interactive", true, "--interactive");
+            }
+            if *stable_only {
+                overrides.set("update.stability", "stable-only", "--stable-only");
+            } else if *track_channel {
+                overrides.set("update.stability", "track-channel", "--track-channel");
+            } else if *include_unstable {
+                overrides.set("update.stability", "include-unstable", "--include-unstable");
+            }
+            if *no_git {
+                overrides.set("git.enabled", false, "--no-git");
+            }
+        }
+        Commands::Check {
+            include_unstable,
+            track_channel,
+            format,
+            ..
+        } => {
+            set_format(&mut overrides, format);
+            if *track_channel {
+                overrides.set("update.stability", "track-channel", "--track-channel");
+            } else if *include_unstable {
+                overrides.set("update.stability", "include-unstable", "--include-unstable");
+            }
+        }
+        Commands::List { format, .. } => set_format(&mut overrides, format),
+        _ => {}
+    }
+    overrides
+}
+
+fn set_format(overrides: &mut CliOverrides, format: &Option<String>) {
+    if let Some(format) = format {
+        overrides.set("output.format", format.as_str(), "--format");
     }
 }
@@ -1,6 +1,10 @@
+pub mod osv;
 pub mod plugin_portal;
+pub mod pom;
 pub mod repository;
 pub mod version;
 
+pub use osv::OsvClient;
 pub use plugin_portal::PluginPortalClient;
+pub use pom::ProjectLinks;
 pub use repository::{MavenRepository, parse_maven_coordinate};
@@ -0,0 +1,112 @@
+use crate::error::{GvcError, Result};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const OSV_QUERY_BATCH: &str = "https://api.osv.dev/v1/querybatch";
+/// Queries accepted by one batch request
+const MAX_BATCH: usize = 1000;
+
+/// Looks up known vulnerabilities of Maven artifacts in the OSV database (osv.dev)
+pub struct OsvClient {
+    client: Client,
+}
+
+#[derive(Serialize)]
+struct BatchQuery<'a> {
+    queries: Vec<Query<'a>>,
+}
+
+#[derive(Serialize)]
+struct Query<'a> {
+    package: Package<'a>,
+    version: &'a str,
+}
+
+#[derive(Serialize)]
+struct Package<'a> {
+    name: &'a str,
+    ecosystem: &'static str,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    results: Vec<QueryResult>,
+}
+
+#[derive(Deserialize)]
+struct QueryResult {
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+#[derive(Deserialize)]
+struct Vulnerability {
+    id: String,
+}
+
+impl OsvClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("gvc")
+            .build()
+            .map_err(|e| GvcError::Io(std::io::Error::other(e)))?;
+
+        Ok(Self { client })
+    }
+
+    /// Advisory ids affecting each `(group:artifact, version)`, in the order given
+    ///
+    /// Unlike version lookups, failures are errors: a security gate must not pass
+    /// because the database could not be reached.
+    pub fn advisories(&self, packages: &[(String, String)]) -> Result<Vec<Vec<String>>> {
+        let mut advisories = Vec::with_capacity(packages.len());
+        for chunk in packages.chunks(MAX_BATCH) {
+            let body = BatchQuery {
+                queries: chunk
+                    .iter()
+                    .map(|(coordinate, version)| Query {
+                        package: Package {
+                            name: coordinate,
+                            ecosystem: "Maven",
+                        },
+                        version,
+                    })
+                    .collect(),
+            };
+
+            if std::env::var("GVC_VERBOSE").is_ok() {
+                eprintln!("[VERBOSE] Querying OSV for {} packages", chunk.len());
+            }
+
+            let response: BatchResponse = self
+                .client
+                .post(OSV_QUERY_BATCH)
+                .json(&body)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.json())
+                .map_err(|e| {
+                    GvcError::Io(std::io::Error::other(format!(
+                        "Failed to query the OSV vulnerability database: {}",
+                        e
+                    )))
+                })?;
+
+            if response.results.len() != chunk.len() {
+                return Err(GvcError::Io(std::io::Error::other(
+                    "OSV returned an unexpected number of results",
+                )));
+            }
+            advisories.extend(
+                response
+                    .results
+                    .into_iter()
+                    .map(|result| result.vulns.into_iter().map(|vuln| vuln.id).collect()),
+            );
+        }
+
+        Ok(advisories)
+    }
+}
@@ -1,12 +1,13 @@
 use crate::error::{GvcError, Result};
-use crate::maven::version::{Version, VersionComparator};
-use crate::repository::{Coordinate, RepositoryClient};
+use crate::maven::version::Version;
+use crate::repository::{Coordinate, RepositoryClient, VersionListing};
 use quick_xml::de::from_str;
 use reqwest::blocking::Client;
 use serde::Deserialize;
 use std::time::Duration;
 
 const GRADLE_PLUGIN_PORTAL: &str = "https://plugins.gradle.org/m2";
+const PORTAL_NAME: &str = "Gradle Plugin Portal";
 const MAX_METADATA_BYTES: usize = 10 * 1024 * 1024;
 
 /// Gradle Plugin Portal client
@@ -26,51 +27,6 @@ impl PluginPortalClient {
         Ok(Self { client })
     }
 
-    /// Fetch the latest version of a Gradle plugin
-    ///
-    /// Plugin IDs like "org.jetbrains.kotlin.jvm" are converted to Maven coordinates:
-    /// - Group: org.jetbrains.kotlin.jvm
-    /// - Artifact: org.jetbrains.kotlin.jvm.gradle.plugin
-    pub fn fetch_latest_plugin_version(
-        &self,
-        plugin_id: &str,
-        stable_only: bool,
-    ) -> Result<Option<String>> {
-        // Gradle plugins are published with a special naming convention
-        // Plugin ID: org.jetbrains.kotlin.jvm
-        // Maven coordinate: org.jetbrains.kotlin.jvm:org.jetbrains.kotlin.jvm.gradle.plugin
-
-        let group = plugin_id;
-        let artifact = format!("{}.gradle.plugin", plugin_id);
-
-        if std::env::var("GVC_VERBOSE").is_ok() {
-            eprintln!(
-                "[VERBOSE] Fetching plugin: {} ({}:{})",
-                plugin_id, group, artifact
-            );
-        }
-
-        let versions = self.fetch_all_plugin_versions(group, &artifact)?;
-
-        if let Some(versions) = versions {
-            if versions.is_empty() {
-                return Ok(None);
-            }
-
-            if std::env::var("GVC_VERBOSE").is_ok() {
-                eprintln!(
-                    "[VERBOSE] Found {} versions for plugin {}",
-                    versions.len(),
-                    plugin_id
-                );
-            }
-
-            Ok(VersionComparator::get_latest(&versions, stable_only))
-        } else {
-            Ok(None)
-        }
-    }
-
     /// Fetch available versions for a plugin, sorted from newest to oldest.
     pub fn fetch_available_plugin_versions(&self, plugin_id: &str) -> Result<Vec<String>> {
         let group = plugin_id;
@@ -148,12 +104,13 @@ impl RepositoryClient for PluginPortalClient {
         self.fetch_available_plugin_versions(&coordinate.group)
     }
 
-    fn fetch_latest_version(
-        &self,
-        coordinate: &Coordinate,
-        stable_only: bool,
-    ) -> Result<Option<String>> {
-        self.fetch_latest_plugin_version(&coordinate.group, stable_only)
+    fn fetch_version_listing(&self, coordinate: &Coordinate) -> Result<VersionListing> {
+        let versions = self.fetch_available_plugin_versions(&coordinate.group)?;
+        let repository = (!versions.is_empty()).then(|| PORTAL_NAME.to_string());
+        Ok(VersionListing {
+            versions,
+            repository,
+        })
     }
 }
 
@@ -196,9 +153,9 @@ mod tests {
     #[test]
     fn test_fetch_kotlin_plugin_version() {
         let client = PluginPortalClient::new().unwrap();
-        let version = client.fetch_latest_plugin_version("org.jetbrains.kotlin.jvm", true);
-        assert!(version.is_ok());
-        if let Ok(Some(v)) = version {
+        let versions = client.fetch_available_plugin_versions("org.jetbrains.kotlin.jvm");
+        assert!(versions.is_ok());
+        if let Some(v) = versions.unwrap().first() {
             println!("Latest Kotlin plugin version: {}", v);
             assert!(!v.is_empty());
         }
@@ -0,0 +1,107 @@
+use quick_xml::de::from_str;
+use serde::Deserialize;
+use url::Url;
+
+/// Links a POM publishes about its project
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectLinks {
+    /// Project home page (`<url>`)
+    pub project: Option<String>,
+    /// Browsable source repository (`<scm><url>`)
+    pub source: Option<String>,
+    /// Release notes, known for projects hosted on GitHub
+    pub releases: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Pom {
+    url: Option<String>,
+    scm: Option<Scm>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scm {
+    url: Option<String>,
+}
+
+impl ProjectLinks {
+    /// Read the links of a POM; unparsable POMs have none
+    pub fn from_pom(xml: &str) -> Self {
+        let pom: Pom = from_str(xml).unwrap_or_default();
+        let project = pom.url.and_then(web_url);
+        let source = pom.scm.and_then(|scm| scm.url).and_then(web_url);
+        let releases = source
+            .iter()
+            .chain(&project)
+            .find_map(|url| github_repository(url))
+            .map(|repository| format!("{}/releases", repository));
+
+        Self {
+            project,
+            source,
+            releases,
+        }
+    }
+}
+
+/// An http(s) URL, skipping unresolved `${...}` properties inherited from a parent POM
+fn web_url(raw: String) -> Option<String> {
+    let raw = raw.trim();
+    if raw.contains("${") {
+        return None;
+    }
+    let url = Url::parse(raw).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| raw.trim_end_matches('/').to_string())
+}
+
+/// `https://github.com/<owner>/<repo>` for URLs pointing into a GitHub repository
+fn github_repository(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    if url.host_str()? != "github.com" {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repository = segments.next()?.trim_end_matches(".git");
+    Some(format!("https://github.com/{}/{}", owner, repository))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_project_source_and_release_links() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.squareup.okhttp3</groupId>
+  <artifactId>okhttp</artifactId>
+  <version>4.12.0</version>
+  <url>https://square.github.io/okhttp/</url>
+  <licenses><license><name>Apache-2.0</name></license></licenses>
+  <scm>
+    <connection>scm:git:https://github.com/square/okhttp.git</connection>
+    <url>https://github.com/square/okhttp</url>
+  </scm>
+</project>"#;
+
+        let links = ProjectLinks::from_pom(pom);
+        assert_eq!(
+            links.project.as_deref(),
+            Some("https://square.github.io/okhttp")
+        );
+        assert_eq!(
+            links.source.as_deref(),
+            Some("https://github.com/square/okhttp")
+        );
+        assert_eq!(
+            links.releases.as_deref(),
+            Some("https://github.com/square/okhttp/releases")
+        );
+
+        let inherited =
+            ProjectLinks::from_pom("<project><url>${project.parent.url}</url></project>");
+        assert_eq!(inherited, ProjectLinks::default());
+    }
+}
@@ -1,10 +1,11 @@
 use crate::error::{GvcError, Result};
-use crate::gradle::Repository as GradleRepository;
-use crate::maven::version::{Version, VersionComparator};
-use crate::repository::{Coordinate, RepositoryClient};
+use crate::gradle::{Repository as GradleRepository, RepositoryCredentials};
+use crate::maven::pom::ProjectLinks;
+use crate::maven::version::Version;
+use crate::repository::{Coordinate, RepositoryClient, VersionListing};
 use quick_xml::de::from_str;
 use regex::Regex;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder};
 use serde::Deserialize;
 use std::net::IpAddr;
 use std::time::Duration;
@@ -47,62 +48,77 @@ impl MavenRepository {
         })
     }
 
-    /// Fetch the latest version of a dependency from repositories
-    /// Stops at the first repository that has the artifact to avoid excessive requests
-    /// Uses group_filters to skip repositories that don't match the group
-    pub fn fetch_latest_version(
-        &self,
-        group: &str,
-        artifact: &str,
-        stable_only: bool,
-    ) -> Result<Option<String>> {
-        // Try repositories in order, return first successful result
+    /// Fetch all available versions for a dependency, sorted from newest to oldest.
+    pub fn fetch_available_versions(&self, group: &str, artifact: &str) -> Result<Vec<String>> {
+        Ok(self.fetch_version_listing(group, artifact)?.versions)
+    }
+
+    /// Like `fetch_available_versions`, also naming the repository that had the artifact
+    pub fn fetch_version_listing(&self, group: &str, artifact: &str) -> Result<VersionListing> {
         for repo in &self.repositories {
-            // Skip repository if it has filters and group doesn't match any of them
             if !repo.group_filters.is_empty() && !Self::matches_filters(group, &repo.group_filters)
             {
                 continue;
             }
 
             if let Ok(Some(versions)) =
-                self.fetch_all_versions_from_repository(&repo.url, group, artifact)
+                self.fetch_all_versions_from_repository(repo, group, artifact)
             {
-                if !versions.is_empty() {
-                    // Found versions in this repository, use them
-                    return Ok(VersionComparator::get_latest(&versions, stable_only));
+                if versions.is_empty() {
+                    continue;
                 }
+
+                let mut parsed: Vec<Version> =
+                    versions.into_iter().map(|v| Version::parse(&v)).collect();
+                parsed.sort();
+                parsed.dedup_by(|a, b| a.original == b.original);
+                return Ok(VersionListing {
+                    versions: parsed.into_iter().rev().map(|v| v.original).collect(),
+                    repository: Some(repo.name.clone()),
+                });
             }
         }
 
-        // No repository had this artifact
-        Ok(None)
+        Ok(VersionListing::default())
     }
 
-    /// Fetch all available versions for a dependency, sorted from newest to oldest.
-    pub fn fetch_available_versions(&self, group: &str, artifact: &str) -> Result<Vec<String>> {
+    /// Links from the POM of `group:artifact:version`, from the first repository serving it
+    ///
+    /// Missing or unreachable POMs yield no links; they only decorate reports.
+    pub fn fetch_project_links(&self, group: &str, artifact: &str, version: &str) -> ProjectLinks {
         for repo in &self.repositories {
             if !repo.group_filters.is_empty() && !Self::matches_filters(group, &repo.group_filters)
             {
                 continue;
             }
 
-            if let Ok(Some(versions)) =
-                self.fetch_all_versions_from_repository(&repo.url, group, artifact)
-            {
-                if versions.is_empty() {
-                    continue;
-                }
+            let pom_url = format!(
+                "{}/{}/{}/{}/{}-{}.pom",
+                repo.url,
+                group.replace('.', "/"),
+                artifact,
+                version,
+                artifact,
+                version
+            );
+            if std::env::var("GVC_VERBOSE").is_ok() {
+                eprintln!("[VERBOSE] Fetching: {}", pom_url);
+            }
 
-                let mut parsed: Vec<Version> =
-                    versions.into_iter().map(|v| Version::parse(&v)).collect();
-                parsed.sort();
-                parsed.dedup_by(|a, b| a.original == b.original);
-                let ordered = parsed.into_iter().rev().map(|v| v.original).collect();
-                return Ok(ordered);
+            let Ok(response) = self.get(repo, &pom_url).send() else {
+                continue;
+            };
+            if !response.status().is_success() {
+                continue;
+            }
+            if let Ok(text) = response.text() {
+                if text.len() <= MAX_METADATA_BYTES {
+                    return ProjectLinks::from_pom(&text);
+                }
             }
         }
 
-        Ok(Vec::new())
+        ProjectLinks::default()
     }
 
     /// Check if a group matches any of the regex filters
@@ -119,21 +135,21 @@ impl MavenRepository {
 
     fn fetch_all_versions_from_repository(
         &self,
-        repo_url: &str,
+        repo: &GradleRepository,
         group: &str,
         artifact: &str,
     ) -> Result<Option<Vec<String>>> {
         let group_path = group.replace('.', "/");
         let metadata_url = format!(
             "{}/{}/{}/maven-metadata.xml",
-            repo_url, group_path, artifact
+            repo.url, group_path, artifact
         );
 
         if std::env::var("GVC_VERBOSE").is_ok() {
             eprintln!("[VERBOSE] Fetching: {}", metadata_url);
         }
 
-        let response = match self.client.get(&metadata_url).send() {
+        let response = match self.get(repo, &metadata_url).send() {
             Ok(resp) => resp,
             Err(e) => {
                 if std::env::var("GVC_VERBOSE").is_ok() {
@@ -176,9 +192,7 @@ impl MavenRepository {
         artifact: &str,
     ) -> Result<Option<DependencyMetadata>> {
         for repo in &self.repositories {
-            if let Ok(Some(metadata)) =
-                self.fetch_metadata_from_repository(&repo.url, group, artifact)
-            {
+            if let Ok(Some(metadata)) = self.fetch_metadata_from_repository(repo, group, artifact) {
                 return Ok(Some(metadata));
             }
         }
@@ -187,17 +201,17 @@ impl MavenRepository {
 
     fn fetch_metadata_from_repository(
         &self,
-        repo_url: &str,
+        repo: &GradleRepository,
         group: &str,
         artifact: &str,
     ) -> Result<Option<DependencyMetadata>> {
         let group_path = group.replace('.', "/");
         let metadata_url = format!(
             "{}/{}/{}/maven-metadata.xml",
-            repo_url, group_path, artifact
+            repo.url, group_path, artifact
         );
 
-        let response = match self.client.get(&metadata_url).send() {
+        let response = match self.get(repo, &metadata_url).send() {
             Ok(resp) => resp,
             Err(_) => return Ok(None),
         };
@@ -228,21 +242,24 @@ impl RepositoryClient for MavenRepository {
         MavenRepository::fetch_available_versions(self, &coordinate.group, &coordinate.artifact)
     }
 
-    fn fetch_latest_version(
-        &self,
-        coordinate: &Coordinate,
-        stable_only: bool,
-    ) -> Result<Option<String>> {
-        MavenRepository::fetch_latest_version(
-            self,
-            &coordinate.group,
-            &coordinate.artifact,
-            stable_only,
-        )
+    fn fetch_version_listing(&self, coordinate: &Coordinate) -> Result<VersionListing> {
+        MavenRepository::fetch_version_listing(self, &coordinate.group, &coordinate.artifact)
     }
 }
 
 impl MavenRepository {
+    /// Build a GET request, attaching the repository credentials when configured
+    fn get(&self, repo: &GradleRepository, url: &str) -> RequestBuilder {
+        let request = self.client.get(url);
+        match &repo.credentials {
+            Some(RepositoryCredentials::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            Some(RepositoryCredentials::Bearer(token)) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
     fn build_client() -> Result<Client> {
         Client::builder()
             .timeout(Duration::from_secs(10))
@@ -258,6 +275,7 @@ impl MavenRepository {
                 name: "Maven Central".to_string(),
                 url: DEFAULT_MAVEN_CENTRAL.to_string(),
                 group_filters: Vec::new(),
+                credentials: None,
             },
             GradleRepository {
                 name: "Google Maven".to_string(),
@@ -267,6 +285,7 @@ impl MavenRepository {
                     ".*android.*".to_string(),
                     ".*androidx.*".to_string(),
                 ],
+                credentials: None,
             },
         ]
     }
@@ -128,6 +128,45 @@ impl VersionComparator {
     }
 }
 
+/// How far an update moves, judged by the first differing numeric component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UpdateCategory {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl UpdateCategory {
+    /// Category of moving from `current` to `latest`; `None` when both share every
+    /// numeric component (e.g. `1.0.0-beta` to `1.0.0`)
+    pub fn between(current: &str, latest: &str) -> Option<Self> {
+        let numbers = |version: &str| -> Vec<u64> {
+            version
+                .split(['.', '-', '+'])
+                .map_while(|part| part.parse::<u64>().ok())
+                .collect()
+        };
+        let (old, new) = (numbers(current), numbers(latest));
+        let len = old.len().max(new.len());
+        let component = |parts: &[u64], index: usize| parts.get(index).copied().unwrap_or(0);
+
+        let index = (0..len).find(|&i| component(&old, i) != component(&new, i))?;
+        Some(match index {
+            0 => UpdateCategory::Major,
+            1 => UpdateCategory::Minor,
+            _ => UpdateCategory::Patch,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateCategory::Patch => "patch",
+            UpdateCategory::Minor => "minor",
+            UpdateCategory::Major => "major",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +293,14 @@ mod tests {
         assert!(v2 > v1);
         assert!(!v3.is_stable());
     }
+
+    #[test]
+    fn test_update_category() {
+        let category = |a, b| UpdateCategory::between(a, b).map(UpdateCategory::as_str);
+        assert_eq!(category("1.9.3", "2.0.0"), Some("major"));
+        assert_eq!(category("4.11.0", "4.12.0"), Some("minor"));
+        assert_eq!(category("2.3.12", "2.3.13"), Some("patch"));
+        assert_eq!(category("1.0", "1.0.1"), Some("patch"));
+        assert_eq!(category("1.0.0-beta", "1.0.0"), None);
+    }
 }
@@ -0,0 +1,368 @@
+use crate::agents::catalog_linter::line_column;
+use crate::agents::update::context::{Section, UpdateReport};
+use crate::error::{GvcError, Result};
+use crate::gradle::CatalogFile;
+use crate::maven::version::{UpdateCategory, Version};
+use crate::utils::toml::TomlUtils;
+use std::path::{Path, PathBuf};
+use toml_edit::{Document, DocumentMut, Item};
+
+/// Where an entry stands after a check or update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    Outdated,
+    Updated,
+    UpToDate,
+    Skipped,
+}
+
+impl EntryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryStatus::Outdated => "outdated",
+            EntryStatus::Updated => "updated",
+            EntryStatus::UpToDate => "up-to-date",
+            EntryStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// One catalog entry of a check or update, with everything the writers need
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    /// Build name, when several builds are reported
+    pub build: Option<String>,
+    pub catalog: String,
+    pub catalog_path: PathBuf,
+    /// 1-based line of the entry's key in the catalog
+    pub line: usize,
+    pub section: Section,
+    pub alias: String,
+    pub coordinate: Option<String>,
+    pub current: Option<String>,
+    pub status: EntryStatus,
+    pub latest: Option<String>,
+    pub candidates: Vec<String>,
+    pub category: Option<UpdateCategory>,
+    pub repository: Option<String>,
+    pub diagnostics: Vec<String>,
+    /// `(group:artifact, version)` of every library the entry decides
+    pub packages: Vec<(String, String)>,
+    /// Known vulnerabilities of `packages`, filled by `--fail-on security`
+    pub advisories: Vec<String>,
+}
+
+impl ReportEntry {
+    /// `latest` is a stable release
+    pub fn latest_is_stable(&self) -> Option<bool> {
+        self.latest
+            .as_deref()
+            .map(|latest| Version::parse(latest).is_stable())
+    }
+
+    /// Catalog path relative to `root` with `/` separators, as CI tools expect
+    pub fn catalog_file(&self, root: &Path) -> String {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let path = self
+            .catalog_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.catalog_path.clone());
+        let relative = path.strip_prefix(&root).unwrap_or(&path);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Short description such as `okhttp 4.11.0 → 4.12.0 (minor)`
+    pub fn summary(&self) -> String {
+        let current = self.current.as_deref().unwrap_or("?");
+        let mut summary = match (&self.latest, self.status) {
+            (Some(latest), EntryStatus::Outdated | EntryStatus::Updated) => {
+                format!("{} {} → {}", self.alias, current, latest)
+            }
+            _ => format!("{} {}", self.alias, current),
+        };
+        if let Some(category) = self.category {
+            summary.push_str(&format!(" ({})", category.as_str()));
+        }
+        summary
+    }
+}
+
+/// Condition that makes `gvc check` exit with a failure code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    /// Any entry has an update
+    Updates,
+    /// Any entry has a major update
+    Major,
+    /// Any library version has a known vulnerability
+    Security,
+}
+
+impl FailOn {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "updates" => Some(FailOn::Updates),
+            "major" => Some(FailOn::Major),
+            "security" => Some(FailOn::Security),
+            _ => None,
+        }
+    }
+
+    /// Whether `entry` trips the gate
+    pub fn trips(self, entry: &ReportEntry) -> bool {
+        let outdated = matches!(entry.status, EntryStatus::Outdated | EntryStatus::Updated);
+        match self {
+            FailOn::Updates => outdated,
+            FailOn::Major => outdated && entry.category == Some(UpdateCategory::Major),
+            FailOn::Security => !entry.advisories.is_empty(),
+        }
+    }
+
+    /// The error ending the run when entries trip the gate
+    pub fn failure(self, entries: &[ReportEntry]) -> Option<GvcError> {
+        let count = entries.iter().filter(|entry| self.trips(entry)).count();
+        match self {
+            _ if count == 0 => None,
+            FailOn::Security => Some(GvcError::VulnerableDependencies(count)),
+            FailOn::Updates | FailOn::Major => Some(GvcError::UpdatesAvailable(count)),
+        }
+    }
+}
+
+/// Build the entries of one catalog from a check or update report
+///
+/// `applied` marks the reported versions as written to the catalog. Entries keep
+/// the report's order: by section, then as they appear in the catalog.
+pub fn collect_entries(
+    build: Option<&str>,
+    catalog: &CatalogFile,
+    report: &UpdateReport,
+    applied: bool,
+) -> Result<Vec<ReportEntry>> {
+    let content = std::fs::read_to_string(&catalog.path)?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .map_err(|e| GvcError::TomlParsing(format!("Failed to parse TOML: {}", e)))?;
+    let spans = Document::parse(content.as_str())
+        .map_err(|e| GvcError::TomlParsing(format!("Failed to parse TOML: {}", e)))?;
+
+    let mut entries = Vec::new();
+    for record in report.entries() {
+        // Entries that never reached a repository (skipped or targeted) are described from the catalog
+        let (coordinate, current) = match (&record.coordinate, &record.current) {
+            (Some(coordinate), Some(current)) => (Some(coordinate.clone()), current.clone()),
+            _ => {
+                let (coordinate, current) = describe(&doc, record.section, &record.name);
+                (
+                    record.coordinate.clone().or(coordinate),
+                    record.current.clone().or(current).unwrap_or_default(),
+                )
+            }
+        };
+        let latest = record
+            .update
+            .clone()
+            .or_else(|| record.candidates.last().cloned());
+
+        let status = match (&record.update, &record.skip_reason) {
+            (Some(_), _) if applied => EntryStatus::Updated,
+            (Some(_), _) => EntryStatus::Outdated,
+            (None, Some(_)) => EntryStatus::Skipped,
+            (None, None) if !record.candidates.is_empty() => EntryStatus::Outdated,
+            (None, None) => EntryStatus::UpToDate,
+        };
+        let category = latest
+            .as_deref()
+            .filter(|_| status != EntryStatus::UpToDate)
+            .and_then(|latest| UpdateCategory::between(&current, latest));
+        let offset = spans
+            .get(record.section.as_str())
+            .and_then(Item::as_table)
+            .and_then(|table| table.key(&record.name))
+            .and_then(|key| key.span())
+            .map_or(0, |span| span.start);
+
+        entries.push(ReportEntry {
+            build: build.map(str::to_string),
+            catalog: catalog.name.clone(),
+            catalog_path: catalog.path.clone(),
+            line: line_column(spans.raw(), offset).0,
+            section: record.section,
+            packages: packages(&doc, record.section, &record.name, &current),
+            alias: record.name.clone(),
+            coordinate,
+            current: Some(current).filter(|c| !c.is_empty()),
+            status,
+            latest,
+            candidates: record.candidates.clone(),
+            category,
+            repository: record.repository.clone(),
+            diagnostics: record.skip_reason.iter().cloned().collect(),
+            advisories: Vec::new(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Libraries decided by an entry: the library itself, or every library using a version alias
+fn packages(
+    doc: &DocumentMut,
+    section: Section,
+    name: &str,
+    version: &str,
+) -> Vec<(String, String)> {
+    let libraries = doc.get("libraries").and_then(Item::as_table);
+    let coordinates: Vec<String> = match section {
+        Section::Plugins => Vec::new(),
+        Section::Libraries => libraries
+            .and_then(|table| table.get(name))
+            .and_then(|item| coordinate_of("libraries", item))
+            .into_iter()
+            .collect(),
+        Section::Versions => libraries
+            .into_iter()
+            .flat_map(|table| table.iter())
+            .filter(|(_, item)| TomlUtils::uses_version_ref(item, name))
+            .filter_map(|(_, item)| coordinate_of("libraries", item))
+            .collect(),
+    };
+
+    if version.is_empty() {
+        return Vec::new();
+    }
+    coordinates
+        .into_iter()
+        .map(|coordinate| (coordinate, version.to_string()))
+        .collect()
+}
+
+/// Coordinate and resolved version of a catalog entry
+///
+/// A version alias is described by the first library (or plugin) referencing it.
+pub(crate) fn describe(
+    doc: &DocumentMut,
+    section: Section,
+    name: &str,
+) -> (Option<String>, Option<String>) {
+    let Some(item) = doc.get(section.as_str()).and_then(|table| table.get(name)) else {
+        return (None, None);
+    };
+
+    let referenced = |key: &str| {
+        doc.get("versions")
+            .and_then(|versions| versions.get(key))
+            .and_then(Item::as_str)
+            .map(str::to_string)
+    };
+    let version = |explicit: Option<String>| {
+        explicit.or_else(|| TomlUtils::extract_version_ref(item).and_then(|key| referenced(&key)))
+    };
+
+    match section {
+        Section::Versions => {
+            let user = ["libraries", "plugins"].into_iter().find_map(|section| {
+                doc.get(section)?
+                    .as_table()?
+                    .iter()
+                    .find(|(_, entry)| TomlUtils::uses_version_ref(entry, name))
+                    .and_then(|(_, entry)| coordinate_of(section, entry))
+            });
+            (user, item.as_str().map(str::to_string))
+        }
+        Section::Libraries => {
+            let details = TomlUtils::extract_library_details(item);
+            (
+                coordinate_of("libraries", item),
+                version(details.and_then(|details| details.version)),
+            )
+        }
+        Section::Plugins => (
+            coordinate_of("plugins", item),
+            version(TomlUtils::extract_plugin_version(item)),
+        ),
+    }
+}
+
+fn coordinate_of(section: &str, item: &Item) -> Option<String> {
+    if section == "plugins" {
+        return TomlUtils::extract_plugin_id(item);
+    }
+    TomlUtils::extract_group_artifact(item).map(|(group, artifact)| format!("{group}:{artifact}"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::agents::update::context::EntryLookup;
+    use tempfile::{TempDir, tempdir};
+
+    /// A catalog with a pinned alias, an outdated and an up-to-date library, and its report
+    pub(crate) fn sample() -> (TempDir, CatalogFile, UpdateReport) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("libs.versions.toml");
+        std::fs::write(
+            &path,
+            "[versions]\nktor = \"2.3.12\" # gvc:pin\n\n[libraries]\nktor-core = { module = \"io.ktor:ktor-client-core\", version.ref = \"ktor\" }\nokhttp = \"com.squareup.okhttp3:okhttp:4.11.0\"\njunit = \"junit:junit:4.13.2\"\n",
+        )
+        .unwrap();
+        let catalog = CatalogFile {
+            name: "libs".into(),
+            path,
+        };
+
+        let mut report = UpdateReport::new();
+        let lookup =
+            |name: &str, coordinate: &str, current: &str, candidates: &[&str]| EntryLookup {
+                section: Section::Libraries,
+                name: name.into(),
+                coordinate: coordinate.into(),
+                current: current.into(),
+                candidates: candidates.iter().map(|c| c.to_string()).collect(),
+                repository: Some("Maven Central".into()),
+            };
+        report.add_lookup(lookup(
+            "okhttp",
+            "com.squareup.okhttp3:okhttp",
+            "4.11.0",
+            &["4.12.0", "5.0.0-alpha.14"],
+        ));
+        report.add_lookup(lookup("junit", "junit:junit", "4.13.2", &[]));
+        report.add_library_update("okhttp".into(), "4.11.0".into(), "4.12.0".into());
+        report.add_skipped(
+            Section::Versions,
+            "ktor".into(),
+            "pinned (# gvc:pin)".into(),
+        );
+
+        (dir, catalog, report)
+    }
+
+    #[test]
+    fn locates_entries_and_applies_the_gate() {
+        let (_dir, catalog, report) = sample();
+        let mut entries = collect_entries(None, &catalog, &report, false).unwrap();
+
+        let lines: Vec<(&str, usize)> = entries
+            .iter()
+            .map(|entry| (entry.alias.as_str(), entry.line))
+            .collect();
+        assert_eq!(lines, vec![("ktor", 2), ("okhttp", 6), ("junit", 7)]);
+        assert_eq!(
+            entries[0].packages,
+            vec![("io.ktor:ktor-client-core".to_string(), "2.3.12".to_string())]
+        );
+
+        assert!(matches!(
+            FailOn::Updates.failure(&entries),
+            Some(GvcError::UpdatesAvailable(1))
+        ));
+        assert!(FailOn::Major.failure(&entries).is_none());
+        assert!(FailOn::Security.failure(&entries).is_none());
+        entries[2].advisories.push("GHSA-xxxx".into());
+        assert!(matches!(
+            FailOn::Security.failure(&entries),
+            Some(GvcError::VulnerableDependencies(1))
+        ));
+    }
+}
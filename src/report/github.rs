@@ -0,0 +1,76 @@
+use crate::report::entry::{EntryStatus, FailOn, ReportEntry};
+use std::path::Path;
+
+/// GitHub Actions workflow commands annotating outdated and vulnerable catalog entries
+///
+/// Updates become `::warning` (or `::notice` when they don't trip `fail_on`),
+/// known vulnerabilities `::error`, so they show up inline in pull request reviews.
+pub fn annotations(entries: &[ReportEntry], root: &Path, fail_on: Option<FailOn>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for entry in entries {
+        let properties = format!(
+            "file={},line={}",
+            escape_property(&entry.catalog_file(root)),
+            entry.line
+        );
+
+        if matches!(entry.status, EntryStatus::Outdated | EntryStatus::Updated) {
+            let command = if fail_on.is_none_or(|gate| gate.trips(entry)) {
+                "warning"
+            } else {
+                "notice"
+            };
+            lines.push(format!(
+                "::{} {},title=Outdated dependency::{}",
+                command,
+                properties,
+                escape_data(&entry.summary())
+            ));
+        }
+        if !entry.advisories.is_empty() {
+            lines.push(format!(
+                "::error {},title=Vulnerable dependency::{}",
+                properties,
+                escape_data(&format!(
+                    "{} {} has known vulnerabilities: {}",
+                    entry.alias,
+                    entry.current.as_deref().unwrap_or("?"),
+                    entry.advisories.join(", ")
+                ))
+            ));
+        }
+    }
+    lines
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::collect_entries;
+    use crate::report::entry::tests::sample;
+
+    #[test]
+    fn annotates_outdated_entries_at_their_line() {
+        let (dir, catalog, report) = sample();
+        let entries = collect_entries(None, &catalog, &report, false).unwrap();
+
+        assert_eq!(
+            annotations(&entries, dir.path(), None),
+            vec![
+                "::warning file=libs.versions.toml,line=6,title=Outdated dependency::okhttp 4.11.0 → 4.12.0 (minor)"
+            ]
+        );
+        assert!(annotations(&entries, dir.path(), Some(FailOn::Major))[0].starts_with("::notice"));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::agents::update::StabilityPolicy;
+use crate::agents::update::context::Section;
+use crate::error::Result;
+use crate::gradle::CatalogFile;
+use crate::report::entry::{ReportEntry, describe};
+use crate::utils::toml::TomlUtils;
+use serde::Serialize;
+use toml_edit::DocumentMut;
+
+/// Version of the JSON layout, raised whenever a field changes meaning or disappears
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Report printed by `gvc check`, `gvc update` and `gvc list` with `--format json`
+#[derive(Debug, Serialize)]
+pub struct JsonReport {
+    schema_version: u32,
+    command: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy: Option<&'static str>,
+    entries: Vec<JsonEntry>,
+    diagnostics: Vec<String>,
+}
+
+/// One catalog entry
+///
+/// `check` and `update` fill the lookup fields; `list` only the catalog ones.
+#[derive(Debug, Serialize)]
+struct JsonEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    build: Option<String>,
+    catalog: String,
+    section: &'static str,
+    alias: String,
+    coordinate: Option<String>,
+    current: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_ref: Option<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    lookup: Option<JsonLookup>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonLookup {
+    /// `outdated`, `updated`, `up-to-date` or `skipped`
+    status: &'static str,
+    latest: Option<String>,
+    candidates: Vec<String>,
+    category: Option<&'static str>,
+    repository: Option<String>,
+    /// `stable` or `prerelease`, for `latest`
+    stability: Option<&'static str>,
+    diagnostics: Vec<String>,
+    advisories: Vec<String>,
+}
+
+impl JsonReport {
+    pub fn new(command: &'static str, policy: Option<StabilityPolicy>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            command,
+            policy: policy.map(policy_name),
+            entries: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Add the entries of a check or update
+    pub fn add_entries(&mut self, entries: &[ReportEntry]) {
+        for entry in entries {
+            self.entries.push(JsonEntry {
+                build: entry.build.clone(),
+                catalog: entry.catalog.clone(),
+                section: entry.section.as_str(),
+                alias: entry.alias.clone(),
+                coordinate: entry.coordinate.clone(),
+                current: entry.current.clone(),
+                version_ref: None,
+                lookup: Some(JsonLookup {
+                    status: entry.status.as_str(),
+                    latest: entry.latest.clone(),
+                    candidates: entry.candidates.clone(),
+                    category: entry.category.map(|category| category.as_str()),
+                    repository: entry.repository.clone(),
+                    stability: entry
+                        .latest_is_stable()
+                        .map(|stable| if stable { "stable" } else { "prerelease" }),
+                    diagnostics: entry.diagnostics.clone(),
+                    advisories: entry.advisories.clone(),
+                }),
+            });
+        }
+    }
+
+    /// Add every entry of a catalog, as `gvc list` shows them
+    pub fn add_listing(&mut self, build: Option<&str>, catalog: &CatalogFile, doc: &DocumentMut) {
+        for section in [Section::Versions, Section::Libraries, Section::Plugins] {
+            let Some(table) = doc.get(section.as_str()).and_then(|v| v.as_table()) else {
+                continue;
+            };
+            for (alias, item) in table.iter() {
+                let (coordinate, current) = describe(doc, section, alias);
+                self.entries.push(JsonEntry {
+                    build: build.map(str::to_string),
+                    catalog: catalog.name.clone(),
+                    section: section.as_str(),
+                    alias: alias.to_string(),
+                    coordinate,
+                    current,
+                    version_ref: TomlUtils::extract_version_ref(item),
+                    lookup: None,
+                });
+            }
+        }
+    }
+
+    /// Record a problem that isn't tied to one entry
+    pub fn add_diagnostic(&mut self, message: impl Into<String>) {
+        self.diagnostics.push(message.into());
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn policy_name(policy: StabilityPolicy) -> &'static str {
+    match policy {
+        StabilityPolicy::StableOnly => "stable-only",
+        StabilityPolicy::IncludeUnstable => "include-unstable",
+        StabilityPolicy::TrackChannel => "track-channel",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::collect_entries;
+    use crate::report::entry::tests::sample;
+
+    #[test]
+    fn reports_lookups_updates_and_skipped_entries() {
+        let (_dir, catalog, report) = sample();
+
+        let mut json = JsonReport::new("check", Some(StabilityPolicy::StableOnly));
+        json.add_entries(&collect_entries(None, &catalog, &report, false).unwrap());
+        let value: serde_json::Value = serde_json::from_str(&json.to_json().unwrap()).unwrap();
+
+        assert_eq!(value["schema_version"], 1);
+        assert_eq!(value["policy"], "stable-only");
+        let entries = value["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0]["section"], "versions");
+        assert_eq!(entries[0]["status"], "skipped");
+        assert_eq!(entries[0]["coordinate"], "io.ktor:ktor-client-core");
+        assert_eq!(entries[0]["current"], "2.3.12");
+        assert_eq!(entries[0]["diagnostics"][0], "pinned (# gvc:pin)");
+
+        assert_eq!(entries[1]["alias"], "okhttp");
+        assert_eq!(entries[1]["status"], "outdated");
+        assert_eq!(entries[1]["latest"], "4.12.0");
+        assert_eq!(entries[1]["category"], "minor");
+        assert_eq!(entries[1]["stability"], "stable");
+        assert_eq!(entries[1]["repository"], "Maven Central");
+        assert_eq!(entries[1]["candidates"][1], "5.0.0-alpha.14");
+
+        assert_eq!(entries[2]["status"], "up-to-date");
+        assert!(entries[2]["category"].is_null());
+    }
+}
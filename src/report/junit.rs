@@ -0,0 +1,110 @@
+use crate::report::entry::{EntryStatus, FailOn, ReportEntry};
+use quick_xml::escape::escape;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Render a JUnit XML report with one test case per dependency
+///
+/// Each catalog becomes a test suite. Entries tripping `fail_on` (any update when
+/// unset) fail, skipped entries are reported as skipped and the rest pass.
+pub fn render(entries: &[ReportEntry], root: &Path, fail_on: Option<FailOn>) -> String {
+    let gate = fail_on.unwrap_or(FailOn::Updates);
+    let fails = |entry: &ReportEntry| gate.trips(entry);
+    let skipped = |entry: &ReportEntry| entry.status == EntryStatus::Skipped;
+
+    let mut suites: Vec<(String, Vec<&ReportEntry>)> = Vec::new();
+    for entry in entries {
+        let name = match &entry.build {
+            Some(build) => format!("{}/{}", build, entry.catalog),
+            None => entry.catalog.clone(),
+        };
+        match suites.iter_mut().find(|(suite, _)| *suite == name) {
+            Some((_, members)) => members.push(entry),
+            None => suites.push((name, vec![entry])),
+        }
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"gvc\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        entries.len(),
+        entries.iter().filter(|entry| fails(entry)).count(),
+        entries.iter().filter(|entry| skipped(entry)).count()
+    );
+    for (name, members) in suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+            escape(name.as_str()),
+            members.len(),
+            members.iter().filter(|entry| fails(entry)).count(),
+            members.iter().filter(|entry| skipped(entry)).count()
+        );
+        for entry in members {
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}.{}\" name=\"{}\" file=\"{}\" line=\"{}\"",
+                escape(entry.catalog.as_str()),
+                entry.section.as_str(),
+                escape(entry.alias.as_str()),
+                escape(entry.catalog_file(root)),
+                entry.line
+            );
+            if fails(entry) {
+                let (kind, detail) = if entry.advisories.is_empty() {
+                    (
+                        entry
+                            .category
+                            .map_or("update", |category| category.as_str()),
+                        format!("Newer versions: {}", entry.candidates.join(", ")),
+                    )
+                } else {
+                    ("advisory", entry.advisories.join(", "))
+                };
+                let _ = writeln!(
+                    xml,
+                    ">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>",
+                    kind,
+                    escape(entry.summary()),
+                    escape(detail)
+                );
+            } else if skipped(entry) {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                    escape(entry.diagnostics.join("; "))
+                );
+            } else {
+                xml.push_str("/>\n");
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::collect_entries;
+    use crate::report::entry::tests::sample;
+
+    #[test]
+    fn writes_one_test_case_per_dependency() {
+        let (dir, catalog, report) = sample();
+        let entries = collect_entries(None, &catalog, &report, false).unwrap();
+
+        let xml = render(&entries, dir.path(), None);
+        assert!(xml.contains("<testsuites name=\"gvc\" tests=\"3\" failures=\"1\" skipped=\"1\">"));
+        assert!(xml.contains(
+            "<testcase classname=\"libs.libraries\" name=\"okhttp\" file=\"libs.versions.toml\" line=\"6\">\n      <failure type=\"minor\" message=\"okhttp 4.11.0 → 4.12.0 (minor)\">"
+        ));
+        assert!(xml.contains("name=\"junit\" file=\"libs.versions.toml\" line=\"7\"/>"));
+        assert!(xml.contains("<skipped message=\"pinned (# gvc:pin)\"/>"));
+
+        let major_only = render(&entries, dir.path(), Some(FailOn::Major));
+        assert!(major_only.contains("failures=\"0\""));
+    }
+}
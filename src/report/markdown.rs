@@ -0,0 +1,131 @@
+use crate::maven::ProjectLinks;
+use crate::maven::version::UpdateCategory;
+use crate::report::entry::{EntryStatus, ReportEntry};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Render a Markdown summary of the updated entries, for pull request and commit bodies
+///
+/// Updates are grouped into major, minor and patch sections, each a table of
+/// alias, coordinate, versions and bump type. `links` are keyed by coordinate.
+pub fn render(entries: &[ReportEntry], links: &HashMap<String, ProjectLinks>) -> String {
+    let updates: Vec<&ReportEntry> = entries
+        .iter()
+        .filter(|entry| matches!(entry.status, EntryStatus::Outdated | EntryStatus::Updated))
+        .filter(|entry| entry.latest.is_some())
+        .collect();
+
+    let mut markdown = String::from("## Dependency updates\n\n");
+    if updates.is_empty() {
+        markdown.push_str("No dependencies were updated.\n");
+        return markdown;
+    }
+
+    let count = |category: Option<UpdateCategory>| {
+        updates
+            .iter()
+            .filter(|entry| entry.category == category)
+            .count()
+    };
+    let _ = writeln!(
+        markdown,
+        "{} {} updated: {} major, {} minor, {} patch.",
+        updates.len(),
+        if updates.len() == 1 {
+            "dependency"
+        } else {
+            "dependencies"
+        },
+        count(Some(UpdateCategory::Major)),
+        count(Some(UpdateCategory::Minor)),
+        count(Some(UpdateCategory::Patch))
+    );
+
+    for (category, title) in [
+        (Some(UpdateCategory::Major), "Major updates"),
+        (Some(UpdateCategory::Minor), "Minor updates"),
+        (Some(UpdateCategory::Patch), "Patch updates"),
+        (None, "Other updates"),
+    ] {
+        let section: Vec<&&ReportEntry> = updates
+            .iter()
+            .filter(|entry| entry.category == category)
+            .collect();
+        if section.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(markdown, "\n### {}\n", title);
+        markdown.push_str("| Alias | Coordinate | Old | New | Bump | Links |\n");
+        markdown.push_str("|-------|------------|-----|-----|------|-------|\n");
+        for entry in section {
+            let alias = match &entry.build {
+                Some(build) => format!("{}/{}", build, entry.alias),
+                None => entry.alias.clone(),
+            };
+            let coordinate = entry.coordinate.as_deref().unwrap_or("");
+            let entry_links = links.get(coordinate).cloned().unwrap_or_default();
+            let coordinate = match &entry_links.project {
+                Some(url) => format!("[{}]({})", cell(coordinate), url),
+                None => cell(coordinate),
+            };
+            let _ = writeln!(
+                markdown,
+                "| `{}` | {} | {} | {} | {} | {} |",
+                cell(&alias),
+                coordinate,
+                cell(entry.current.as_deref().unwrap_or("")),
+                cell(entry.latest.as_deref().unwrap_or("")),
+                category.map_or("other", UpdateCategory::as_str),
+                link_list(&entry_links)
+            );
+        }
+    }
+
+    markdown
+}
+
+fn link_list(links: &ProjectLinks) -> String {
+    [
+        ("source", &links.source),
+        ("release notes", &links.releases),
+    ]
+    .into_iter()
+    .filter_map(|(label, url)| Some(format!("[{}]({})", label, url.as_ref()?)))
+    .collect::<Vec<_>>()
+    .join(" · ")
+}
+
+/// Keep table cells intact when values contain pipes
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::collect_entries;
+    use crate::report::entry::tests::sample;
+
+    #[test]
+    fn groups_updates_by_bump_type_with_links() {
+        let (_dir, catalog, report) = sample();
+        let entries = collect_entries(None, &catalog, &report, true).unwrap();
+        let links = HashMap::from([(
+            "com.squareup.okhttp3:okhttp".to_string(),
+            ProjectLinks {
+                project: Some("https://square.github.io/okhttp".into()),
+                source: Some("https://github.com/square/okhttp".into()),
+                releases: Some("https://github.com/square/okhttp/releases".into()),
+            },
+        )]);
+
+        let markdown = render(&entries, &links);
+        assert!(markdown.contains("1 dependency updated: 0 major, 1 minor, 0 patch."));
+        assert!(markdown.contains("### Minor updates"));
+        assert!(!markdown.contains("### Major updates"));
+        assert!(markdown.contains(
+            "| `okhttp` | [com.squareup.okhttp3:okhttp](https://square.github.io/okhttp) | 4.11.0 | 4.12.0 | minor | [source](https://github.com/square/okhttp) · [release notes](https://github.com/square/okhttp/releases) |"
+        ));
+    }
+}
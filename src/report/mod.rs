@@ -0,0 +1,18 @@
+// Report module - machine-readable output of check, list and update runs
+//
+// Human-readable output stays in the workflow printers; the writers here
+// render the same results for scripts and CI:
+// - ReportEntry: one catalog entry with its location, lookup and advisories
+// - JsonReport: versioned JSON document for `--format json`
+// - junit / sarif / github: CI reports and pull request annotations
+// - markdown: update summary for pull request and commit bodies
+// - FailOn: the `--fail-on` gate deciding the exit code of `gvc check`
+pub mod entry;
+pub mod github;
+pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod sarif;
+
+pub use entry::{FailOn, ReportEntry, collect_entries};
+pub use json::JsonReport;
@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::report::entry::{EntryStatus, FailOn, ReportEntry};
+use serde_json::{Value, json};
+use std::path::Path;
+
+const OUTDATED_RULE: &str = "outdated-dependency";
+const ADVISORY_RULE: &str = "vulnerable-dependency";
+
+/// Render a SARIF 2.1.0 log with a result per outdated or vulnerable entry
+///
+/// Results point at the entry's line in its catalog. Updates tripping `fail_on`
+/// are warnings and the rest notes; known vulnerabilities are errors.
+pub fn render(entries: &[ReportEntry], root: &Path, fail_on: Option<FailOn>) -> Result<String> {
+    let mut results = Vec::new();
+    for entry in entries {
+        let location = json!([{
+            "physicalLocation": {
+                "artifactLocation": { "uri": entry.catalog_file(root) },
+                "region": { "startLine": entry.line }
+            }
+        }]);
+
+        if matches!(entry.status, EntryStatus::Outdated | EntryStatus::Updated) {
+            let level = if fail_on.is_none_or(|gate| gate.trips(entry)) {
+                "warning"
+            } else {
+                "note"
+            };
+            results.push(json!({
+                "ruleId": OUTDATED_RULE,
+                "level": level,
+                "message": { "text": format!("{} is outdated: {}", entry.alias, entry.summary()) },
+                "locations": location,
+                "properties": {
+                    "coordinate": entry.coordinate,
+                    "candidates": entry.candidates,
+                    "repository": entry.repository
+                }
+            }));
+        }
+        if !entry.advisories.is_empty() {
+            results.push(json!({
+                "ruleId": ADVISORY_RULE,
+                "level": "error",
+                "message": {
+                    "text": format!(
+                        "{} {} has known vulnerabilities: {}",
+                        entry.alias,
+                        entry.current.as_deref().unwrap_or("?"),
+                        entry.advisories.join(", ")
+                    )
+                },
+                "locations": location
+            }));
+        }
+    }
+
+    let log: Value = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gvc",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": [
+                        {
+                            "id": OUTDATED_RULE,
+                            "shortDescription": { "text": "A newer version of the dependency is available" }
+                        },
+                        {
+                            "id": ADVISORY_RULE,
+                            "shortDescription": { "text": "The dependency version has known vulnerabilities" }
+                        }
+                    ]
+                }
+            },
+            "results": results
+        }]
+    });
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::collect_entries;
+    use crate::report::entry::tests::sample;
+
+    #[test]
+    fn locates_results_in_the_catalog() {
+        let (dir, catalog, report) = sample();
+        let entries = collect_entries(None, &catalog, &report, false).unwrap();
+
+        let log: Value =
+            serde_json::from_str(&render(&entries, dir.path(), None).unwrap()).unwrap();
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], OUTDATED_RULE);
+        assert_eq!(results[0]["level"], "warning");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "libs.versions.toml");
+        assert_eq!(location["region"]["startLine"], 6);
+    }
+}
@@ -28,14 +28,24 @@ impl Coordinate {
     }
 }
 
+/// Versions of a coordinate and the repository that published them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionListing {
+    pub versions: Vec<String>,
+    /// Name of the repository the versions were read from
+    pub repository: Option<String>,
+}
+
 pub trait RepositoryClient: Send + Sync {
     fn fetch_available_versions(&self, coordinate: &Coordinate) -> Result<Vec<String>>;
 
-    fn fetch_latest_version(
-        &self,
-        coordinate: &Coordinate,
-        stable_only: bool,
-    ) -> Result<Option<String>>;
+    /// Available versions along with the repository that answered
+    fn fetch_version_listing(&self, coordinate: &Coordinate) -> Result<VersionListing> {
+        Ok(VersionListing {
+            versions: self.fetch_available_versions(coordinate)?,
+            repository: None,
+        })
+    }
 }
 
 pub trait VersionStrategy: Send + Sync {
@@ -0,0 +1,190 @@
+use crate::error::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// Lines of unchanged context around each hunk
+const CONTEXT: usize = 3;
+
+/// Above this many line pairs the changed region is shown as a full replacement
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// A pending rewrite of a file, kept in memory until the user accepts it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FileEdit {
+    pub fn is_change(&self) -> bool {
+        self.original != self.updated
+    }
+
+    pub fn write(&self) -> Result<()> {
+        fs::write(&self.path, &self.updated)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Render a unified diff between two texts; empty when they are equal
+pub fn unified_diff(original: &str, updated: &str, old_label: &str, new_label: &str) -> String {
+    if original == updated {
+        return String::new();
+    }
+
+    let old: Vec<&str> = original.split_inclusive('\n').collect();
+    let new: Vec<&str> = updated.split_inclusive('\n').collect();
+    let ops = diff_lines(&old, &new);
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Equal)
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut i = 0;
+    while i < changes.len() {
+        let mut j = i;
+        while j + 1 < changes.len() && changes[j + 1] - changes[j] - 1 <= 2 * CONTEXT {
+            j += 1;
+        }
+
+        let start = changes[i].saturating_sub(CONTEXT);
+        let end = (changes[j] + 1 + CONTEXT).min(ops.len());
+        render_hunk(&ops, start, end, &mut out);
+        i = j + 1;
+    }
+
+    out
+}
+
+fn render_hunk(ops: &[(Op, &str)], start: usize, end: usize, out: &mut String) {
+    let before = &ops[..start];
+    let hunk = &ops[start..end];
+    let old_before = before.iter().filter(|(op, _)| *op != Op::Insert).count();
+    let new_before = before.iter().filter(|(op, _)| *op != Op::Delete).count();
+    let old_count = hunk.iter().filter(|(op, _)| *op != Op::Insert).count();
+    let new_count = hunk.iter().filter(|(op, _)| *op != Op::Delete).count();
+
+    out.push_str(&format!(
+        "@@ -{} +{} @@\n",
+        hunk_range(old_before, old_count),
+        hunk_range(new_before, new_count)
+    ));
+
+    for (op, line) in hunk {
+        out.push(match op {
+            Op::Equal => ' ',
+            Op::Delete => '-',
+            Op::Insert => '+',
+        });
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+fn hunk_range(before: usize, count: usize) -> String {
+    let start = if count == 0 { before } else { before + 1 };
+    if count == 1 {
+        start.to_string()
+    } else {
+        format!("{},{}", start, count)
+    }
+}
+
+/// Line operations turning `old` into `new`, via the longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|l| (Op::Equal, *l)).collect();
+
+    if old_mid.len() * new_mid.len() > MAX_DIFF_CELLS {
+        ops.extend(old_mid.iter().map(|l| (Op::Delete, *l)));
+        ops.extend(new_mid.iter().map(|l| (Op::Insert, *l)));
+    } else {
+        // lcs[i][j] = common subsequence length of old_mid[i..] and new_mid[j..]
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                ops.push((Op::Equal, old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                ops.push((Op::Delete, old_mid[i]));
+                i += 1;
+            } else {
+                ops.push((Op::Insert, new_mid[j]));
+                j += 1;
+            }
+        }
+        ops.extend(old_mid[i..].iter().map(|l| (Op::Delete, *l)));
+        ops.extend(new_mid[j..].iter().map(|l| (Op::Insert, *l)));
+    }
+
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Equal, *l)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_hunks_with_context() {
+        let original = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let updated = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+
+        let diff = unified_diff(original, updated, "a/x", "b/x");
+        assert_eq!(
+            diff,
+            "--- a/x\n+++ b/x\n@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n@@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+    }
+
+    #[test]
+    fn marks_missing_trailing_newline() {
+        let diff = unified_diff("one\ntwo", "one\nthree", "a/f", "b/f");
+        assert_eq!(
+            diff,
+            "--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n one\n-two\n\\ No newline at end of file\n+three\n\\ No newline at end of file\n"
+        );
+        assert!(unified_diff("same\n", "same\n", "a", "b").is_empty());
+    }
+}
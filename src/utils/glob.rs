@@ -0,0 +1,41 @@
+use crate::error::{GvcError, Result};
+use regex::Regex;
+
+/// Compile a case-insensitive glob (`*` and `?` wildcards) into an anchored regex
+pub fn compile_glob(pattern: &str) -> Result<Regex> {
+    let mut regex = String::from("(?i)^");
+    for ch in pattern.chars() {
+        match ch {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '.' | '+' | '(' | ')' | '|' | '^' | '$' | '{' | '}' | '[' | ']' | '\\' => {
+                regex.push('\\');
+                regex.push(ch);
+            }
+            _ => regex.push(ch),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex)
+        .map_err(|e| GvcError::ProjectValidation(format!("Invalid pattern '{}': {}", pattern, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_wildcards_case_insensitively() {
+        let glob = compile_glob("com.squareup.*:OkHttp*").unwrap();
+        assert!(glob.is_match("com.squareup.okhttp3:okhttp"));
+        assert!(!glob.is_match("comXsquareup.okhttp3:okhttp"));
+    }
+
+    #[test]
+    fn anchors_literal_patterns() {
+        let glob = compile_glob("kotlin").unwrap();
+        assert!(glob.is_match("Kotlin"));
+        assert!(!glob.is_match("kotlin-stdlib"));
+    }
+}
@@ -1,2 +1,5 @@
+pub mod diff;
+pub mod glob;
+pub mod output;
 pub mod path_validator;
 pub mod toml;
@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set while stdout is reserved for a machine-readable report
+static MACHINE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Send human-readable output to stderr, keeping stdout for a report such as JSON
+pub fn reserve_stdout() {
+    MACHINE_OUTPUT.store(true, Ordering::Relaxed);
+}
+
+/// Whether human-readable output currently goes to stderr
+pub fn stdout_reserved() -> bool {
+    MACHINE_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// `println!` for progress and status text: stderr while stdout carries a report
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::utils::output::stdout_reserved() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// `print!` counterpart of [`outln!`], for prompts
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if $crate::utils::output::stdout_reserved() {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
+pub(crate) use {out, outln};
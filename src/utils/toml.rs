@@ -1,5 +1,5 @@
 use crate::maven::parse_maven_coordinate;
-use toml_edit::{Item, Value};
+use toml_edit::{Array, Item, Value};
 
 /// Canonical representation of a library entry inside the version catalog.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -99,6 +99,34 @@ impl TomlUtils {
         None
     }
 
+    /// Points an existing version reference at another key, keeping its formatting.
+    pub fn set_version_ref(item: &mut Item, version_key: &str) -> bool {
+        let reference = match item {
+            Item::Value(Value::InlineTable(table)) => table
+                .get_mut("version")
+                .and_then(|version| version.as_inline_table_mut())
+                .and_then(|version| version.get_mut("ref")),
+            Item::Table(table) => match table.get_mut("version") {
+                Some(Item::Table(version)) => version
+                    .get_mut("ref")
+                    .and_then(|reference| reference.as_value_mut()),
+                Some(Item::Value(Value::InlineTable(version))) => version.get_mut("ref"),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match reference {
+            Some(reference) if reference.is_str() => {
+                let decor = reference.decor().clone();
+                *reference = Value::from(version_key);
+                *reference.decor_mut() = decor;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Updates an item's version to a concrete value, returning true if a change was applied.
     pub fn update_version(item: &mut Item, new_version: &str) -> bool {
         if item.as_str().is_some() {
@@ -149,6 +177,120 @@ impl TomlUtils {
             version_ref,
         })
     }
+
+    /// Extracts the plugin id from `"id:version"` strings, inline tables, or standard tables.
+    pub fn extract_plugin_id(item: &Item) -> Option<String> {
+        if let Some(raw) = item.as_str() {
+            return raw.split_once(':').map(|(id, _)| id.to_string());
+        }
+
+        item.as_table_like()
+            .and_then(|table| table.get("id"))
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+    }
+
+    /// Extracts the concrete version of a plugin entry, including the `"id:version"` notation.
+    pub fn extract_plugin_version(item: &Item) -> Option<String> {
+        match item.as_str() {
+            Some(raw) => raw.split_once(':').map(|(_, version)| version.to_string()),
+            None => Self::extract_version(item),
+        }
+    }
+
+    /// Sets the concrete version of a plugin entry in any notation, keeping comments.
+    /// Entries using `version.ref` are left alone.
+    pub fn set_plugin_version(item: &mut Item, new_version: &str) -> bool {
+        if let Some(Value::String(raw)) = item.as_value_mut() {
+            let Some((id, _)) = raw.value().split_once(':') else {
+                return false;
+            };
+            let decor = raw.decor().clone();
+            let mut updated = Value::from(format!("{}:{}", id, new_version));
+            *updated.decor_mut() = decor;
+            *item = Item::Value(updated);
+            return true;
+        }
+
+        if let Some(inline_table) = item.as_inline_table_mut() {
+            return match inline_table.get_mut("version") {
+                Some(version) if version.is_str() => {
+                    let decor = version.decor().clone();
+                    *version = Value::from(new_version);
+                    *version.decor_mut() = decor;
+                    true
+                }
+                _ => false,
+            };
+        }
+
+        if let Some(table) = item.as_table_mut() {
+            if table.get("version").is_some_and(|version| version.is_str()) {
+                table.insert("version", Item::Value(Value::from(new_version)));
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Normalizes an alias the way Gradle derives accessors: `-`, `_` and `.` are equivalent.
+    pub fn accessor_key(alias: &str) -> String {
+        alias
+            .chars()
+            .map(|c| match c {
+                '_' | '.' => '-',
+                other => other,
+            })
+            .collect()
+    }
+
+    /// Appends a value to an array, reusing the layout of the existing elements.
+    pub fn push_array_value(array: &mut Array, value: impl Into<Value>) {
+        let mut value = value.into();
+        let len = array.len();
+        if let Some(last) = array.get_mut(len.wrapping_sub(1)) {
+            let prefix = match last.decor().prefix().and_then(|p| p.as_str()) {
+                Some(prefix) if prefix.contains('\n') => prefix.to_string(),
+                _ => " ".to_string(),
+            };
+            // Whitespace after the last element (e.g. a newline before `]`) moves along
+            let suffix = last
+                .decor()
+                .suffix()
+                .and_then(|s| s.as_str())
+                .unwrap_or_default()
+                .to_string();
+            last.decor_mut().set_suffix("");
+            value.decor_mut().set_prefix(prefix);
+            value.decor_mut().set_suffix(suffix);
+        }
+        array.push_formatted(value);
+    }
+
+    /// Removes the element at `index`, keeping the surrounding layout intact.
+    pub fn remove_array_value(array: &mut Array, index: usize) -> Option<Value> {
+        if index >= array.len() {
+            return None;
+        }
+
+        let removed = array.remove(index);
+        let prefix = removed.decor().prefix().and_then(|p| p.as_str());
+        let suffix = removed.decor().suffix().and_then(|s| s.as_str());
+
+        if index == 0 {
+            if let (Some(first), Some(prefix)) = (array.get_mut(0), prefix) {
+                first.decor_mut().set_prefix(prefix.to_string());
+            }
+        }
+        if index == array.len() && index > 0 {
+            if let (Some(last), Some(suffix)) = (array.get_mut(index - 1), suffix) {
+                last.decor_mut().set_suffix(suffix.to_string());
+            }
+        }
+
+        Some(removed)
+    }
 }
 
 #[cfg(test)]
@@ -220,4 +362,64 @@ mod tests {
         assert!(details.version.is_none());
         assert_eq!(details.version_ref.as_deref(), Some("core"));
     }
+
+    #[test]
+    fn pushes_and_removes_array_values_keeping_layout() {
+        let mut doc: DocumentMut = "single = [\"a\"]\nmulti = [\n    \"a\",\n    \"b\"\n]\n"
+            .parse()
+            .unwrap();
+
+        TomlUtils::push_array_value(doc["single"].as_array_mut().unwrap(), "b");
+        TomlUtils::push_array_value(doc["multi"].as_array_mut().unwrap(), "c");
+        assert_eq!(
+            doc.to_string(),
+            "single = [\"a\", \"b\"]\nmulti = [\n    \"a\",\n    \"b\",\n    \"c\"\n]\n"
+        );
+
+        TomlUtils::remove_array_value(doc["single"].as_array_mut().unwrap(), 0);
+        TomlUtils::remove_array_value(doc["multi"].as_array_mut().unwrap(), 2);
+        assert_eq!(
+            doc.to_string(),
+            "single = [\"b\"]\nmulti = [\n    \"a\",\n    \"b\"\n]\n"
+        );
+    }
+
+    #[test]
+    fn updates_plugin_versions_in_every_notation() {
+        let doc: DocumentMut = r#"[plugins]
+short = "org.example.short:1.0" # keep
+inline = { id = "org.example.inline", version = "1.0" }
+shared = { id = "org.example.shared", version.ref = "shared" }
+"#
+        .parse()
+        .unwrap();
+        let mut plugins = doc["plugins"].as_table().unwrap().clone();
+
+        for key in ["short", "inline"] {
+            let item = plugins.get_mut(key).unwrap();
+            assert_eq!(
+                TomlUtils::extract_plugin_version(item).as_deref(),
+                Some("1.0")
+            );
+            assert!(TomlUtils::set_plugin_version(item, "2.0"));
+        }
+        let shared = plugins.get_mut("shared").unwrap();
+        assert_eq!(
+            TomlUtils::extract_plugin_id(shared).as_deref(),
+            Some("org.example.shared")
+        );
+        assert!(!TomlUtils::set_plugin_version(shared, "2.0"));
+
+        let rendered = plugins.to_string();
+        assert!(rendered.contains("short = \"org.example.short:2.0\" # keep"));
+        assert!(rendered.contains("inline = { id = \"org.example.inline\", version = \"2.0\" }"));
+    }
+
+    #[test]
+    fn accessor_key_treats_separators_alike() {
+        assert_eq!(
+            TomlUtils::accessor_key("androidx.core_ktx"),
+            "androidx-core-ktx"
+        );
+    }
 }
@@ -1,59 +1,86 @@
-use crate::agents::catalog_editor::{parse_library_coordinate, parse_plugin_coordinate};
+use crate::agents::catalog_editor::{
+    MigrationOutcome, parse_library_coordinate, parse_plugin_coordinate, read_bundles,
+};
+use crate::agents::catalog_linter::LintSeverity;
+use crate::agents::journal::{Journal, content_hash};
+use crate::agents::project_scanner::ProjectInfo;
+use crate::agents::update::context::Section;
+use crate::agents::update::markers::MarkerRules;
+use crate::agents::version_aligner::Misalignment;
+use crate::agents::version_control::{Bump, DirtyTree};
 use crate::agents::{
-    AddResult, AddTargetKind, CatalogEditor, DependencyUpdater, ProjectScannerAgent, UpdateReport,
-    VersionControlAgent,
+    AddResult, AddTargetKind, BundleInfo, CatalogEditor, CatalogFormatter, CatalogLinter,
+    CommitPlanner, DependencyMigrator, DependencyUpdater, LintIssue, ProjectScannerAgent,
+    StabilityPolicy, UpdateReport, UsageAnalyzer, VersionAligner, VersionControlAgent,
 };
+use crate::config::{GvcConfig, LoadedConfig, OutputFormat, RepositoriesConfig};
 use crate::error::{GvcError, Result};
-use crate::gradle::{GradleConfigParser, Repository};
+use crate::forge::{ForgeClient, PullRequest};
+use crate::gradle::{
+    BuildScriptScanner, CatalogFile, GradleBuild, GradleConfigParser, Repository, catalog_accessor,
+    discover_builds,
+};
+use crate::maven::{MavenRepository, OsvClient, ProjectLinks};
+use crate::report::entry::EntryStatus;
+use crate::report::{self, FailOn, JsonReport, ReportEntry, collect_entries};
 use crate::repository::{Coordinate, DefaultVersionStrategy, RepositoryFactory, VersionStrategy};
+use crate::utils::diff::{FileEdit, unified_diff};
+use crate::utils::output::{self, out, outln};
 use crate::utils::path_validator::PathValidator;
 use colored::Colorize;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use std::path::Path;
 
+/// Options for `gvc add`
+pub struct AddOptions<'a> {
+    pub alias: Option<&'a str>,
+    pub version_alias: Option<&'a str>,
+    pub bundle: Option<&'a str>,
+    pub stable_only: bool,
+    pub catalog: Option<&'a str>,
+}
+
 /// Add a new dependency or plugin entry to the version catalog
 pub fn execute_add<P: AsRef<Path>>(
     project_path: P,
     plugin_flag: bool,
-    _library_flag: bool,
     coordinate: &str,
-    alias_override: Option<&str>,
-    version_alias_override: Option<&str>,
-    stable_only: bool,
+    options: AddOptions<'_>,
+    config: &GvcConfig,
 ) -> Result<()> {
+    let AddOptions {
+        alias: alias_override,
+        version_alias: version_alias_override,
+        bundle,
+        stable_only,
+        catalog,
+    } = options;
     let project_path = PathValidator::validate_project_path(project_path)?;
-    println!(
+    outln!(
         "{}",
         "Adding entry to Gradle version catalog...".cyan().bold()
     );
 
-    println!("\n{}", "1. Validating project structure...".yellow());
+    outln!("\n{}", "1. Validating project structure...".yellow());
     let scanner = ProjectScannerAgent::new(&project_path);
     let project_info = scanner.validate()?;
-    println!("{}", "✓ Project structure is valid".green());
+    let catalog = project_info.catalog(catalog)?;
+    outln!("{}", "✓ Project structure is valid".green());
 
     let (target, coordinate) = resolve_add_target(plugin_flag, coordinate)?;
 
-    println!(
+    outln!(
         "\n{}",
         "2. Reading Gradle repository configuration...".yellow()
     );
-    let gradle_parser = GradleConfigParser::new(&project_path);
-    let gradle_config = gradle_parser.parse()?;
-    println!(
-        "   Found {} repositories:",
-        gradle_config.repositories.len()
-    );
-    for repo in &gradle_config.repositories {
-        println!("   • {} ({})", repo.name.bright_cyan(), repo.url.dimmed());
-    }
+    let repositories = read_repositories(&project_path, &config.repositories)?;
 
-    println!(
+    outln!(
         "\n{}",
         "3. Validating coordinate against remote repositories...".yellow()
     );
 
-    let repositories = gradle_config.repositories.clone();
-
     let resolved_coordinate = match target {
         AddTargetKind::Library => {
             let (group, artifact, version) = parse_library_coordinate(coordinate)?;
@@ -73,8 +100,11 @@ pub fn execute_add<P: AsRef<Path>>(
         }
     };
 
-    println!("\n{}", "4. Writing to version catalog...".yellow());
-    let editor = CatalogEditor::new(&project_info.toml_path);
+    outln!(
+        "\n{}",
+        format!("4. Writing to version catalog '{}'...", catalog.name).yellow()
+    );
+    let editor = CatalogEditor::new(&catalog.path);
 
     let result = match target {
         AddTargetKind::Library => {
@@ -87,7 +117,17 @@ pub fn execute_add<P: AsRef<Path>>(
 
     print_add_result(&result);
 
-    println!("\n{}", "✨ Entry added successfully!".green().bold());
+    if let Some(bundle) = bundle {
+        let added = editor.add_to_bundle(bundle, std::slice::from_ref(&result.alias))?;
+        if !added.is_empty() {
+            outln!(
+                "{}",
+                format!("✓ Added '{}' to bundle '{}'", result.alias, bundle).green()
+            );
+        }
+    }
+
+    outln!("\n{}", "✨ Entry added successfully!".green().bold());
 
     Ok(())
 }
@@ -111,7 +151,7 @@ fn resolve_add_target(plugin_flag: bool, coordinate: &str) -> Result<(AddTargetK
 fn print_add_result(result: &AddResult) {
     match result.target {
         AddTargetKind::Library => {
-            println!(
+            outln!(
                 "{}",
                 format!(
                     "✓ Library '{}' added with version alias '{}'",
@@ -121,7 +161,7 @@ fn print_add_result(result: &AddResult) {
             );
         }
         AddTargetKind::Plugin => {
-            println!(
+            outln!(
                 "{}",
                 format!(
                     "✓ Plugin '{}' added with version alias '{}'",
@@ -177,7 +217,7 @@ fn resolve_version_for_library(
         )));
     };
 
-    println!(
+    outln!(
         "   {}",
         format!("✓ {group}:{artifact} @ {target_version}").green()
     );
@@ -202,226 +242,1735 @@ fn resolve_version_for_plugin(
         )));
     }
 
-    let target_version = if version.eq_ignore_ascii_case("latest") {
-        match strategy.select_latest(&available_versions, stable_only) {
-            Some(v) => v,
-            None => {
-                if stable_only {
-                    return Err(GvcError::ProjectValidation(format!(
-                        "No stable versions available for plugin '{}'. Re-run with --no-stable-only to include pre-releases.",
-                        plugin_id
-                    )));
+    let target_version = if version.eq_ignore_ascii_case("latest") {
+        match strategy.select_latest(&available_versions, stable_only) {
+            Some(v) => v,
+            None => {
+                if stable_only {
+                    return Err(GvcError::ProjectValidation(format!(
+                        "No stable versions available for plugin '{}'. Re-run with --no-stable-only to include pre-releases.",
+                        plugin_id
+                    )));
+                }
+                return Err(GvcError::ProjectValidation(format!(
+                    "No versions available for plugin '{}'",
+                    plugin_id
+                )));
+            }
+        }
+    } else if available_versions.iter().any(|v| v == &version) {
+        version
+    } else {
+        return Err(GvcError::ProjectValidation(format!(
+            "Version '{}' for plugin '{}' not found on Gradle Plugin Portal",
+            version, plugin_id
+        )));
+    };
+
+    outln!(
+        "   {}",
+        format!("✓ plugin {plugin_id} @ {target_version}").green()
+    );
+
+    Ok(target_version)
+}
+
+/// Which builds and catalogs `check` and `update` work on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildScope<'a> {
+    /// Only this catalog (by name) in every build
+    pub catalog: Option<&'a str>,
+    /// Every Gradle build under the project path instead of the project alone
+    pub all_builds: bool,
+    /// Keep dependencies shared between builds on one version
+    pub align: bool,
+}
+
+/// `gvc update --dry-run`: show the catalog edits as a diff instead of writing them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DryRun<'a> {
+    pub enabled: bool,
+    /// Also save the diff here, as a patch applicable with `git apply`
+    pub output_patch: Option<&'a Path>,
+}
+
+/// Execute the update workflow
+pub fn execute_update<P: AsRef<Path>>(
+    project_path: P,
+    config: &GvcConfig,
+    filter: Option<String>,
+    scope: BuildScope<'_>,
+    dry_run: DryRun<'_>,
+    summary_md: Option<&Path>,
+) -> Result<()> {
+    let project_path = PathValidator::validate_project_path(project_path)?;
+    let summarize = summary_md.is_some() || config.git.summary_body || config.forge.open_pr;
+    let policy = config.update.stability;
+    let no_git = !config.git.enabled || dry_run.enabled;
+    let mut json = json_report(config.output.format, "update", Some(policy));
+    if dry_run.enabled {
+        outln!(
+            "{}",
+            "Starting dependency update process (dry run)..."
+                .cyan()
+                .bold()
+        );
+    } else {
+        outln!("{}", "Starting dependency update process...".cyan().bold());
+    }
+
+    // Step 1: Validate project structure
+    outln!("\n{}", "1. Validating project structure...".yellow());
+    let scanner = ProjectScannerAgent::new(&project_path);
+    let mut project_info = scanner.validate()?;
+    let mut builds = select_builds(&project_path, &project_info, scope)?;
+    outln!("{}", "✓ Project structure is valid".green());
+
+    // Step 2: Check Git status (if Git is available and not disabled)
+    let mut forge = None;
+    // Restores the stash or removes the worktree when the update returns
+    let mut _isolated = None;
+    let mut project_path = project_path;
+    if project_info.has_git && !no_git {
+        outln!("\n{}", "2. Checking Git status...".yellow());
+        let git_agent = VersionControlAgent::new(&project_path)?;
+
+        if config.forge.open_pr {
+            let remote_url = git_agent.remote_url(&config.forge.remote)?;
+            let client = ForgeClient::from_config(&config.forge, &remote_url)?;
+            let base = match &config.forge.base_branch {
+                Some(base) => base.clone(),
+                None => git_agent.current_branch()?.ok_or_else(|| {
+                    GvcError::Config(
+                        "HEAD is detached; set forge.base_branch for the pull request".to_string(),
+                    )
+                })?,
+            };
+            forge = Some((client, base));
+        }
+
+        if git_agent.is_working_directory_clean()? {
+            outln!("{}", "✓ Working directory is clean".green());
+        } else {
+            ensure_catalogs_committed(&git_agent, &builds)?;
+            match config.git.dirty_tree {
+                DirtyTree::Refuse => {
+                    outln!(
+                        "{}",
+                        "⚠ Warning: Working directory has uncommitted changes".red()
+                    );
+                    outln!(
+                        "Please commit or stash your changes before proceeding, or rerun with --stash or --worktree."
+                    );
+                    return Ok(());
+                }
+                DirtyTree::Stash => {
+                    let stash = git_agent.stash_changes()?;
+                    if stash.is_some() {
+                        outln!(
+                            "{}",
+                            "✓ Stashed uncommitted changes; they are restored after the update"
+                                .green()
+                        );
+                    } else {
+                        outln!(
+                            "{}",
+                            "⚠ Git had nothing to stash; updating with the working tree as it is"
+                                .yellow()
+                        );
+                    }
+                    _isolated = stash;
+                }
+                DirtyTree::Worktree => {
+                    let tree = git_agent.add_worktree()?;
+                    if let Some(worktree) = tree.worktree() {
+                        outln!(
+                            "{}",
+                            format!(
+                                "✓ Updating in a temporary worktree at {}",
+                                worktree.display()
+                            )
+                            .green()
+                        );
+                        project_path = PathValidator::validate_project_path(worktree)?;
+                        project_info = ProjectScannerAgent::new(&project_path).validate()?;
+                        builds = select_builds(&project_path, &project_info, scope)?;
+                    }
+                    _isolated = Some(tree);
+                }
+            }
+        }
+    } else if dry_run.enabled {
+        outln!("\n{}", "2. Dry run, skipping Git checks".yellow());
+    } else if !no_git {
+        outln!(
+            "\n{}",
+            "2. Git repository not detected, skipping Git checks".yellow()
+        );
+    }
+
+    // Step 3: Read Gradle repository configuration, per build
+    outln!(
+        "\n{}",
+        "3. Reading Gradle repository configuration...".yellow()
+    );
+    let mut build_repositories = Vec::new();
+    for build in &builds {
+        if builds.len() > 1 {
+            print_build_header(&project_path, build);
+        }
+        build_repositories.push(read_repositories(&build.path, &config.repositories)?);
+    }
+
+    // Step 4: Update dependencies
+    outln!("\n{}", "4. Updating dependencies...".yellow());
+    let inputs = UpdateInputs {
+        config,
+        filter: filter.as_deref(),
+        scope,
+        summarize,
+        dry_run: dry_run.enabled,
+    };
+    let Some(mut plan) = plan_update(&project_path, &builds, &build_repositories, &inputs)? else {
+        return Ok(());
+    };
+
+    // Step 5: Switch to the update branch before writing, as a reused one may differ
+    let mut branch_name = None;
+    if project_info.has_git && !no_git && plan.is_change() {
+        outln!("\n{}", "5. Creating Git commit...".yellow());
+        let git_agent = VersionControlAgent::new(&project_path)?
+            .with_config(&config.git)
+            .with_bumps(plan.bumps());
+        let original_branch = git_agent.current_branch()?;
+        let branch = git_agent.create_update_branch()?;
+        if plan.is_stale() {
+            outln!(
+                "{}",
+                format!(
+                    "Branch {} already changes the catalogs; updating them as they are there",
+                    branch
+                )
+                .yellow()
+            );
+            match plan_update(&project_path, &builds, &build_repositories, &inputs)? {
+                Some(replanned) => plan = replanned,
+                None => {
+                    if let Some(original) = &original_branch {
+                        git_agent.checkout(original)?;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        if plan.is_change() {
+            branch_name = Some(branch);
+        } else {
+            outln!(
+                "{}",
+                format!("✓ Branch {} already has these updates", branch).green()
+            );
+            if let Some(original) = &original_branch {
+                git_agent.checkout(original)?;
+            }
+        }
+    }
+    if !dry_run.enabled {
+        for edit in &plan.edits {
+            edit.write()?;
+        }
+    }
+
+    let changed = plan.is_change();
+    let UpdatePlan {
+        reports,
+        edits,
+        entries,
+        links,
+        misalignments,
+        ..
+    } = plan;
+    if let Some(json) = json.as_mut() {
+        for misalignment in &misalignments {
+            json.add_diagnostic(misalignment_message(misalignment));
+        }
+    }
+
+    let summary = summarize.then(|| report::markdown::render(&entries, &links));
+    if let (Some(path), Some(summary)) = (summary_md, &summary) {
+        write_summary(path, summary)?;
+    }
+
+    if dry_run.enabled {
+        print_dry_run(&project_path, &edits, dry_run.output_patch)?;
+    }
+
+    // Step 6: Git operations (if enabled)
+    if let Some(branch_name) = branch_name {
+        let git_agent = VersionControlAgent::new(&project_path)?
+            .with_config(&config.git)
+            .with_bumps(update_bumps(&reports))
+            .with_commit_body(summary.clone().filter(|_| config.git.summary_body));
+        let root = project_path
+            .canonicalize()
+            .unwrap_or_else(|_| project_path.clone());
+        let (paths, outside): (Vec<_>, Vec<_>) = builds
+            .iter()
+            .flat_map(|build| &build.catalogs)
+            .map(|catalog| catalog.path.clone())
+            .partition(|path| path.starts_with(&root) || path.is_relative());
+        for path in outside {
+            outln!(
+                "{}",
+                format!(
+                    "⚠ {} is outside this repository and was not committed",
+                    path.display()
+                )
+                .yellow()
+            );
+        }
+        let inside: Vec<FileEdit> = edits
+            .iter()
+            .filter(|edit| paths.contains(&edit.path))
+            .cloned()
+            .collect();
+        let steps = CommitPlanner::from_config(&config.git)?.plan(&entries, &inside)?;
+        if steps.is_empty() {
+            git_agent.stage_catalogs(&paths)?;
+            git_agent.commit_updates()?;
+        } else {
+            git_agent.commit_steps(&steps)?;
+        }
+        let commits = steps.len().max(1);
+        outln!(
+            "{}",
+            format!(
+                "✓ Changes committed to branch: {}{}",
+                branch_name,
+                if commits > 1 {
+                    format!(" ({} commits)", commits)
+                } else {
+                    String::new()
+                }
+            )
+            .green()
+        );
+
+        if config.forge.push || config.forge.open_pr {
+            git_agent.push(&config.forge.remote, &branch_name)?;
+            outln!(
+                "{}",
+                format!("✓ Pushed {} to {}", branch_name, config.forge.remote).green()
+            );
+        }
+        if let Some((client, base)) = forge {
+            let url = client.open(&PullRequest {
+                title: git_agent.pull_request_title(),
+                body: summary.unwrap_or_default(),
+                head: branch_name,
+                base,
+            })?;
+            outln!("{}", format!("✓ Opened pull request: {}", url).green());
+        }
+    } else if !changed && !dry_run.enabled {
+        outln!("\n{}", "No updates were applied".yellow());
+    }
+    if dry_run.enabled {
+        outln!(
+            "\n{}",
+            "✨ Dry run completed; no files were changed".green().bold()
+        );
+        if let Some(mut json) = json {
+            json.add_entries(&entries);
+            println!("{}", json.to_json()?);
+        }
+        return Ok(());
+    }
+
+    outln!(
+        "\n{}",
+        "✨ Update process completed successfully!".green().bold()
+    );
+    if let Some(mut json) = json {
+        json.add_entries(&entries);
+        println!("{}", json.to_json()?);
+    }
+    Ok(())
+}
+
+/// Settings of an update that stay the same when it is planned again on a reused branch
+struct UpdateInputs<'a> {
+    config: &'a GvcConfig,
+    filter: Option<&'a str>,
+    scope: BuildScope<'a>,
+    summarize: bool,
+    dry_run: bool,
+}
+
+/// Catalog edits and reports of an update, worked out without writing anything
+struct UpdatePlan {
+    reports: Vec<UpdateReport>,
+    edits: Vec<FileEdit>,
+    entries: Vec<ReportEntry>,
+    links: HashMap<String, ProjectLinks>,
+    misalignments: Vec<Misalignment>,
+    aligned: usize,
+}
+
+impl UpdatePlan {
+    fn is_change(&self) -> bool {
+        self.aligned > 0 || self.reports.iter().any(|report| !report.is_empty())
+    }
+
+    /// Whether a catalog no longer holds the content the edits were planned on
+    fn is_stale(&self) -> bool {
+        self.edits.iter().any(|edit| {
+            std::fs::read_to_string(&edit.path).ok().as_deref() != Some(edit.original.as_str())
+        })
+    }
+
+    fn bumps(&self) -> Vec<Bump> {
+        update_bumps(&self.reports)
+    }
+}
+
+/// Every version change in `reports`, for branch names and commit messages
+fn update_bumps(reports: &[UpdateReport]) -> Vec<Bump> {
+    reports
+        .iter()
+        .flat_map(|report| report.entries())
+        .filter_map(|entry| {
+            Some(Bump {
+                name: entry.name.clone(),
+                old: entry.current.clone().unwrap_or_default(),
+                new: entry.update.clone()?,
+            })
+        })
+        .collect()
+}
+
+/// Work out the updates of every catalog and print them; `None` when the user cancels
+fn plan_update(
+    project_path: &Path,
+    builds: &[GradleBuild],
+    build_repositories: &[Vec<Repository>],
+    inputs: &UpdateInputs<'_>,
+) -> Result<Option<UpdatePlan>> {
+    let config = inputs.config;
+    let policy = config.update.stability;
+    let interactive = config.update.interactive;
+    let mut reports = Vec::new();
+    let mut edits: Vec<FileEdit> = Vec::new();
+    let mut entries = Vec::new();
+    let mut links = HashMap::new();
+    for (build, repositories) in builds.iter().zip(build_repositories) {
+        if builds.len() > 1 {
+            print_build_header(project_path, build);
+        }
+        let maven = if inputs.summarize {
+            Some(MavenRepository::with_repositories(repositories.clone())?)
+        } else {
+            None
+        };
+        let updater = DependencyUpdater::with_repositories(repositories.clone())?
+            .with_rules(MarkerRules::from_config(&config.rules)?);
+
+        let mut report = UpdateReport::new();
+        for catalog in &build.catalogs {
+            if build.catalogs.len() > 1 {
+                print_catalog_header(&build.path, catalog);
+            }
+            let result = match inputs.filter {
+                Some(pattern) => {
+                    updater.update_targeted_dependency(&catalog.path, policy, interactive, pattern)
+                }
+                None => updater.update_version_catalog(&catalog.path, policy, interactive),
+            };
+            let update = match result {
+                Ok(update) => update,
+                Err(GvcError::UserCancelled) => {
+                    outln!("\n{}", "Update cancelled by user.".yellow());
+                    return Ok(None);
+                }
+                Err(e) => return Err(e),
+            };
+            let catalog_report = update.report;
+            if !catalog_report.is_empty() && update.edit.is_change() {
+                edits.push(update.edit);
+            }
+            let label = build_label(builds, build);
+            let catalog_entries =
+                collect_entries(label, catalog, &catalog_report, !inputs.dry_run)?;
+            if let Some(maven) = &maven {
+                fetch_project_links(maven, &catalog_entries, &mut links);
+            }
+            entries.extend(catalog_entries);
+            if build.catalogs.len() > 1 {
+                report.merge(catalog_report.prefixed(&catalog.name));
+            } else {
+                report.merge(catalog_report);
+            }
+        }
+        reports.push(report);
+    }
+
+    outln!("{}", "✓ Update completed".green());
+
+    for (build, report) in builds.iter().zip(&reports) {
+        if builds.len() > 1 {
+            print_build_header(project_path, build);
+        }
+        print_update_report(report);
+    }
+
+    let mut aligned = 0;
+    let mut misalignments = Vec::new();
+    if inputs.scope.align {
+        outln!(
+            "\n{}",
+            "Aligning shared dependencies across builds...".yellow()
+        );
+        let aligner = VersionAligner::new(builds)
+            .with_pending(&edits)
+            .with_rules(MarkerRules::from_config(&config.rules)?)
+            .with_policy(policy);
+        misalignments = aligner.find_misalignments()?;
+        print_misalignments(&misalignments);
+        let (count, alignment_edits) = aligner.plan_alignment(&misalignments)?;
+        aligned = count;
+        for edit in alignment_edits.into_iter().filter(FileEdit::is_change) {
+            match edits.iter_mut().find(|pending| pending.path == edit.path) {
+                Some(pending) => pending.updated = edit.updated,
+                None => edits.push(edit),
+            }
+        }
+        if aligned > 0 {
+            outln!(
+                "{}",
+                format!("✓ Aligned {} {}", aligned, entries_label(aligned)).green()
+            );
+        }
+    }
+
+    Ok(Some(UpdatePlan {
+        reports,
+        edits,
+        entries,
+        links,
+        misalignments,
+        aligned,
+    }))
+}
+
+/// Look up project links of updated libraries in their POMs, once per coordinate
+fn fetch_project_links(
+    maven: &MavenRepository,
+    entries: &[ReportEntry],
+    links: &mut HashMap<String, ProjectLinks>,
+) {
+    for entry in entries {
+        if entry.section == Section::Plugins
+            || !matches!(entry.status, EntryStatus::Outdated | EntryStatus::Updated)
+        {
+            continue;
+        }
+        let (Some(coordinate), Some(version)) = (&entry.coordinate, &entry.latest) else {
+            continue;
+        };
+        // Plugin ids (of plugin-only version aliases) have no POM coordinate
+        let Some((group, artifact)) = coordinate.split_once(':') else {
+            continue;
+        };
+        if !links.contains_key(coordinate) {
+            let found = maven.fetch_project_links(group, artifact, version);
+            links.insert(coordinate.clone(), found);
+        }
+    }
+}
+
+/// Write the Markdown summary to a file, or print it when the path is `-`
+fn write_summary(path: &Path, summary: &str) -> Result<()> {
+    if path == Path::new("-") {
+        outln!("\n{}", summary);
+        return Ok(());
+    }
+    std::fs::write(path, summary)?;
+    outln!(
+        "{}",
+        format!("✓ Update summary written to {}", path.display()).green()
+    );
+    Ok(())
+}
+
+/// Fail when a catalog the update would touch has uncommitted changes
+fn ensure_catalogs_committed(
+    git_agent: &VersionControlAgent,
+    builds: &[GradleBuild],
+) -> Result<()> {
+    let dirty = git_agent.dirty_paths()?;
+    for catalog in builds.iter().flat_map(|build| &build.catalogs) {
+        let path = catalog
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| catalog.path.clone());
+        if dirty.contains(&path) {
+            return Err(GvcError::GitOperation(format!(
+                "{} has uncommitted changes; commit or stash them before updating it",
+                catalog.path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The builds to work on: the project alone, or every build under it with `--all-builds`
+fn select_builds(
+    project_path: &Path,
+    project_info: &ProjectInfo,
+    scope: BuildScope<'_>,
+) -> Result<Vec<GradleBuild>> {
+    if !scope.all_builds {
+        let name = project_path
+            .canonicalize()
+            .ok()
+            .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| project_path.display().to_string());
+        return Ok(vec![GradleBuild {
+            name,
+            path: project_path.to_path_buf(),
+            catalogs: project_info.select_catalogs(scope.catalog)?,
+        }]);
+    }
+
+    let mut builds = discover_builds(project_path);
+    if let Some(name) = scope.catalog {
+        for build in &mut builds {
+            build.catalogs.retain(|catalog| catalog.name == name);
+        }
+        builds.retain(|build| !build.catalogs.is_empty());
+        if builds.is_empty() {
+            return Err(GvcError::ProjectValidation(format!(
+                "No build under {} has a catalog named '{}'",
+                project_path.display(),
+                name
+            )));
+        }
+    }
+
+    outln!(
+        "{}",
+        format!(
+            "✓ Found {} Gradle build(s) with version catalogs",
+            builds.len()
+        )
+        .green()
+    );
+    Ok(builds)
+}
+
+/// Remove a library or plugin entry from the version catalog
+pub fn execute_remove<P: AsRef<Path>>(
+    project_path: P,
+    alias: &str,
+    plugin_flag: bool,
+    library_flag: bool,
+) -> Result<()> {
+    let project_path = PathValidator::validate_project_path(project_path)?;
+    outln!(
+        "{}",
+        "Removing entry from Gradle version catalog..."
+            .cyan()
+            .bold()
+    );
+
+    outln!("\n{}", "1. Validating project structure...".yellow());
+    let scanner = ProjectScannerAgent::new(&project_path);
+    let project_info = scanner.validate()?;
+    outln!("{}", "✓ Project structure is valid".green());
+
+    outln!("\n{}", "2. Removing entry from version catalog...".yellow());
+    let target = if plugin_flag {
+        Some(AddTargetKind::Plugin)
+    } else if library_flag {
+        Some(AddTargetKind::Library)
+    } else {
+        None
+    };
+    let editor = CatalogEditor::new(&project_info.toml_path).with_build_scripts(&project_path);
+    let result = editor.remove_entry(alias, target)?;
+
+    let (kind, section) = match result.target {
+        AddTargetKind::Library => ("Library", "libraries"),
+        AddTargetKind::Plugin => ("Plugin", "plugins"),
+    };
+    outln!(
+        "{}",
+        format!("✓ {} '{}' removed", kind, result.alias).green()
+    );
+    for bundle in &result.bundles {
+        outln!("   Dropped from bundle '{}'", bundle.bright_cyan());
+    }
+    if let Some(version_alias) = &result.removed_version_alias {
+        outln!(
+            "   Removed unused version alias '{}'",
+            version_alias.bright_cyan()
+        );
+    }
+    if let Some(version_alias) = &result.kept_version_alias {
+        outln!(
+            "   Kept version alias '{}', which build scripts still read as {}",
+            version_alias.bright_cyan(),
+            catalog_accessor("libs", "versions", version_alias)
+        );
+    }
+
+    outln!(
+        "\n{}",
+        "3. Checking build scripts for remaining usages...".yellow()
+    );
+    let accessor = catalog_accessor("libs", section, &result.alias);
+    let longer = remaining_accessors(&project_info.toml_path, section)
+        .into_iter()
+        .filter(|other| other.starts_with(&format!("{}.", accessor)))
+        .collect::<Vec<_>>();
+    let usages = BuildScriptScanner::new(&project_path).find_usages(&accessor, &longer)?;
+
+    if usages.is_empty() {
+        outln!("{}", format!("✓ No build script uses {}", accessor).green());
+    } else {
+        outln!(
+            "{}",
+            format!(
+                "⚠ {} is still used in {} place(s); the build will fail until these are updated:",
+                accessor,
+                usages.len()
+            )
+            .yellow()
+        );
+        for usage in &usages {
+            let path = usage
+                .path
+                .strip_prefix(&project_path)
+                .unwrap_or(&usage.path);
+            outln!(
+                "   {}:{}  {}",
+                path.display(),
+                usage.line,
+                usage.text.dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Accessors of every entry left in a catalog section
+fn remaining_accessors(toml_path: &Path, section: &str) -> Vec<String> {
+    std::fs::read_to_string(toml_path)
+        .ok()
+        .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok())
+        .and_then(|doc| {
+            doc.get(section).and_then(|v| v.as_table()).map(|table| {
+                table
+                    .iter()
+                    .map(|(key, _)| catalog_accessor("libs", section, key))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Move hard-coded coordinates and plugin versions from build scripts into the catalog
+pub fn execute_migrate<P: AsRef<Path>>(project_path: P, dry_run: bool) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    outln!(
+        "{}",
+        "Migrating hard-coded dependencies into the version catalog..."
+            .cyan()
+            .bold()
+    );
+
+    outln!("\n{}", "1. Scanning build scripts...".yellow());
+    let plan =
+        DependencyMigrator::new(&project_info.project_path, &project_info.toml_path).plan()?;
+    if plan.declarations.is_empty() {
+        outln!(
+            "{}",
+            "✓ No hard-coded dependencies or plugin versions found".green()
+        );
+        return Ok(());
+    }
+    outln!(
+        "{}",
+        format!(
+            "✓ Found {} hard-coded declaration(s)",
+            plan.declarations.len()
+        )
+        .green()
+    );
+
+    outln!(
+        "\n{}",
+        "2. Mapping declarations to catalog entries...".yellow()
+    );
+    for (target, outcome) in &plan.outcomes {
+        match outcome {
+            MigrationOutcome::Created {
+                alias,
+                version_alias,
+            } => outln!(
+                "  {} {} {} {}",
+                "+".green().bold(),
+                alias.bright_cyan(),
+                target.to_string().dimmed(),
+                format!("(version.ref = \"{}\")", version_alias).dimmed()
+            ),
+            MigrationOutcome::Existing { alias } => outln!(
+                "  {} {} {}",
+                "=".blue().bold(),
+                alias.bright_cyan(),
+                format!("{} already in the catalog", target).dimmed()
+            ),
+            MigrationOutcome::Conflict {
+                alias,
+                catalog_version,
+            } => outln!(
+                "  {}",
+                format!(
+                    "⚠ {} left hard-coded: catalog entry '{}' uses version {}",
+                    target, alias, catalog_version
+                )
+                .yellow()
+            ),
+        }
+    }
+
+    if dry_run {
+        outln!();
+        for edit in &plan.edits {
+            print_diff(&project_info.project_path, edit);
+        }
+        outln!("\n{}", "Dry run: no files were changed.".yellow());
+        return Ok(());
+    }
+
+    outln!("\n{}", "3. Writing changes...".yellow());
+    for edit in &plan.edits {
+        edit.write()?;
+    }
+    outln!(
+        "{}",
+        format!("✓ Updated {} file(s)", plan.edits.len()).green()
+    );
+    Ok(())
+}
+
+/// Rename a catalog alias and rewrite the accessors that use it
+pub fn execute_rename<P: AsRef<Path>>(
+    project_path: P,
+    old: &str,
+    new: &str,
+    section: Option<&str>,
+    yes: bool,
+) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    outln!("{}", "Renaming catalog alias...".cyan().bold());
+
+    outln!("\n{}", "1. Planning catalog changes...".yellow());
+    let plan = CatalogEditor::new(&project_info.toml_path).plan_rename(old, new, section)?;
+    outln!(
+        "{}",
+        format!("✓ [{}] '{}' -> '{}'", plan.section, plan.from, plan.to).green()
+    );
+    for bundle in &plan.bundles {
+        outln!("   Bundle '{}' updated", bundle.bright_cyan());
+    }
+    for alias in &plan.version_refs {
+        outln!("   version.ref of '{}' updated", alias.bright_cyan());
+    }
+
+    outln!("\n{}", "2. Rewriting accessor usages...".yellow());
+    let old_accessor = catalog_accessor("libs", plan.section, &plan.from);
+    let longer = remaining_accessors(&project_info.toml_path, plan.section)
+        .into_iter()
+        .filter(|other| other.starts_with(&format!("{}.", old_accessor)))
+        .collect::<Vec<_>>();
+    let script_edits = BuildScriptScanner::new(&project_info.project_path).rewrite_accessor(
+        "libs",
+        plan.section,
+        &plan.from,
+        &plan.to,
+        &longer,
+    )?;
+    outln!(
+        "{}",
+        format!(
+            "✓ {} -> {} in {} file(s)",
+            old_accessor,
+            catalog_accessor("libs", plan.section, &plan.to),
+            script_edits.len()
+        )
+        .green()
+    );
+
+    let edits: Vec<FileEdit> = std::iter::once(plan.edit)
+        .chain(script_edits)
+        .filter(FileEdit::is_change)
+        .collect();
+    if edits.is_empty() {
+        outln!("\n{}", "Nothing to change".yellow());
+        return Ok(());
+    }
+
+    outln!();
+    for edit in &edits {
+        print_diff(&project_info.project_path, edit);
+    }
+
+    if !yes && !confirm("Apply these changes? [y/N]: ")? {
+        outln!("{}", "Rename cancelled; no files were changed.".yellow());
+        return Ok(());
+    }
+
+    for edit in &edits {
+        edit.write()?;
+    }
+    outln!(
+        "\n{}",
+        format!("✓ Renamed '{}' to '{}'", plan.from, plan.to)
+            .green()
+            .bold()
+    );
+    Ok(())
+}
+
+/// Move libraries of one group that repeat a literal version onto a shared `[versions]` alias
+pub fn execute_dedupe_versions<P: AsRef<Path>>(project_path: P, yes: bool) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    outln!("{}", "Consolidating repeated versions...".cyan().bold());
+
+    outln!(
+        "\n{}",
+        "1. Looking for repeated literal versions...".yellow()
+    );
+    let editor = CatalogEditor::new(&project_info.toml_path);
+    let duplicates = editor.find_duplicate_versions()?;
+    if duplicates.is_empty() {
+        outln!(
+            "\n{}",
+            "✨ No library group repeats a literal version"
+                .green()
+                .bold()
+        );
+        return Ok(());
+    }
+
+    let mut selected = Vec::new();
+    for duplicate in duplicates {
+        let origin = if duplicate.reused { "existing" } else { "new" };
+        outln!(
+            "  • {} {} → [versions] {} ({})",
+            duplicate.group.white().bold(),
+            duplicate.version.green(),
+            duplicate.version_alias.bright_cyan(),
+            origin.dimmed()
+        );
+        outln!("      {}", duplicate.aliases.join(", ").dimmed());
+        if yes
+            || confirm(&format!(
+                "  Consolidate into '{}'? [y/N]: ",
+                duplicate.version_alias
+            ))?
+        {
+            selected.push(duplicate);
+        }
+    }
+    if selected.is_empty() {
+        outln!(
+            "{}",
+            "Nothing selected; the catalog was not changed.".yellow()
+        );
+        return Ok(());
+    }
+
+    outln!("\n{}", "2. Updating the catalog...".yellow());
+    let edit = editor.plan_dedupe_versions(&selected)?;
+    outln!();
+    print_diff(&project_info.project_path, &edit);
+    edit.write()?;
+
+    let libraries: usize = selected.iter().map(|d| d.aliases.len()).sum();
+    outln!(
+        "\n{}",
+        format!(
+            "✓ {} libraries now share {} version alias(es)",
+            libraries,
+            selected.len()
+        )
+        .green()
+        .bold()
+    );
+    Ok(())
+}
+
+/// Show the edits of `gvc update --dry-run` and optionally save them as a patch
+fn print_dry_run(
+    project_path: &Path,
+    edits: &[FileEdit],
+    output_patch: Option<&Path>,
+) -> Result<()> {
+    outln!("\n{}", "Changes that would be written:".cyan().bold());
+    if edits.is_empty() {
+        outln!("{}", "  (none)".dimmed());
+    }
+    for edit in edits {
+        print_diff(project_path, edit);
+    }
+
+    if let Some(path) = output_patch {
+        let patch: String = edits
+            .iter()
+            .map(|edit| edit_diff(project_path, edit))
+            .collect();
+        std::fs::write(path, patch)?;
+        outln!(
+            "{}",
+            format!("✓ Patch written to {}", path.display()).green()
+        );
+    }
+    Ok(())
+}
+
+/// Unified diff of a pending file edit, with paths relative to the project
+fn edit_diff(project_path: &Path, edit: &FileEdit) -> String {
+    let display = edit
+        .path
+        .strip_prefix(project_path)
+        .unwrap_or(&edit.path)
+        .display()
+        .to_string()
+        .replace('\\', "/");
+    unified_diff(
+        &edit.original,
+        &edit.updated,
+        &format!("a/{}", display),
+        &format!("b/{}", display),
+    )
+}
+
+/// Print a colored unified diff of a pending file edit
+fn print_diff(project_path: &Path, edit: &FileEdit) {
+    for line in edit_diff(project_path, edit).lines() {
+        let colored = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else {
+            line.normal()
+        };
+        outln!("{}", colored);
+    }
+}
+
+/// Ask a yes/no question; anything but `y`/`yes` declines
+fn confirm(prompt: &str) -> Result<bool> {
+    out!("{}", prompt.bold());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Validate the version catalog, optionally fixing safe issues
+pub fn execute_lint<P: AsRef<Path>>(project_path: P, fix: bool) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    let linter =
+        CatalogLinter::new(&project_info.toml_path).with_build_scripts(&project_info.project_path);
+    let display_path = project_info
+        .toml_path
+        .strip_prefix(&project_info.project_path)
+        .unwrap_or(&project_info.toml_path)
+        .display()
+        .to_string();
+
+    let mut issues = linter.lint()?;
+
+    if fix && issues.iter().any(|issue| issue.rule.fixable()) {
+        let fixed = linter.fix(&issues)?;
+        for issue in &fixed {
+            outln!(
+                "{} {}:{}:{}: {}",
+                "fixed".green().bold(),
+                display_path,
+                issue.line,
+                issue.column,
+                issue.message
+            );
+        }
+        issues = linter.lint()?;
+    }
+
+    for issue in &issues {
+        print_lint_issue(&display_path, issue, fix);
+    }
+
+    if issues.is_empty() {
+        outln!("{}", "✓ No problems found in the version catalog".green());
+        return Ok(());
+    }
+
+    let fixable = issues.iter().filter(|issue| issue.rule.fixable()).count();
+    if fixable > 0 && !fix {
+        outln!(
+            "\n{}",
+            format!("{} problem(s) can be fixed with `gvc lint --fix`", fixable).dimmed()
+        );
+    }
+
+    Err(GvcError::LintFailed(issues.len()))
+}
+
+fn print_lint_issue(path: &str, issue: &LintIssue, fixing: bool) {
+    let severity = issue.rule.severity();
+    let label = format!("{}[{}]", severity, issue.rule.code());
+    let label = match severity {
+        LintSeverity::Error => label.red().bold(),
+        LintSeverity::Warning => label.yellow().bold(),
+    };
+    let hint = if issue.rule.fixable() && !fixing {
+        " (fixable)".dimmed().to_string()
+    } else {
+        String::new()
+    };
+
+    outln!(
+        "{}:{}:{}: {}: {}{}",
+        path,
+        issue.line,
+        issue.column,
+        label,
+        issue.message,
+        hint
+    );
+}
+
+/// Sort and normalize the version catalog, or only verify it with `check`
+pub fn execute_fmt<P: AsRef<Path>>(project_path: P, check: bool) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    let display_path = project_info
+        .toml_path
+        .strip_prefix(&project_info.project_path)
+        .unwrap_or(&project_info.toml_path)
+        .display()
+        .to_string();
+
+    let changed = CatalogFormatter::new(&project_info.toml_path).format(check)?;
+    match (changed, check) {
+        (false, _) => outln!("{}", format!("✓ {} is formatted", display_path).green()),
+        (true, false) => outln!("{}", format!("✓ Formatted {}", display_path).green()),
+        (true, true) => return Err(GvcError::NotFormatted(display_path)),
+    }
+
+    Ok(())
+}
+
+/// Revert the last journaled command: restore its catalogs and delete the update
+/// branch it created, unless that branch was pushed
+pub fn execute_undo<P: AsRef<Path>>(project_path: P, no_git: bool) -> Result<()> {
+    let project_path = PathValidator::validate_project_path(project_path)?;
+    let journal = Journal::new(&project_path);
+    let Some(entry) = journal.last()? else {
+        outln!(
+            "{}",
+            format!("Nothing to undo in {}", journal.dir().display()).yellow()
+        );
+        return Ok(());
+    };
+    outln!(
+        "{}",
+        format!(
+            "Undoing `gvc {}` from {}...",
+            entry.command, entry.timestamp
+        )
+        .cyan()
+        .bold()
+    );
+
+    for file in &entry.files {
+        let hash = std::fs::read_to_string(journal.resolve(&file.path))
+            .ok()
+            .map(|content| content_hash(&content));
+        if hash != file.after && hash != file.before {
+            return Err(GvcError::ProjectValidation(format!(
+                "{} changed after `gvc {}`; commit or discard those edits before undoing",
+                file.path.display(),
+                entry.command
+            )));
+        }
+    }
+
+    let git_agent = if !no_git && project_path.join(".git").exists() && !entry.branches.is_empty() {
+        Some(VersionControlAgent::new(&project_path)?)
+    } else {
+        None
+    };
+    if let Some(git_agent) = &git_agent {
+        let on_created = git_agent.current_branch()?.is_some_and(|current| {
+            entry
+                .branches
+                .iter()
+                .any(|branch| branch.previous.is_none() && branch.name == current)
+        });
+        if on_created {
+            match (&entry.branch, &entry.head) {
+                (Some(original), _) => {
+                    git_agent.checkout(original)?;
+                    outln!("{}", format!("✓ Switched back to {}", original).green());
+                }
+                (None, Some(head)) => {
+                    git_agent.checkout_detached(head)?;
+                    outln!(
+                        "{}",
+                        format!("✓ Switched back to detached {}", short_commit(head)).green()
+                    );
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    for file in &entry.files {
+        let path = journal.resolve(&file.path);
+        let hash = std::fs::read_to_string(&path)
+            .ok()
+            .map(|content| content_hash(&content));
+        if hash == file.before {
+            continue;
+        }
+        match &file.before {
+            Some(before) => std::fs::write(&path, journal.read_object(before)?)?,
+            None => std::fs::remove_file(&path)?,
+        }
+        outln!("{}", format!("✓ Restored {}", file.path.display()).green());
+    }
+
+    if let Some(git_agent) = &git_agent {
+        let tips = git_agent.branch_tips()?;
+        for branch in &entry.branches {
+            let tip = tips
+                .iter()
+                .find(|(name, _)| *name == branch.name)
+                .map(|(_, commit)| commit);
+            match (&branch.previous, tip) {
+                (_, None) => {}
+                (Some(previous), Some(_)) => outln!(
+                    "{}",
+                    format!(
+                        "⚠ {} was moved from {} and is kept; reset it yourself if needed",
+                        branch.name,
+                        short_commit(previous)
+                    )
+                    .yellow()
+                ),
+                (None, Some(tip)) if *tip != branch.commit => outln!(
+                    "{}",
+                    format!("⚠ {} has new commits and is kept", branch.name).yellow()
+                ),
+                (None, Some(_)) if git_agent.is_pushed(&branch.name)? => outln!(
+                    "{}",
+                    format!(
+                        "⚠ {} was pushed and is kept; delete it on the remote first",
+                        branch.name
+                    )
+                    .yellow()
+                ),
+                (None, Some(_)) => {
+                    git_agent.delete_branch(&branch.name)?;
+                    outln!("{}", format!("✓ Deleted branch {}", branch.name).green());
                 }
-                return Err(GvcError::ProjectValidation(format!(
-                    "No versions available for plugin '{}'",
-                    plugin_id
-                )));
             }
         }
-    } else if available_versions.iter().any(|v| v == &version) {
-        version
-    } else {
-        return Err(GvcError::ProjectValidation(format!(
-            "Version '{}' for plugin '{}' not found on Gradle Plugin Portal",
-            version, plugin_id
-        )));
-    };
-
-    println!(
-        "   {}",
-        format!("✓ plugin {plugin_id} @ {target_version}").green()
-    );
+    }
+    journal.pop()?;
+    outln!("\n{}", "✨ Undo completed".green().bold());
+    Ok(())
+}
 
-    Ok(target_version)
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
 }
 
-/// Execute the update workflow
-pub fn execute_update<P: AsRef<Path>>(
-    project_path: P,
-    interactive: bool,
-    filter: Option<String>,
-    stable_only: bool,
-    no_git: bool,
-) -> Result<()> {
-    let project_path = PathValidator::validate_project_path(project_path)?;
-    println!("{}", "Starting dependency update process...".cyan().bold());
+/// Report catalog entries that no build script references, optionally removing them
+pub fn execute_unused<P: AsRef<Path>>(project_path: P, remove: bool) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    outln!(
+        "{}",
+        "Scanning build logic for catalog usages...".cyan().bold()
+    );
 
-    // Step 1: Validate project structure
-    println!("\n{}", "1. Validating project structure...".yellow());
-    let scanner = ProjectScannerAgent::new(&project_path);
-    let project_info = scanner.validate()?;
-    println!("{}", "✓ Project structure is valid".green());
+    let analyzer = UsageAnalyzer::new(&project_info.project_path, &project_info.toml_path);
+    let unused = analyzer.find_unused()?;
 
-    // Step 2: Check Git status (if Git is available and not disabled)
-    if project_info.has_git && !no_git {
-        println!("\n{}", "2. Checking Git status...".yellow());
-        let git_agent = VersionControlAgent::new(&project_path)?;
+    if unused.is_empty() {
+        outln!("{}", "✓ Every catalog entry is referenced".green());
+        return Ok(());
+    }
 
-        if !git_agent.is_working_directory_clean()? {
-            println!(
-                "{}",
-                "⚠ Warning: Working directory has uncommitted changes".red()
+    let sections = [
+        ("libraries", &unused.libraries),
+        ("plugins", &unused.plugins),
+        ("bundles", &unused.bundles),
+        ("versions", &unused.versions),
+    ];
+    for (section, aliases) in sections.iter().filter(|(_, aliases)| !aliases.is_empty()) {
+        outln!("\n{}", format!("Unused [{}]:", section).yellow());
+        for alias in aliases.iter() {
+            outln!(
+                "  - {} {}",
+                alias.bright_cyan(),
+                format!("({})", catalog_accessor("libs", section, alias)).dimmed()
             );
-            println!("Please commit or stash your changes before proceeding.");
-            return Ok(());
         }
-        println!("{}", "✓ Working directory is clean".green());
-    } else if !no_git {
-        println!(
+    }
+
+    if !remove {
+        outln!(
             "\n{}",
-            "2. Git repository not detected, skipping Git checks".yellow()
+            format!(
+                "{} unused {}; run `gvc unused --remove` to delete them",
+                unused.len(),
+                entries_label(unused.len())
+            )
+            .dimmed()
         );
+        return Ok(());
     }
 
-    // Step 3: Read Gradle repository configuration
-    println!(
-        "\n{}",
-        "3. Reading Gradle repository configuration...".yellow()
-    );
-    let gradle_parser = GradleConfigParser::new(&project_path);
-    let gradle_config = gradle_parser.parse()?;
+    outln!("\n{}", "Removing unused entries...".yellow());
+    let editor = CatalogEditor::new(&project_info.toml_path);
+    let mut removed_versions = Vec::new();
+
+    for bundle in &unused.bundles {
+        editor.remove_from_bundle(bundle, &[])?;
+    }
+    for (aliases, kind) in [
+        (&unused.libraries, AddTargetKind::Library),
+        (&unused.plugins, AddTargetKind::Plugin),
+    ] {
+        for alias in aliases {
+            let result = editor.remove_entry(alias, Some(kind))?;
+            removed_versions.extend(result.removed_version_alias);
+        }
+    }
+    for version in unused
+        .versions
+        .iter()
+        .filter(|version| !removed_versions.contains(version))
+    {
+        editor.remove_version(version)?;
+    }
 
-    println!(
-        "   Found {} repositories:",
-        gradle_config.repositories.len()
+    outln!(
+        "{}",
+        format!(
+            "✓ Removed {} unused {}",
+            unused.len(),
+            entries_label(unused.len())
+        )
+        .green()
     );
-    for repo in &gradle_config.repositories {
-        println!("   • {} ({})", repo.name.bright_cyan(), repo.url.dimmed());
+    Ok(())
+}
+
+fn entries_label(count: usize) -> &'static str {
+    if count == 1 { "entry" } else { "entries" }
+}
+
+/// Add library aliases to a bundle
+pub fn execute_bundle_add<P: AsRef<Path>>(
+    project_path: P,
+    bundle: &str,
+    aliases: &[String],
+) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    let editor = CatalogEditor::new(&project_info.toml_path);
+
+    let added = editor.add_to_bundle(bundle, aliases)?;
+    if added.is_empty() {
+        outln!(
+            "{}",
+            format!("Bundle '{}' already contains every alias", bundle).yellow()
+        );
+    } else {
+        outln!(
+            "{}",
+            format!("✓ Added {} to bundle '{}'", added.join(", "), bundle).green()
+        );
     }
 
-    // Step 4: Update dependencies
-    println!("\n{}", "4. Updating dependencies...".yellow());
-    let updater = DependencyUpdater::with_repositories(gradle_config.repositories)?;
-
-    let report = match filter {
-        Some(pattern) => match updater.update_targeted_dependency(
-            &project_info.toml_path,
-            stable_only,
-            interactive,
-            &pattern,
-        ) {
-            Ok(report) => report,
-            Err(GvcError::UserCancelled) => {
-                println!("\n{}", "Update cancelled by user.".yellow());
-                return Ok(());
-            }
-            Err(e) => return Err(e),
-        },
-        None => {
-            match updater.update_version_catalog(&project_info.toml_path, stable_only, interactive)
-            {
-                Ok(report) => report,
-                Err(GvcError::UserCancelled) => {
-                    println!("\n{}", "Update cancelled by user.".yellow());
-                    return Ok(());
+    Ok(())
+}
+
+/// Remove aliases from a bundle, or the whole bundle
+pub fn execute_bundle_remove<P: AsRef<Path>>(
+    project_path: P,
+    bundle: &str,
+    aliases: &[String],
+) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    let editor = CatalogEditor::new(&project_info.toml_path);
+
+    let removed = editor.remove_from_bundle(bundle, aliases)?;
+    if aliases.is_empty() {
+        outln!("{}", format!("✓ Removed bundle '{}'", bundle).green());
+    } else {
+        outln!(
+            "{}",
+            format!("✓ Removed {} from bundle '{}'", removed.join(", "), bundle).green()
+        );
+    }
+
+    Ok(())
+}
+
+/// List bundles and their members
+pub fn execute_bundle_list<P: AsRef<Path>>(project_path: P) -> Result<()> {
+    let project_info = validate_project(project_path)?;
+    let bundles = CatalogEditor::new(&project_info.toml_path).list_bundles()?;
+
+    if bundles.is_empty() {
+        outln!("{}", "No bundles defined in the version catalog".yellow());
+        return Ok(());
+    }
+
+    print_bundles(&bundles);
+    Ok(())
+}
+
+fn validate_project<P: AsRef<Path>>(project_path: P) -> Result<ProjectInfo> {
+    let project_path = PathValidator::validate_project_path(project_path)?;
+    ProjectScannerAgent::new(&project_path).validate()
+}
+
+fn print_bundles(bundles: &[BundleInfo]) {
+    outln!("\n{}", "Bundles:".yellow().bold());
+    for bundle in bundles {
+        let members: Vec<String> = bundle
+            .members
+            .iter()
+            .map(|member| {
+                if bundle.missing.contains(member) {
+                    format!("{} {}", member.red(), "(missing library)".dimmed())
+                } else {
+                    member.to_string()
                 }
-                Err(e) => return Err(e),
-            }
-        }
-    };
+            })
+            .collect();
+        outln!("  {} = [{}]", bundle.name.blue(), members.join(", "));
+    }
+}
 
-    println!("{}", "✓ Update completed".green());
+/// Warn about bundle members that don't reference a library
+fn warn_invalid_bundles(doc: &toml_edit::DocumentMut) {
+    for bundle in read_bundles(doc) {
+        for member in &bundle.missing {
+            outln!(
+                "{}",
+                format!(
+                    "⚠ Bundle '{}' references unknown library alias '{}'",
+                    bundle.name, member
+                )
+                .yellow()
+            );
+        }
+    }
+}
 
-    // Step 5: Display summary
-    print_update_report(&report);
+/// Print the effective configuration and where each value comes from
+pub fn execute_config_show(loaded: &LoadedConfig) -> Result<()> {
+    outln!("{}", "Effective gvc configuration:".cyan().bold());
 
-    // Step 6: Git operations (if enabled)
-    if project_info.has_git && !no_git && !report.is_empty() {
-        println!("\n{}", "5. Creating Git commit...".yellow());
-        let git_agent = VersionControlAgent::new(&project_path)?;
-        let branch_name = git_agent.commit_to_new_branch()?;
-        println!(
-            "{}",
-            format!("✓ Changes committed to branch: {}", branch_name).green()
+    let mut current_section = "";
+    for (key, (value, source)) in &loaded.values {
+        let (section, name) = key.split_once('.').unwrap_or(("", key.as_str()));
+        if section != current_section {
+            outln!("\n[{}]", section.bold());
+            current_section = section;
+        }
+        outln!(
+            "  {} = {}  {}",
+            name,
+            value,
+            format!("# {}", source).dimmed()
         );
-    } else if report.is_empty() {
-        println!("\n{}", "No updates were applied".yellow());
     }
 
-    println!(
-        "\n{}",
-        "✨ Update process completed successfully!".green().bold()
-    );
     Ok(())
 }
 
+/// Gates and report files of `gvc check`, for CI
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CheckOutputs<'a> {
+    /// Fail with a dedicated exit code when entries match
+    pub fail_on: Option<FailOn>,
+    /// Write a JUnit XML report here
+    pub junit: Option<&'a Path>,
+    /// Write a SARIF log here
+    pub sarif: Option<&'a Path>,
+    /// Print GitHub Actions annotations for outdated and vulnerable entries
+    pub github_annotations: bool,
+}
+
 /// Execute the check workflow (dry-run)
-pub fn execute_check<P: AsRef<Path>>(project_path: P, stable_only: bool) -> Result<()> {
+pub fn execute_check<P: AsRef<Path>>(
+    project_path: P,
+    config: &GvcConfig,
+    scope: BuildScope<'_>,
+    outputs: CheckOutputs<'_>,
+) -> Result<()> {
     let project_path = PathValidator::validate_project_path(project_path)?;
-    let version_channel = if stable_only { "stable" } else { "all" };
-    println!(
+    let policy = config.update.stability;
+    let mut json = json_report(config.output.format, "check", Some(policy));
+    outln!(
         "{}",
-        format!(
-            "Checking for available updates ({} versions)...",
-            version_channel
-        )
-        .cyan()
-        .bold()
+        format!("Checking for available updates ({} versions)...", policy)
+            .cyan()
+            .bold()
     );
 
     // Step 1: Validate project structure
-    println!("\n{}", "1. Validating project structure...".yellow());
+    outln!("\n{}", "1. Validating project structure...".yellow());
     let scanner = ProjectScannerAgent::new(&project_path);
     let project_info = scanner.validate()?;
-    println!("{}", "✓ Project structure is valid".green());
+    let builds = select_builds(&project_path, &project_info, scope)?;
+    outln!("{}", "✓ Project structure is valid".green());
+    for catalog in builds.iter().flat_map(|build| &build.catalogs) {
+        if let Some(doc) = std::fs::read_to_string(&catalog.path)
+            .ok()
+            .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok())
+        {
+            warn_invalid_bundles(&doc);
+        }
+    }
 
-    // Step 2: Read Gradle repository configuration
-    println!(
+    // Step 2: Read Gradle repository configuration, per build
+    outln!(
         "\n{}",
         "2. Reading Gradle repository configuration...".yellow()
     );
-    let gradle_parser = GradleConfigParser::new(&project_path);
-    let gradle_config = gradle_parser.parse()?;
-
-    println!(
-        "   Found {} repositories:",
-        gradle_config.repositories.len()
-    );
-    for repo in &gradle_config.repositories {
-        println!("   • {} ({})", repo.name.bright_cyan(), repo.url.dimmed());
+    let mut build_repositories = Vec::new();
+    for build in &builds {
+        if builds.len() > 1 {
+            print_build_header(&project_path, build);
+        }
+        build_repositories.push(read_repositories(&build.path, &config.repositories)?);
     }
 
     // Step 3: Check for updates without modifying the file
-    println!("\n{}", "3. Checking for available updates...".yellow());
+    outln!("\n{}", "3. Checking for available updates...".yellow());
+
+    // Load each catalog without writing it back
+    let mut reports = Vec::new();
+    let mut entries = Vec::new();
+    for (build, repositories) in builds.iter().zip(build_repositories) {
+        let updater = DependencyUpdater::with_repositories(repositories)?
+            .with_rules(MarkerRules::from_config(&config.rules)?);
+
+        let mut report = UpdateReport::new();
+        for catalog in &build.catalogs {
+            let catalog_report = updater.check_for_updates(&catalog.path, policy)?;
+            let label = build_label(&builds, build);
+            entries.extend(collect_entries(label, catalog, &catalog_report, false)?);
+            if build.catalogs.len() > 1 {
+                report.merge(catalog_report.prefixed(&catalog.name));
+            } else {
+                report.merge(catalog_report);
+            }
+        }
+        reports.push(report);
+    }
 
-    let updater = DependencyUpdater::with_repositories(gradle_config.repositories)?;
+    outln!("{}", "✓ Check completed".green());
 
-    // Load the current TOML without writing it back
-    let report = updater.check_for_updates(&project_info.toml_path, stable_only)?;
+    // Step 4: Display available updates
+    for (build, report) in builds.iter().zip(&reports) {
+        if builds.len() > 1 {
+            print_build_header(&project_path, build);
+        }
+        print_available_updates(report, policy);
+    }
 
-    println!("{}", "✓ Check completed".green());
+    if scope.align {
+        outln!(
+            "\n{}",
+            "Checking version alignment across builds...".yellow()
+        );
+        let misalignments = VersionAligner::new(&builds)
+            .with_rules(MarkerRules::from_config(&config.rules)?)
+            .with_policy(policy)
+            .find_misalignments()?;
+        if misalignments.is_empty() {
+            outln!(
+                "{}",
+                "✓ Shared dependencies use the same version everywhere".green()
+            );
+        } else {
+            print_misalignments(&misalignments);
+            outln!("\n{}", "To align them, run:".dimmed());
+            outln!("  {}", "gvc update --all-builds --align".cyan());
+        }
+        if let Some(json) = json.as_mut() {
+            for misalignment in &misalignments {
+                json.add_diagnostic(misalignment_message(misalignment));
+            }
+        }
+    }
 
-    // Step 4: Display available updates
-    print_available_updates(&report, stable_only);
+    if outputs.fail_on == Some(FailOn::Security) {
+        check_advisories(&mut entries)?;
+    }
+    if let Some(path) = outputs.junit {
+        std::fs::write(
+            path,
+            report::junit::render(&entries, &project_path, outputs.fail_on),
+        )?;
+        outln!("{} {}", "✓ JUnit report written to".green(), path.display());
+    }
+    if let Some(path) = outputs.sarif {
+        std::fs::write(
+            path,
+            report::sarif::render(&entries, &project_path, outputs.fail_on)?,
+        )?;
+        outln!("{} {}", "✓ SARIF report written to".green(), path.display());
+    }
+    if outputs.github_annotations && json.is_none() {
+        // Workflow commands only take effect on stdout, which a JSON report owns
+        for annotation in report::github::annotations(&entries, &project_path, outputs.fail_on) {
+            println!("{}", annotation);
+        }
+    }
+    if let Some(mut json) = json {
+        json.add_entries(&entries);
+        println!("{}", json.to_json()?);
+    }
+
+    match outputs
+        .fail_on
+        .and_then(|fail_on| fail_on.failure(&entries))
+    {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
+}
 
+/// Look up known vulnerabilities of every library version in OSV and report them
+fn check_advisories(entries: &mut [ReportEntry]) -> Result<()> {
+    outln!(
+        "\n{}",
+        "Checking for known vulnerabilities (osv.dev)...".yellow()
+    );
+    let packages: Vec<_> = entries
+        .iter()
+        .flat_map(|entry| entry.packages.iter().cloned())
+        .collect();
+    let mut advisories = OsvClient::new()?.advisories(&packages)?.into_iter();
+    let mut vulnerable = 0;
+    for entry in entries.iter_mut() {
+        let mut ids: Vec<String> = advisories
+            .by_ref()
+            .take(entry.packages.len())
+            .flatten()
+            .collect();
+        ids.sort();
+        ids.dedup();
+        if !ids.is_empty() {
+            vulnerable += 1;
+            outln!(
+                "  {} {} {}: {}",
+                "⚠".yellow(),
+                entry.alias.bold(),
+                entry.current.as_deref().unwrap_or("?"),
+                ids.join(", ").red()
+            );
+        }
+        entry.advisories = ids;
+    }
+    if vulnerable == 0 {
+        outln!("{}", "✓ No known vulnerabilities".green());
+    }
     Ok(())
 }
 
-fn print_available_updates(report: &UpdateReport, stable_only: bool) {
+/// Collect the repositories to query: those declared in Gradle plus configured ones
+fn read_repositories(project_path: &Path, config: &RepositoriesConfig) -> Result<Vec<Repository>> {
+    let detected = if config.from_gradle {
+        GradleConfigParser::new(project_path).parse()?.repositories
+    } else {
+        Vec::new()
+    };
+    let repositories = config.resolve(detected)?;
+
+    outln!("   Found {} repositories:", repositories.len());
+    for repo in &repositories {
+        let auth = if repo.credentials.is_some() {
+            " [authenticated]".dimmed().to_string()
+        } else {
+            String::new()
+        };
+        outln!(
+            "   • {} ({}){}",
+            repo.name.bright_cyan(),
+            repo.url.dimmed(),
+            auth
+        );
+    }
+
+    Ok(repositories)
+}
+
+fn print_available_updates(report: &UpdateReport, policy: StabilityPolicy) {
+    print_skipped_entries(report);
+
     if report.is_empty() {
-        println!("\n{}", "✨ All dependencies are up to date!".green().bold());
+        outln!("\n{}", "✨ All dependencies are up to date!".green().bold());
         return;
     }
 
-    println!("\n{}", "📦 Available Updates:".cyan().bold());
-    println!(
+    outln!("\n{}", "📦 Available Updates:".cyan().bold());
+    outln!(
         "{}",
         format!("Found {} update(s)", report.total_updates()).yellow()
     );
 
-    if stable_only {
-        println!("{}", "   (showing stable versions only)".dimmed());
-    } else {
-        println!(
-            "{}",
-            "   (showing all versions including pre-releases)".dimmed()
-        );
+    match policy {
+        StabilityPolicy::StableOnly => {
+            outln!("{}", "   (showing stable versions only)".dimmed());
+        }
+        StabilityPolicy::IncludeUnstable => {
+            outln!(
+                "{}",
+                "   (showing all versions including pre-releases)".dimmed()
+            );
+        }
+        StabilityPolicy::TrackChannel => {
+            outln!(
+                "{}",
+                "   (pre-release entries follow their channel, stable entries stay stable)"
+                    .dimmed()
+            );
+        }
     }
 
-    if !report.version_updates.is_empty() {
-        println!("\n{}:", "Version updates".cyan().bold());
-        for (name, (old, new)) in &report.version_updates {
-            println!(
+    let version_updates: Vec<_> = report.updates(Section::Versions).collect();
+    if !version_updates.is_empty() {
+        outln!("\n{}:", "Version updates".cyan().bold());
+        for (name, old, new) in version_updates {
+            outln!(
                 "  • {} {} → {}",
                 name.white().bold(),
                 old.red(),
@@ -430,28 +1979,41 @@ fn print_available_updates(report: &UpdateReport, stable_only: bool) {
         }
     }
 
-    if !report.library_updates.is_empty() {
-        println!("\n{}:", "Library updates".cyan().bold());
-        for (name, (old, new)) in &report.library_updates {
+    let library_updates: Vec<_> = report
+        .entries()
+        .iter()
+        .filter(|entry| entry.section == Section::Libraries)
+        .filter_map(|entry| Some((entry, entry.update.as_deref()?)))
+        .collect();
+    if !library_updates.is_empty() {
+        outln!("\n{}:", "Library updates".cyan().bold());
+        for (entry, new) in library_updates {
             let stability = if is_stable_version(new) {
                 "stable".green()
             } else {
                 "pre-release".yellow()
             };
-            println!(
-                "  • {} {} → {} ({})",
-                name.white().bold(),
-                old.dimmed(),
+            let source = entry
+                .repository
+                .as_deref()
+                .map(|repository| format!(" from {}", repository))
+                .unwrap_or_default();
+            outln!(
+                "  • {} {} → {} ({}){}",
+                entry.name.white().bold(),
+                entry.current.as_deref().unwrap_or("?").dimmed(),
                 new.green().bold(),
-                stability
+                stability,
+                source.dimmed()
             );
         }
     }
 
-    if !report.plugin_updates.is_empty() {
-        println!("\n{}:", "Plugin updates".cyan().bold());
-        for (name, (old, new)) in &report.plugin_updates {
-            println!(
+    let plugin_updates: Vec<_> = report.updates(Section::Plugins).collect();
+    if !plugin_updates.is_empty() {
+        outln!("\n{}:", "Plugin updates".cyan().bold());
+        for (name, old, new) in plugin_updates {
+            outln!(
                 "  • {} {} → {}",
                 name.white().bold(),
                 old.red(),
@@ -460,11 +2022,11 @@ fn print_available_updates(report: &UpdateReport, stable_only: bool) {
         }
     }
 
-    println!("\n{}", "To apply these updates, run:".dimmed());
-    if stable_only {
-        println!("  {}", "gvc update --stable-only".cyan());
-    } else {
-        println!("  {}", "gvc update".cyan());
+    outln!("\n{}", "To apply these updates, run:".dimmed());
+    match policy {
+        StabilityPolicy::StableOnly => outln!("  {}", "gvc update --stable-only".cyan()),
+        StabilityPolicy::IncludeUnstable => outln!("  {}", "gvc update".cyan()),
+        StabilityPolicy::TrackChannel => outln!("  {}", "gvc update --track-channel".cyan()),
     }
 }
 
@@ -478,42 +2040,165 @@ fn is_stable_version(version: &str) -> bool {
 }
 
 /// Execute the list workflow - display all dependencies
-pub fn execute_list<P: AsRef<Path>>(project_path: P) -> Result<()> {
+pub fn execute_list<P: AsRef<Path>>(
+    project_path: P,
+    catalog: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
     let project_path = PathValidator::validate_project_path(project_path)?;
-    println!(
+    let json = json_report(format, "list", None);
+    outln!(
         "{}",
         "Listing dependencies in version catalog...".cyan().bold()
     );
 
     // Step 1: Validate project structure
-    println!("\n{}", "1. Validating project structure...".yellow());
+    outln!("\n{}", "1. Validating project structure...".yellow());
     let scanner = ProjectScannerAgent::new(&project_path);
     let project_info = scanner.validate()?;
-    println!("{}", "✓ Project structure is valid".green());
-
-    // Step 2: Parse TOML file
-    println!("\n{}", "2. Reading version catalog...".yellow());
-    let content = std::fs::read_to_string(&project_info.toml_path).map_err(|e| {
-        crate::error::GvcError::TomlParsing(format!("Failed to read catalog: {}", e))
-    })?;
-
-    let doc = content
-        .parse::<toml_edit::DocumentMut>()
-        .map_err(|e| crate::error::GvcError::TomlParsing(format!("Failed to parse TOML: {}", e)))?;
+    let catalogs = project_info.select_catalogs(catalog)?;
+    outln!("{}", "✓ Project structure is valid".green());
+
+    // Step 2: Parse TOML files
+    outln!("\n{}", "2. Reading version catalog...".yellow());
+    let mut documents = Vec::new();
+    for catalog in &catalogs {
+        let content = std::fs::read_to_string(&catalog.path).map_err(|e| {
+            crate::error::GvcError::TomlParsing(format!("Failed to read catalog: {}", e))
+        })?;
+
+        let doc = content.parse::<toml_edit::DocumentMut>().map_err(|e| {
+            crate::error::GvcError::TomlParsing(format!("Failed to parse TOML: {}", e))
+        })?;
+        documents.push((catalog, doc));
+    }
 
-    println!("{}", "✓ Catalog loaded".green());
+    outln!("{}", "✓ Catalog loaded".green());
 
     // Step 3: Display dependencies
-    print_dependencies(&doc);
+    if let Some(mut json) = json {
+        for (catalog, doc) in &documents {
+            json.add_listing(None, catalog, doc);
+        }
+        println!("{}", json.to_json()?);
+        return Ok(());
+    }
+    for (catalog, doc) in &documents {
+        if documents.len() > 1 {
+            print_catalog_header(&project_path, catalog);
+        }
+        print_dependencies(doc);
+    }
 
     Ok(())
 }
 
+/// Start a JSON report when requested, moving human-readable output to stderr
+fn json_report(
+    format: OutputFormat,
+    command: &'static str,
+    policy: Option<StabilityPolicy>,
+) -> Option<JsonReport> {
+    if format != OutputFormat::Json {
+        return None;
+    }
+    output::reserve_stdout();
+    Some(JsonReport::new(command, policy))
+}
+
+/// Name of `build` in reports, when there are several
+fn build_label<'a>(builds: &[GradleBuild], build: &'a GradleBuild) -> Option<&'a str> {
+    (builds.len() > 1).then_some(build.name.as_str())
+}
+
+fn misalignment_message(misalignment: &Misalignment) -> String {
+    let lagging: Vec<String> = misalignment
+        .lagging()
+        .map(|entry| match &entry.held {
+            Some(reason) => format!("{} ({}, {})", entry.build, entry.version, reason),
+            None => format!("{} ({})", entry.build, entry.version),
+        })
+        .collect();
+    format!(
+        "{} is behind {} in {}",
+        misalignment.module,
+        misalignment.target,
+        lagging.join(", ")
+    )
+}
+
+/// Name the build the following output belongs to
+fn print_build_header(project_path: &Path, build: &GradleBuild) {
+    let root = project_path
+        .canonicalize()
+        .unwrap_or_else(|_| project_path.to_path_buf());
+    let display = build
+        .path
+        .strip_prefix(&root)
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .unwrap_or(&build.path)
+        .display()
+        .to_string();
+    outln!(
+        "\n{} {}",
+        format!("Build '{}'", build.name).blue().bold(),
+        format!("({})", display).dimmed()
+    );
+}
+
+fn print_misalignments(misalignments: &[Misalignment]) {
+    if misalignments.is_empty() {
+        return;
+    }
+
+    outln!("\n{}:", "Versions differing between builds".cyan().bold());
+    for misalignment in misalignments {
+        outln!(
+            "  • {} → {}",
+            misalignment.module.white().bold(),
+            misalignment.target.green().bold()
+        );
+        for entry in &misalignment.entries {
+            let version = if entry.version == misalignment.target {
+                entry.version.green()
+            } else {
+                entry.version.red()
+            };
+            match &entry.held {
+                Some(reason) => outln!(
+                    "      {} {} {} {}",
+                    entry.build.dimmed(),
+                    entry.alias,
+                    version,
+                    reason.dimmed()
+                ),
+                None => outln!("      {} {} {}", entry.build.dimmed(), entry.alias, version),
+            }
+        }
+    }
+}
+
+/// Name the catalog the following output belongs to
+fn print_catalog_header(project_path: &Path, catalog: &CatalogFile) {
+    let display = catalog
+        .path
+        .strip_prefix(project_path)
+        .unwrap_or(&catalog.path)
+        .display()
+        .to_string();
+    outln!(
+        "\n{} {}",
+        format!("Catalog '{}'", catalog.name).magenta().bold(),
+        format!("({})", display).dimmed()
+    );
+}
+
 fn print_dependencies(doc: &toml_edit::DocumentMut) {
     use crate::maven::parse_maven_coordinate;
     use std::collections::HashMap;
 
-    println!("\n{}", "📦 Dependencies:".cyan().bold());
+    outln!("\n{}", "📦 Dependencies:".cyan().bold());
 
     // First, collect all version references
     let mut version_refs = HashMap::new();
@@ -528,7 +2213,7 @@ fn print_dependencies(doc: &toml_edit::DocumentMut) {
     // Display [libraries] section in Maven coordinate format
     if let Some(libraries) = doc.get("libraries").and_then(|v| v.as_table()) {
         if !libraries.is_empty() {
-            println!("\n{}", "Libraries:".yellow().bold());
+            outln!("\n{}", "Libraries:".yellow().bold());
             let mut lib_list: Vec<_> = libraries.iter().collect();
             lib_list.sort_by_key(|(k, _)| *k);
 
@@ -611,11 +2296,11 @@ fn print_dependencies(doc: &toml_edit::DocumentMut) {
                 }
 
                 if !coordinate.is_empty() && !version_str.is_empty() {
-                    println!("  {}", format!("{}:{}", coordinate, version_str).cyan());
+                    outln!("  {}", format!("{}:{}", coordinate, version_str).cyan());
                 } else if !coordinate.is_empty() {
-                    println!("  {} {}", coordinate.cyan(), "(version unknown)".dimmed());
+                    outln!("  {} {}", coordinate.cyan(), "(version unknown)".dimmed());
                 } else {
-                    println!("  {} {}", name.yellow(), "(coordinate unknown)".dimmed());
+                    outln!("  {} {}", name.yellow(), "(coordinate unknown)".dimmed());
                 }
             }
         }
@@ -624,7 +2309,7 @@ fn print_dependencies(doc: &toml_edit::DocumentMut) {
     // Display [plugins] section
     if let Some(plugins) = doc.get("plugins").and_then(|v| v.as_table()) {
         if !plugins.is_empty() {
-            println!("\n{}", "Plugins:".yellow().bold());
+            outln!("\n{}", "Plugins:".yellow().bold());
             let mut plugin_list: Vec<_> = plugins.iter().collect();
             plugin_list.sort_by_key(|(k, _)| *k);
 
@@ -660,14 +2345,20 @@ fn print_dependencies(doc: &toml_edit::DocumentMut) {
                 }
 
                 if !version_str.is_empty() {
-                    println!("  {}", format!("{}:{}", plugin_id, version_str).magenta());
+                    outln!("  {}", format!("{}:{}", plugin_id, version_str).magenta());
                 } else {
-                    println!("  {} {}", plugin_id.magenta(), "(version unknown)".dimmed());
+                    outln!("  {} {}", plugin_id.magenta(), "(version unknown)".dimmed());
                 }
             }
         }
     }
 
+    // Display [bundles] section
+    let bundles = read_bundles(doc);
+    if !bundles.is_empty() {
+        print_bundles(&bundles);
+    }
+
     // Summary
     let library_count = doc
         .get("libraries")
@@ -680,27 +2371,52 @@ fn print_dependencies(doc: &toml_edit::DocumentMut) {
         .map(|t| t.len())
         .unwrap_or(0);
 
-    println!("\n{}", "Summary:".cyan().bold());
-    println!("  {} libraries", library_count.to_string().yellow());
-    println!("  {} plugins", plugin_count.to_string().yellow());
+    outln!("\n{}", "Summary:".cyan().bold());
+    outln!("  {} libraries", library_count.to_string().yellow());
+    outln!("  {} plugins", plugin_count.to_string().yellow());
+    if !bundles.is_empty() {
+        outln!("  {} bundles", bundles.len().to_string().yellow());
+    }
+}
+
+fn print_skipped_entries(report: &UpdateReport) {
+    let skipped: Vec<_> = report.skipped().collect();
+    if skipped.is_empty() {
+        return;
+    }
+
+    outln!("\n{}:", "Skipped entries".cyan().bold());
+    for (name, reason) in skipped {
+        outln!("  • {} {}", name.white().bold(), reason.dimmed());
+    }
 }
 
 fn print_update_report(report: &UpdateReport) {
+    print_skipped_entries(report);
+
     if report.is_empty() {
-        println!("\n{}", "No updates were found".yellow());
+        outln!("\n{}", "No updates were found".yellow());
         return;
     }
 
-    println!("\n{}", "Update Summary:".cyan().bold());
-    println!(
+    outln!("\n{}", "Update Summary:".cyan().bold());
+    outln!(
         "{}",
         format!("Total updates: {}", report.total_updates()).green()
     );
 
-    if !report.version_updates.is_empty() {
-        println!("\n{}:", "Version updates".cyan());
-        for (name, (old, new)) in &report.version_updates {
-            println!(
+    for (section, title) in [
+        (Section::Versions, "Version updates"),
+        (Section::Libraries, "Library updates"),
+        (Section::Plugins, "Plugin updates"),
+    ] {
+        let updates: Vec<_> = report.updates(section).collect();
+        if updates.is_empty() {
+            continue;
+        }
+        outln!("\n{}:", title.cyan());
+        for (name, old, new) in updates {
+            outln!(
                 "  • {} {} → {}",
                 name.white().bold(),
                 old.red(),
@@ -708,28 +2424,138 @@ fn print_update_report(report: &UpdateReport) {
             );
         }
     }
+}
 
-    if !report.library_updates.is_empty() {
-        println!("\n{}:", "Library updates".cyan());
-        for (name, (old, new)) in &report.library_updates {
-            println!(
-                "  • {} {} → {}",
-                name.white().bold(),
-                old.red(),
-                new.green()
-            );
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::journal::Snapshot;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    const CATALOG: &str = "[libraries]\nokhttp = \"com.squareup.okhttp3:okhttp:4.12.0\"\n";
+    const SCRIPT: &str = "dependencies {\n    implementation(libs.okhttp)\n}\n";
+    const PLUGIN: &str = "val okhttp = libs.okhttp.get()\n";
+
+    fn gradle_project(root: &Path) {
+        fs::create_dir_all(root.join("gradle")).unwrap();
+        fs::create_dir_all(root.join("buildSrc/src/main/kotlin")).unwrap();
+        fs::write(root.join("gradlew"), "").unwrap();
+        fs::write(root.join("settings.gradle.kts"), "").unwrap();
+        fs::write(root.join("gradle/libs.versions.toml"), CATALOG).unwrap();
+        fs::write(root.join("build.gradle.kts"), SCRIPT).unwrap();
+        fs::write(root.join("buildSrc/src/main/kotlin/Network.kt"), PLUGIN).unwrap();
     }
 
-    if !report.plugin_updates.is_empty() {
-        println!("\n{}:", "Plugin updates".cyan());
-        for (name, (old, new)) in &report.plugin_updates {
-            println!(
-                "  • {} {} → {}",
-                name.white().bold(),
-                old.red(),
-                new.green()
-            );
-        }
+    #[test]
+    fn undo_restores_build_logic_rewritten_by_rename() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        gradle_project(&root);
+
+        let snapshot = Snapshot::take(&root, true).unwrap();
+        execute_rename(&root, "okhttp", "okhttp3", None, true).unwrap();
+        let entry = snapshot.record("rename").unwrap().unwrap();
+        assert_eq!(entry.files.len(), 3);
+        assert!(
+            fs::read_to_string(root.join("build.gradle.kts"))
+                .unwrap()
+                .contains("libs.okhttp3")
+        );
+
+        execute_undo(&root, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("gradle/libs.versions.toml")).unwrap(),
+            CATALOG
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("build.gradle.kts")).unwrap(),
+            SCRIPT
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("buildSrc/src/main/kotlin/Network.kt")).unwrap(),
+            PLUGIN
+        );
+        assert_eq!(Journal::new(&root).last().unwrap(), None);
+    }
+
+    #[test]
+    fn undo_deletes_the_update_branch_unless_files_changed_since() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        gradle_project(&root);
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        let snapshot = Snapshot::take(&root, false).unwrap();
+        git(&["checkout", "-q", "-b", "deps/update"]);
+        let catalog = root.join("gradle/libs.versions.toml");
+        let updated = CATALOG.replace("4.12.0", "4.12.1");
+        fs::write(&catalog, &updated).unwrap();
+        git(&["commit", "-q", "-am", "update"]);
+        snapshot.record("update").unwrap().unwrap();
+
+        fs::write(&catalog, "[libraries]\n").unwrap();
+        assert!(execute_undo(&root, false).is_err());
+        assert_eq!(git(&["branch", "--show-current"]), "deps/update\n");
+
+        fs::write(&catalog, &updated).unwrap();
+        execute_undo(&root, false).unwrap();
+        assert_eq!(git(&["branch", "--show-current"]), "main\n");
+        assert_eq!(git(&["branch", "--list", "deps/update"]), "");
+        assert_eq!(fs::read_to_string(&catalog).unwrap(), CATALOG);
+        assert_eq!(Journal::new(&root).last().unwrap(), None);
+    }
+
+    #[test]
+    fn undo_returns_to_a_detached_head() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        gradle_project(&root);
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(&root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}", args);
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "t"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        let start = git(&["rev-parse", "HEAD"]);
+        git(&["checkout", "-q", "--detach"]);
+
+        let snapshot = Snapshot::take(&root, false).unwrap();
+        git(&["checkout", "-q", "-b", "deps/update"]);
+        let catalog = root.join("gradle/libs.versions.toml");
+        fs::write(&catalog, CATALOG.replace("4.12.0", "4.12.1")).unwrap();
+        git(&["commit", "-q", "-am", "update"]);
+        let entry = snapshot.record("update").unwrap().unwrap();
+        assert_eq!(entry.branch, None);
+        assert_eq!(entry.head.as_deref(), Some(start.trim()));
+
+        execute_undo(&root, false).unwrap();
+        assert_eq!(git(&["branch", "--show-current"]), "");
+        assert_eq!(git(&["rev-parse", "HEAD"]), start);
+        assert_eq!(git(&["branch", "--list", "deps/update"]), "");
+        assert_eq!(fs::read_to_string(&catalog).unwrap(), CATALOG);
+        assert_eq!(Journal::new(&root).last().unwrap(), None);
     }
 }